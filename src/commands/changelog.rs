@@ -2,7 +2,10 @@ use clap::{Args, Subcommand};
 use serde::Serialize;
 
 use super::CmdResult;
-use homeboy::changelog::{self, AddItemsOutput, InitOutput, ShowOutput};
+use homeboy::changelog::{
+    self, AddItemsOutput, ChangelogQuery, GenerateOutput, InitOutput, LintOutput, ParsedChangelog,
+    ParsedShowOutput, ShowOutput,
+};
 
 #[derive(Args)]
 pub struct ChangelogArgs {
@@ -20,6 +23,22 @@ pub enum ChangelogCommand {
     Show {
         /// Component ID to show changelog for
         component_id: Option<String>,
+
+        /// Output the parsed changelog (versions, dates, subsections, items) as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Only the release with this version
+        #[arg(long, value_name = "VERSION", conflicts_with_all = ["since", "between"])]
+        version: Option<String>,
+
+        /// Releases newer than this version, plus the next section
+        #[arg(long, value_name = "VERSION", conflicts_with = "between")]
+        since: Option<String>,
+
+        /// Releases newer than FROM, up to and including TO
+        #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+        between: Option<Vec<String>>,
     },
 
     /// Add changelog items to the configured "next" section
@@ -45,6 +64,26 @@ pub enum ChangelogCommand {
         entry_type: Option<String>,
     },
 
+    /// Generate next-section entries from conventional commits
+    Generate {
+        /// Component ID
+        component_id: String,
+
+        /// Tag to read commits from (default: latest tag)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Show what would be added without writing the changelog
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Check a changelog for Keep a Changelog compliance
+    Lint {
+        /// Component ID
+        component_id: String,
+    },
+
     /// Initialize a new changelog file
     Init {
         /// Path for the changelog file (relative to component)
@@ -67,6 +106,14 @@ pub struct ChangelogShowOutput {
     pub content: String,
 }
 
+#[derive(Serialize)]
+
+pub struct HomeboyChangelogParsedOutput {
+    pub topic_label: String,
+    #[serde(flatten)]
+    pub changelog: ParsedChangelog,
+}
+
 #[derive(Serialize)]
 #[serde(tag = "command")]
 pub enum ChangelogOutput {
@@ -74,22 +121,66 @@ pub enum ChangelogOutput {
 
     ShowComponent(ShowOutput),
 
+    ShowParsed(ParsedChangelogOutput),
+
     Add(AddItemsOutput),
 
     Init(InitOutput),
+
+    Generate(GenerateOutput),
+
+    Lint(LintOutput),
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum ParsedChangelogOutput {
+    Component(ParsedShowOutput),
+    Homeboy(HomeboyChangelogParsedOutput),
+}
+
+fn show_query(
+    version: &Option<String>,
+    since: &Option<String>,
+    between: &Option<Vec<String>>,
+) -> ChangelogQuery {
+    match (version, since, between.as_deref()) {
+        (Some(version), _, _) => ChangelogQuery::Version(version.clone()),
+        (_, Some(since), _) => ChangelogQuery::Since(since.clone()),
+        (_, _, Some([from, to])) => ChangelogQuery::Between(from.clone(), to.clone()),
+        _ => ChangelogQuery::All,
+    }
+}
+
+fn parse_homeboy_changelog() -> ParsedChangelog {
+    let settings = changelog::resolve_effective_settings(None);
+    changelog::parse_changelog(HOMEBOY_CHANGELOG, &settings.next_section_aliases)
 }
 
 pub fn run_markdown(args: ChangelogArgs) -> CmdResult<String> {
     match (&args.command, args.show_self) {
         (None, true) => show_homeboy_markdown(),
-        (Some(ChangelogCommand::Show { component_id: None }), _) => show_homeboy_markdown(),
-        (Some(ChangelogCommand::Show { component_id: Some(id) }), _) => {
-            let output = changelog::show(id)?;
-            Ok((output.content, 0))
+        (
+            Some(ChangelogCommand::Show {
+                component_id,
+                version,
+                since,
+                between,
+                ..
+            }),
+            _,
+        ) => {
+            let query = show_query(version, since, between);
+            match (component_id, &query) {
+                (None, ChangelogQuery::All) => show_homeboy_markdown(),
+                (None, _) => Ok((parse_homeboy_changelog().query(&query)?.to_markdown(), 0)),
+                (Some(id), ChangelogQuery::All) => Ok((changelog::show(id)?.content, 0)),
+                (Some(id), _) => Ok((changelog::show_parsed(id, &query)?.changelog.to_markdown(), 0)),
+            }
         }
         (None, false) => Err(homeboy::Error::validation_invalid_argument(
             "command",
-            "No subcommand provided. Use a subcommand (add, init, show) or --self to view Homeboy's changelog",
+            "No subcommand provided. Use a subcommand (add, generate, init, lint, show) or --self to view Homeboy's changelog",
             None,
             Some(vec![
                 "homeboy changelog add <component_id> <message>".to_string(),
@@ -98,7 +189,13 @@ pub fn run_markdown(args: ChangelogArgs) -> CmdResult<String> {
                 "homeboy changelog show <component_id>".to_string(),
             ]),
         )),
-        (Some(ChangelogCommand::Add { .. }) | Some(ChangelogCommand::Init { .. }), _) => {
+        (
+            Some(ChangelogCommand::Add { .. })
+            | Some(ChangelogCommand::Init { .. })
+            | Some(ChangelogCommand::Generate { .. })
+            | Some(ChangelogCommand::Lint { .. }),
+            _,
+        ) => {
             Err(homeboy::Error::validation_invalid_argument(
                 "command",
                 "Markdown output is only supported for 'changelog show'",
@@ -110,8 +207,10 @@ pub fn run_markdown(args: ChangelogArgs) -> CmdResult<String> {
 }
 
 pub fn is_show_markdown(args: &ChangelogArgs) -> bool {
-    matches!(args.command, Some(ChangelogCommand::Show { .. }))
-        || (args.command.is_none() && args.show_self)
+    matches!(
+        args.command,
+        Some(ChangelogCommand::Show { json: false, .. })
+    ) || (args.command.is_none() && args.show_self)
 }
 
 pub fn run(
@@ -123,17 +222,48 @@ pub fn run(
             let (out, code) = show_homeboy_json()?;
             Ok((ChangelogOutput::Show(out), code))
         }
-        (Some(ChangelogCommand::Show { component_id: None }), _) => {
-            let (out, code) = show_homeboy_json()?;
-            Ok((ChangelogOutput::Show(out), code))
-        }
-        (Some(ChangelogCommand::Show { component_id: Some(id) }), _) => {
-            let output = changelog::show(id)?;
-            Ok((ChangelogOutput::ShowComponent(output), 0))
+        (
+            Some(ChangelogCommand::Show {
+                component_id,
+                json,
+                version,
+                since,
+                between,
+            }),
+            _,
+        ) => {
+            let query = show_query(version, since, between);
+            match (component_id, *json || !matches!(query, ChangelogQuery::All)) {
+                (None, false) => {
+                    let (out, code) = show_homeboy_json()?;
+                    Ok((ChangelogOutput::Show(out), code))
+                }
+                (Some(id), false) => {
+                    let output = changelog::show(id)?;
+                    Ok((ChangelogOutput::ShowComponent(output), 0))
+                }
+                (None, true) => {
+                    let output = HomeboyChangelogParsedOutput {
+                        topic_label: "changelog".to_string(),
+                        changelog: parse_homeboy_changelog().query(&query)?,
+                    };
+                    Ok((
+                        ChangelogOutput::ShowParsed(ParsedChangelogOutput::Homeboy(output)),
+                        0,
+                    ))
+                }
+                (Some(id), true) => {
+                    let output = changelog::show_parsed(id, &query)?;
+                    Ok((
+                        ChangelogOutput::ShowParsed(ParsedChangelogOutput::Component(output)),
+                        0,
+                    ))
+                }
+            }
         }
         (None, false) => Err(homeboy::Error::validation_invalid_argument(
             "command",
-            "No subcommand provided. Use a subcommand (add, init, show) or --self to view Homeboy's changelog",
+            "No subcommand provided. Use a subcommand (add, generate, init, lint, show) or --self to view Homeboy's changelog",
             None,
             Some(vec![
                 "homeboy changelog add <component_id> <message>".to_string(),
@@ -187,6 +317,19 @@ pub fn run(
             let output = changelog::init(id, path.as_deref(), *configure)?;
             Ok((ChangelogOutput::Init(output), 0))
         }
+        (Some(ChangelogCommand::Generate {
+            component_id,
+            since,
+            dry_run,
+        }), _) => {
+            let output = changelog::generate(component_id, since.as_deref(), *dry_run)?;
+            Ok((ChangelogOutput::Generate(output), 0))
+        }
+        (Some(ChangelogCommand::Lint { component_id }), _) => {
+            let output = changelog::lint(component_id)?;
+            let exit_code = if output.passed { 0 } else { 1 };
+            Ok((ChangelogOutput::Lint(output), exit_code))
+        }
     }
 }
 
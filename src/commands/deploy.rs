@@ -1,14 +1,22 @@
-use clap::Args;
+use clap::{Args, Subcommand};
 use serde::Serialize;
+use std::path::Path;
 
-use homeboy::deploy::{self, ComponentDeployResult, DeployConfig, DeploySummary};
+use homeboy::deploy::{
+    self, ComponentDeployResult, DeployConfig, DeploySummary, FanOutConfig, ProjectDeployResult,
+    RollbackConfig,
+};
+use homeboy::deploy_history::{self, DeployHistoryRecord};
+use homeboy::deploy_hooks::HookResult;
+use homeboy::deploy_plan::DeployPlan;
 
 use super::CmdResult;
 
 #[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct DeployArgs {
     /// Project ID
-    pub project_id: String,
+    pub project_id: Option<String>,
 
     /// JSON input spec for bulk operations
     #[arg(long)]
@@ -36,6 +44,82 @@ pub struct DeployArgs {
     /// Check component status without building or deploying
     #[arg(long)]
     pub check: bool,
+
+    /// With --check or --outdated, compare file hashes against the server
+    #[arg(long)]
+    pub deep: bool,
+
+    /// Build and upload up to N components concurrently
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub parallel: usize,
+
+    /// Deploy the --component to every project that uses it (build once)
+    #[arg(long, conflicts_with_all = ["project_id", "all", "check", "deep"])]
+    pub all_projects: bool,
+
+    /// Replace an existing remote deploy lock held by another deploy
+    #[arg(long)]
+    pub force_unlock: bool,
+
+    /// Build and write a reviewable deploy plan to this file instead of deploying
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["dry_run", "check", "all_projects"])]
+    pub plan_out: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<DeployCommand>,
+}
+
+#[derive(Subcommand)]
+pub enum DeployCommand {
+    /// Roll back components to a previously deployed release or backup
+    Rollback {
+        /// Project ID
+        project_id: String,
+
+        /// Component ID (default: all project components)
+        component_id: Option<String>,
+
+        /// Release directory name or version to restore (default: previous release)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Replace an existing remote deploy lock held by another deploy
+        #[arg(long)]
+        force_unlock: bool,
+    },
+    /// Deploy exactly what a saved plan (from --plan-out) describes
+    Apply {
+        /// Plan file written by `deploy --plan-out`
+        plan: String,
+
+        /// Replace an existing remote deploy lock held by another deploy
+        #[arg(long)]
+        force_unlock: bool,
+    },
+    /// Show recorded deploys for a project (newest first)
+    History {
+        /// Project ID
+        project_id: String,
+
+        /// Only show deploys that included this component
+        #[arg(short = 'c', long = "component")]
+        component_id: Option<String>,
+
+        /// Maximum number of records to show
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum DeployCommandOutput {
+    Run(DeployOutput),
+    Rollback(DeployRollbackOutput),
+    History(DeployHistoryOutput),
+    FanOut(DeployFanOutOutput),
+    Plan(DeployPlanOutput),
+    Apply(DeployApplyOutput),
 }
 
 #[derive(Serialize)]
@@ -47,20 +131,213 @@ pub struct DeployOutput {
     pub outdated: bool,
     pub dry_run: bool,
     pub check: bool,
+    pub deep: bool,
+    pub results: Vec<ComponentDeployResult>,
+    pub summary: DeploySummary,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<HookResult>,
+}
+
+#[derive(Serialize)]
+
+pub struct DeployFanOutOutput {
+    pub command: String,
+    pub component_id: String,
+    pub outdated: bool,
+    pub dry_run: bool,
+    pub local_version: Option<String>,
+    pub build_exit_code: Option<i32>,
+    pub projects: Vec<ProjectDeployResult>,
+    pub summary: DeploySummary,
+}
+
+#[derive(Serialize)]
+
+pub struct DeployPlanOutput {
+    pub command: String,
+    pub plan_path: String,
+    pub plan: DeployPlan,
+}
+
+#[derive(Serialize)]
+
+pub struct DeployApplyOutput {
+    pub command: String,
+    pub project_id: String,
+    pub plan_path: String,
     pub results: Vec<ComponentDeployResult>,
     pub summary: DeploySummary,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<HookResult>,
+}
+
+#[derive(Serialize)]
+
+pub struct DeployRollbackOutput {
+    pub command: String,
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    pub results: Vec<ComponentDeployResult>,
+    pub summary: DeploySummary,
+}
+
+#[derive(Serialize)]
+
+pub struct DeployHistoryOutput {
+    pub command: String,
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_id: Option<String>,
+    pub records: Vec<DeployHistoryRecord>,
+}
+
+pub fn run(
+    args: DeployArgs,
+    _global: &crate::commands::GlobalArgs,
+) -> CmdResult<DeployCommandOutput> {
+    match args.command {
+        Some(DeployCommand::Rollback {
+            project_id,
+            component_id,
+            to,
+            force_unlock,
+        }) => {
+            let (output, exit_code) = run_rollback(project_id, component_id, to, force_unlock)?;
+            Ok((DeployCommandOutput::Rollback(output), exit_code))
+        }
+        Some(DeployCommand::Apply { plan, force_unlock }) => {
+            let (output, exit_code) = run_apply(plan, force_unlock)?;
+            Ok((DeployCommandOutput::Apply(output), exit_code))
+        }
+        Some(DeployCommand::History {
+            project_id,
+            component_id,
+            limit,
+        }) => {
+            homeboy::project::load(&project_id)?;
+            let records = deploy_history::list(&project_id, component_id.as_deref(), limit)?;
+            Ok((
+                DeployCommandOutput::History(DeployHistoryOutput {
+                    command: "deploy.history".to_string(),
+                    project_id,
+                    component_id,
+                    records,
+                }),
+                0,
+            ))
+        }
+        None if args.all_projects => {
+            let (output, exit_code) = run_fan_out(args)?;
+            Ok((DeployCommandOutput::FanOut(output), exit_code))
+        }
+        None if args.plan_out.is_some() => {
+            let output = run_plan(args)?;
+            Ok((DeployCommandOutput::Plan(output), 0))
+        }
+        None => {
+            let (output, exit_code) = run_deploy(args)?;
+            Ok((DeployCommandOutput::Run(output), exit_code))
+        }
+    }
+}
+
+fn run_rollback(
+    project_id: String,
+    component_id: Option<String>,
+    to: Option<String>,
+    force_unlock: bool,
+) -> CmdResult<DeployRollbackOutput> {
+    let config = RollbackConfig {
+        component_ids: component_id.into_iter().collect(),
+        to: to.clone(),
+        force_unlock,
+    };
+
+    let result = deploy::rollback(&project_id, &config)?;
+    let exit_code = if result.summary.failed > 0 { 1 } else { 0 };
+
+    Ok((
+        DeployRollbackOutput {
+            command: "deploy.rollback".to_string(),
+            project_id,
+            to,
+            results: result.results,
+            summary: result.summary,
+        },
+        exit_code,
+    ))
+}
+
+fn run_fan_out(args: DeployArgs) -> CmdResult<DeployFanOutOutput> {
+    let mut component_ids = args.component_ids.clone();
+    component_ids.extend(args.component_flags.iter().cloned());
+
+    let component_id = match component_ids.as_slice() {
+        [id] => id.clone(),
+        [] => {
+            return Err(homeboy::Error::validation_missing_argument(vec![
+                "component".to_string(),
+            ]))
+        }
+        _ => {
+            return Err(homeboy::Error::validation_invalid_argument(
+                "component",
+                "--all-projects deploys exactly one component",
+                None,
+                Some(component_ids),
+            ))
+        }
+    };
+
+    let config = FanOutConfig {
+        component_id,
+        outdated: args.outdated,
+        dry_run: args.dry_run,
+        force_unlock: args.force_unlock,
+    };
+
+    let result = deploy::deploy_to_all_projects(&config)?;
+    let hook_failed = result
+        .projects
+        .iter()
+        .any(|p| p.hooks.iter().any(|h| !h.success));
+    let exit_code = if result.summary.failed > 0 || hook_failed {
+        1
+    } else {
+        0
+    };
+
+    Ok((
+        DeployFanOutOutput {
+            command: "deploy.all_projects".to_string(),
+            component_id: result.component_id,
+            outdated: args.outdated,
+            dry_run: args.dry_run,
+            local_version: result.local_version,
+            build_exit_code: result.build_exit_code,
+            projects: result.projects,
+            summary: result.summary,
+        },
+        exit_code,
+    ))
 }
 
-pub fn run(mut args: DeployArgs, _global: &crate::commands::GlobalArgs) -> CmdResult<DeployOutput> {
+/// Validate run arguments and build the core deploy config.
+fn deploy_config(args: &mut DeployArgs) -> homeboy::Result<(String, DeployConfig)> {
+    let project_id = args.project_id.clone().ok_or_else(|| {
+        homeboy::Error::validation_missing_argument(vec!["project_id".to_string()])
+    })?;
+
     // Check for common subcommand mistakes
     let subcommand_hints = ["status", "list", "show", "help"];
-    if subcommand_hints.contains(&args.project_id.as_str()) {
+    if subcommand_hints.contains(&project_id.as_str()) {
         return Err(homeboy::Error::validation_invalid_argument(
             "project_id",
             format!(
-                "'{}' looks like a subcommand, but 'deploy' doesn't have subcommands. \
+                "'{}' looks like a subcommand, but 'deploy' only has the 'rollback', 'apply' and 'history' subcommands. \
                   Usage: homeboy deploy <projectId> [componentIds...] [--all]",
-                args.project_id
+                project_id
             ),
             None,
             None,
@@ -69,13 +346,13 @@ pub fn run(mut args: DeployArgs, _global: &crate::commands::GlobalArgs) -> CmdRe
 
     // Check if user provided component ID where project ID expected (reversed argument order)
     let available_components = homeboy::component::list_ids().unwrap_or_default();
-    if available_components.contains(&args.project_id) {
+    if available_components.contains(&project_id) {
         return Err(homeboy::Error::validation_invalid_argument(
             "project_id",
             format!(
                 "'{}' is a component, not a project. \
                  Did you mean: homeboy deploy <project> {}",
-                args.project_id, args.project_id
+                project_id, project_id
             ),
             None,
             Some(vec![
@@ -100,21 +377,74 @@ pub fn run(mut args: DeployArgs, _global: &crate::commands::GlobalArgs) -> CmdRe
         outdated: args.outdated,
         dry_run: args.dry_run,
         check: args.check,
+        deep: args.deep,
+        parallel: args.parallel,
+        force_unlock: args.force_unlock,
     };
 
-    let result = deploy::run(&args.project_id, &config)?;
-    let exit_code = if result.summary.failed > 0 { 1 } else { 0 };
+    Ok((project_id, config))
+}
+
+fn run_plan(mut args: DeployArgs) -> homeboy::Result<DeployPlanOutput> {
+    let (project_id, config) = deploy_config(&mut args)?;
+    let plan_path = args.plan_out.clone().unwrap_or_default();
+
+    let plan = deploy::plan(&project_id, &config)?;
+    plan.save(Path::new(&plan_path))?;
+
+    Ok(DeployPlanOutput {
+        command: "deploy.plan".to_string(),
+        plan_path,
+        plan,
+    })
+}
+
+fn run_apply(plan_path: String, force_unlock: bool) -> CmdResult<DeployApplyOutput> {
+    let plan = DeployPlan::load(Path::new(&plan_path))?;
+    let result = deploy::apply(&plan, force_unlock)?;
+    let hook_failed = result.hooks.iter().any(|h| !h.success);
+    let exit_code = if result.summary.failed > 0 || hook_failed {
+        1
+    } else {
+        0
+    };
+
+    Ok((
+        DeployApplyOutput {
+            command: "deploy.apply".to_string(),
+            project_id: plan.project_id,
+            plan_path,
+            results: result.results,
+            summary: result.summary,
+            hooks: result.hooks,
+        },
+        exit_code,
+    ))
+}
+
+fn run_deploy(mut args: DeployArgs) -> CmdResult<DeployOutput> {
+    let (project_id, config) = deploy_config(&mut args)?;
+
+    let result = deploy::run(&project_id, &config)?;
+    let hook_failed = result.hooks.iter().any(|h| !h.success);
+    let exit_code = if result.summary.failed > 0 || hook_failed {
+        1
+    } else {
+        0
+    };
 
     Ok((
         DeployOutput {
             command: "deploy.run".to_string(),
-            project_id: args.project_id,
+            project_id,
             all: args.all,
             outdated: args.outdated,
             dry_run: args.dry_run,
             check: args.check,
+            deep: args.deep,
             results: result.results,
             summary: result.summary,
+            hooks: result.hooks,
         },
         exit_code,
     ))
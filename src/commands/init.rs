@@ -11,7 +11,6 @@ use homeboy::module::{
 use homeboy::project::{self, Project};
 use homeboy::server::{self, Server};
 use homeboy::{changelog, git, version};
-use std::fs;
 use std::path::PathBuf;
 
 use super::CmdResult;
@@ -298,14 +297,12 @@ fn resolve_changelog_snapshots(
         None => return (None, None),
     };
 
-    let changelog_path = match changelog::resolve_changelog_path(component) {
-        Ok(path) => path,
-        Err(_) => return (None, None),
-    };
-    let content = match fs::read_to_string(&changelog_path) {
-        Ok(content) => content,
+    let changelog_file = match changelog::read_changelog(component) {
+        Ok(file) => file,
         Err(_) => return (None, None),
     };
+    let changelog_path = changelog_file.path.clone();
+    let content = changelog_file.markdown();
     let settings = changelog::resolve_effective_settings(Some(component));
 
     let changelog_snapshot = build_changelog_snapshot(&content, &changelog_path, &settings);
@@ -419,7 +416,7 @@ fn extract_first_bullet(lines: &[&str], start: usize) -> Option<String> {
 }
 
 fn parse_version_label(label: &str) -> Option<String> {
-    let re = regex::Regex::new(r"\[?(\d+\.\d+\.\d+)\]?").ok()?;
+    let re = regex::Regex::new(&format!(r"\[?({})\]?", homeboy::version::VERSION_REGEX)).ok()?;
     re.captures(label)
         .and_then(|caps| caps.get(1))
         .map(|m| m.as_str().to_string())
@@ -435,12 +432,10 @@ fn validate_version_targets(components: &[Component]) -> Vec<String> {
     for comp in components {
         if let Some(targets) = &comp.version_targets {
             for target in targets {
-                if target.pattern.is_none()
-                    && version::default_pattern_for_file(&target.file).is_none()
-                {
+                if let Err(err) = version::resolve_target_locator(target) {
                     warnings.push(format!(
-                        "Component '{}' has version target '{}' with no pattern and no module default. Run: homeboy component set {} --version-targets @file.json",
-                        comp.id, target.file, comp.id
+                        "Component '{}' has version target '{}': {}. Run: homeboy component set {} --version-targets @file.json",
+                        comp.id, target.file, err.message, comp.id
                     ));
                 }
             }
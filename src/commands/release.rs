@@ -2,6 +2,7 @@ use clap::{Args, Subcommand};
 use serde::Serialize;
 
 use homeboy::release::{self, ReleasePlan, ReleaseRun};
+use homeboy::release_runs::{self, ReleaseRunSummary};
 
 use super::CmdResult;
 
@@ -29,6 +30,20 @@ enum ReleaseCommand {
         /// Component ID to run
         component_id: String,
     },
+    /// Resume a failed release run, skipping steps that already succeeded
+    Resume {
+        /// Run ID printed by `release run`
+        run_id: String,
+    },
+    /// List saved release runs for a component
+    Runs {
+        /// Component ID
+        component_id: String,
+
+        /// Maximum number of runs to show
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
 }
 
 #[derive(Serialize)]
@@ -39,6 +54,13 @@ pub enum ReleaseOutput {
     Plan { plan: ReleasePlan },
     #[serde(rename = "release.run")]
     Run { run: ReleaseRun },
+    #[serde(rename = "release.resume")]
+    Resume { run: ReleaseRun },
+    #[serde(rename = "release.runs")]
+    Runs {
+        component_id: String,
+        runs: Vec<ReleaseRunSummary>,
+    },
 }
 
 pub fn run(args: ReleaseArgs, _global: &crate::commands::GlobalArgs) -> CmdResult<ReleaseOutput> {
@@ -52,6 +74,20 @@ pub fn run(args: ReleaseArgs, _global: &crate::commands::GlobalArgs) -> CmdResul
                 let run = release::run(&component_id, None)?;
                 Ok((ReleaseOutput::Run { run }, 0))
             }
+            ReleaseCommand::Resume { run_id } => {
+                let run = release::resume(&run_id)?;
+                Ok((ReleaseOutput::Resume { run }, 0))
+            }
+            ReleaseCommand::Runs {
+                component_id,
+                limit,
+            } => {
+                let runs = release_runs::list(&component_id, Some(limit))?
+                    .iter()
+                    .map(ReleaseRunSummary::from)
+                    .collect();
+                Ok((ReleaseOutput::Runs { component_id, runs }, 0))
+            }
         }
     } else if let Some(component_id) = args.component_id {
         let run = release::run(&component_id, None)?;
@@ -59,7 +95,7 @@ pub fn run(args: ReleaseArgs, _global: &crate::commands::GlobalArgs) -> CmdResul
     } else {
         Err(homeboy::Error::validation_invalid_argument(
             "input",
-            "Provide component ID or use `release plan|run <component>`",
+            "Provide component ID or use `release plan|run|runs <component>`",
             None,
             None,
         ))
@@ -3,8 +3,10 @@ use serde::Serialize;
 
 use homeboy::git::{commit, CommitOptions};
 use homeboy::version::{
-    bump_version, increment_version, read_version, set_version, VersionTargetInfo,
+    bump_version, next_version, read_version, resolve_bump_type, set_version, AutoBump,
+    VersionTargetInfo,
 };
+use homeboy::version_constraints::{self, ProjectConstraintReport};
 
 use super::CmdResult;
 
@@ -25,6 +27,7 @@ pub enum VersionOutput {
     Show(VersionShowOutput),
     Bump(VersionBumpOutput),
     Set(VersionSetOutput),
+    Check(VersionCheckOutput),
 }
 
 #[derive(Args)]
@@ -50,10 +53,14 @@ enum VersionCommand {
         #[arg(long)]
         no_commit: bool,
 
+        /// Prerelease identifier for pre* bumps (e.g. beta -> 1.3.0-beta.0)
+        #[arg(long, value_name = "ID")]
+        preid: Option<String>,
+
         /// Component ID
         component_id: Option<String>,
 
-        /// Version bump type (patch, minor, major)
+        /// Version bump type
         #[arg(last = true)]
         bump_type: BumpType,
     },
@@ -66,6 +73,12 @@ enum VersionCommand {
         /// New version (e.g., 1.2.3)
         new_version: String,
     },
+    /// Check components' dependsOn ranges against each project's component versions
+    Check {
+        /// Only check this project (default: all projects)
+        #[arg(long)]
+        project: Option<String>,
+    },
 }
 
 #[derive(Clone, ValueEnum)]
@@ -73,6 +86,15 @@ enum BumpType {
     Patch,
     Minor,
     Major,
+    /// Next prerelease (1.2.0-beta.0 -> 1.2.0-beta.1, 1.2.0 -> 1.2.1-0)
+    Prerelease,
+    Prepatch,
+    Preminor,
+    Premajor,
+    /// Drop the prerelease (1.3.0-rc.2 -> 1.3.0)
+    Release,
+    /// Infer patch/minor/major from conventional commits since the last tag
+    Auto,
 }
 
 impl BumpType {
@@ -81,6 +103,12 @@ impl BumpType {
             BumpType::Patch => "patch",
             BumpType::Minor => "minor",
             BumpType::Major => "major",
+            BumpType::Prerelease => "prerelease",
+            BumpType::Prepatch => "prepatch",
+            BumpType::Preminor => "preminor",
+            BumpType::Premajor => "premajor",
+            BumpType::Release => "release",
+            BumpType::Auto => "auto",
         }
     }
 }
@@ -108,6 +136,10 @@ pub struct VersionBumpOutput {
     changelog_finalized: bool,
     changelog_changed: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    changelog_mirror_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auto_bump: Option<AutoBump>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     dry_run: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     git_commit: Option<GitCommitInfo>,
@@ -126,9 +158,21 @@ pub struct VersionSetOutput {
     changelog_finalized: bool,
     changelog_changed: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    changelog_mirror_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     git_commit: Option<GitCommitInfo>,
 }
 
+#[derive(Serialize)]
+
+pub struct VersionCheckOutput {
+    command: String,
+    projects: Vec<ProjectConstraintReport>,
+    checked: usize,
+    unsatisfied: usize,
+    passed: bool,
+}
+
 pub fn run(args: VersionArgs, _global: &crate::commands::GlobalArgs) -> CmdResult<VersionOutput> {
     match args.command {
         VersionCommand::Show { component_id } => {
@@ -147,21 +191,31 @@ pub fn run(args: VersionArgs, _global: &crate::commands::GlobalArgs) -> CmdResul
         VersionCommand::Bump {
             dry_run,
             no_commit,
+            preid,
             bump_type,
             component_id,
         } => {
             if dry_run {
                 let info = read_version(component_id.as_deref())?;
-
-                let new_version =
-                    increment_version(&info.version, bump_type.as_str()).ok_or_else(|| {
-                        homeboy::error::Error::validation_invalid_argument(
-                            "version",
-                            format!("Invalid version format: {}", info.version),
-                            None,
-                            Some(vec![info.version.clone()]),
-                        )
-                    })?;
+                let component = component_id
+                    .as_deref()
+                    .map(homeboy::component::load)
+                    .transpose()?;
+                let scheme = component
+                    .as_ref()
+                    .and_then(|c| c.version_scheme)
+                    .unwrap_or_default();
+
+                let (resolved, auto_bump) = match &component {
+                    Some(component) => resolve_bump_type(
+                        component,
+                        &info.version,
+                        bump_type.as_str(),
+                        preid.as_deref(),
+                    )?,
+                    None => (bump_type.as_str().to_string(), None),
+                };
+                let new_version = next_version(&info.version, &resolved, preid.as_deref(), scheme)?;
 
                 eprintln!(
                     "[version] [dry-run] Would bump {} -> {}",
@@ -178,6 +232,8 @@ pub fn run(args: VersionArgs, _global: &crate::commands::GlobalArgs) -> CmdResul
                         changelog_path: String::new(),
                         changelog_finalized: false,
                         changelog_changed: false,
+                        changelog_mirror_path: None,
+                        auto_bump,
                         dry_run: Some(true),
                         git_commit: None,
                     }),
@@ -185,7 +241,11 @@ pub fn run(args: VersionArgs, _global: &crate::commands::GlobalArgs) -> CmdResul
                 ));
             }
 
-            let result = bump_version(component_id.as_deref(), bump_type.as_str())?;
+            let result = bump_version(
+                component_id.as_deref(),
+                bump_type.as_str(),
+                preid.as_deref(),
+            )?;
 
             // Auto-commit unless --no-commit
             let git_commit = if no_commit {
@@ -198,6 +258,7 @@ pub fn run(args: VersionArgs, _global: &crate::commands::GlobalArgs) -> CmdResul
                 if !result.changelog_path.is_empty() {
                     files_to_stage.push(result.changelog_path.clone());
                 }
+                files_to_stage.extend(result.changelog_mirror_path.clone());
 
                 let commit_message = format!("release: v{}", result.new_version);
 
@@ -252,6 +313,8 @@ pub fn run(args: VersionArgs, _global: &crate::commands::GlobalArgs) -> CmdResul
                     changelog_path: result.changelog_path,
                     changelog_finalized: result.changelog_finalized,
                     changelog_changed: result.changelog_changed,
+                    changelog_mirror_path: result.changelog_mirror_path,
+                    auto_bump: result.auto_bump,
                     dry_run: Some(false),
                     git_commit,
                 }),
@@ -272,6 +335,7 @@ pub fn run(args: VersionArgs, _global: &crate::commands::GlobalArgs) -> CmdResul
             if !result.changelog_path.is_empty() {
                 files_to_stage.push(result.changelog_path.clone());
             }
+            files_to_stage.extend(result.changelog_mirror_path.clone());
 
             let commit_message = format!("release: v{}", result.new_version);
 
@@ -325,11 +389,33 @@ pub fn run(args: VersionArgs, _global: &crate::commands::GlobalArgs) -> CmdResul
                     changelog_path: result.changelog_path,
                     changelog_finalized: result.changelog_finalized,
                     changelog_changed: result.changelog_changed,
+                    changelog_mirror_path: result.changelog_mirror_path,
                     git_commit,
                 }),
                 0,
             ))
         }
+        VersionCommand::Check { project } => {
+            let projects = version_constraints::check_projects(project.as_deref())?;
+            let checked = projects.iter().map(|p| p.checks.len()).sum();
+            let unsatisfied = projects
+                .iter()
+                .flat_map(|p| &p.checks)
+                .filter(|check| !check.satisfied)
+                .count();
+            let passed = unsatisfied == 0;
+
+            Ok((
+                VersionOutput::Check(VersionCheckOutput {
+                    command: "version.check".to_string(),
+                    projects,
+                    checked,
+                    unsatisfied,
+                    passed,
+                }),
+                if passed { 0 } else { 1 },
+            ))
+        }
     }
 }
 
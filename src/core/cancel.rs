@@ -0,0 +1,162 @@
+//! Cancellation of child processes started by a pipeline step.
+//!
+//! A step attempt with a timeout runs inside a [`CancelHandle`] scope. Commands
+//! spawned through [`output`] and [`status`] on that thread are started in
+//! their own process group and registered with the handle, so a timed-out
+//! attempt can be terminated instead of left running. Outside a scope both
+//! functions behave like `Command::output` and `Command::status`.
+
+use std::cell::RefCell;
+use std::io;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::sync::{Arc, Mutex};
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelHandle>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct CancelState {
+    cancelled: bool,
+    /// Process (group) IDs of running children
+    children: Vec<u32>,
+}
+
+/// Shared cancellation state for one step attempt.
+#[derive(Clone, Default)]
+pub(crate) struct CancelHandle(Arc<Mutex<CancelState>>);
+
+impl CancelHandle {
+    /// Run `f` with commands spawned on the current thread governed by this handle.
+    pub(crate) fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        let previous = CURRENT.with(|c| c.borrow_mut().replace(self.clone()));
+        let result = f();
+        CURRENT.with(|c| *c.borrow_mut() = previous);
+        result
+    }
+
+    /// Refuse further spawns and signal every running child's process group:
+    /// `SIGTERM`, or `SIGKILL` when `force` is set.
+    pub(crate) fn cancel(&self, force: bool) {
+        let mut state = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        state.cancelled = true;
+        for pid in &state.children {
+            kill_group(*pid, force);
+        }
+    }
+
+    /// Spawn `cmd` unless cancelled, registering the child for [`cancel`](Self::cancel).
+    fn spawn(&self, cmd: &mut Command) -> io::Result<Child> {
+        let mut state = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if state.cancelled {
+            return Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "step was cancelled",
+            ));
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.process_group(0);
+        }
+
+        let child = cmd.spawn()?;
+        state.children.push(child.id());
+        Ok(child)
+    }
+
+    fn finished(&self, pid: u32) {
+        let mut state = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        state.children.retain(|child| *child != pid);
+    }
+}
+
+fn current() -> Option<CancelHandle> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+#[cfg(unix)]
+fn kill_group(pid: u32, force: bool) {
+    let signal = if force { "-KILL" } else { "-TERM" };
+    let _ = Command::new("kill")
+        .args([signal, "--", &format!("-{}", pid)])
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(not(unix))]
+fn kill_group(pid: u32, force: bool) {
+    let mut cmd = Command::new("taskkill");
+    cmd.args(["/T", "/PID", &pid.to_string()]);
+    if force {
+        cmd.arg("/F");
+    }
+    let _ = cmd.stdout(Stdio::null()).stderr(Stdio::null()).status();
+}
+
+/// `Command::output`, cancellable inside a [`CancelHandle`] scope.
+pub(crate) fn output(cmd: &mut Command) -> io::Result<Output> {
+    output_with_stdin(cmd.stdin(Stdio::null()))
+}
+
+/// Like [`output`], keeping the stdin already configured on `cmd`.
+pub(crate) fn output_with_stdin(cmd: &mut Command) -> io::Result<Output> {
+    let Some(handle) = current() else {
+        return cmd.output();
+    };
+
+    let child = handle.spawn(cmd.stdout(Stdio::piped()).stderr(Stdio::piped()))?;
+    let pid = child.id();
+    let output = child.wait_with_output();
+    handle.finished(pid);
+    output
+}
+
+/// `Command::status`, cancellable inside a [`CancelHandle`] scope.
+pub(crate) fn status(cmd: &mut Command) -> io::Result<ExitStatus> {
+    let Some(handle) = current() else {
+        return cmd.status();
+    };
+
+    let mut child = handle.spawn(cmd)?;
+    let pid = child.id();
+    let status = child.wait();
+    handle.finished(pid);
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn cancel_terminates_running_children_and_refuses_new_ones() {
+        let handle = CancelHandle::default();
+        let worker = handle.clone();
+        let started = Instant::now();
+
+        let thread = std::thread::spawn(move || {
+            worker.scope(|| {
+                let first = output(Command::new("sh").args(["-c", "sleep 5"]));
+                let second = output(&mut Command::new("true"));
+                (first, second)
+            })
+        });
+
+        std::thread::sleep(Duration::from_millis(200));
+        handle.cancel(false);
+        let (first, second) = thread.join().unwrap();
+
+        assert!(!first.unwrap().status.success());
+        assert_eq!(second.unwrap_err().kind(), io::ErrorKind::Interrupted);
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn output_outside_scope_is_plain() {
+        let output = output(Command::new("sh").args(["-c", "echo hi"])).unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hi");
+    }
+}
@@ -1,13 +1,16 @@
 use chrono::Local;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
+use crate::changelog_format::{self, ChangelogFile, ChangelogFormat};
 use crate::component::{self, Component};
 use crate::config::read_json_spec_to_string;
 use crate::core::local_files::{self, FileSystem};
 use crate::core::version;
 use crate::error::{Error, Result};
+use crate::git::{self, CommitCategory, CommitInfo};
 use crate::project;
 
 const DEFAULT_NEXT_SECTION_LABEL: &str = "Unreleased";
@@ -151,6 +154,80 @@ pub fn resolve_changelog_path(component: &Component) -> Result<PathBuf> {
     resolve_target_path(&component.local_path, target)
 }
 
+/// Copy the section finalized as `version` in `finalized` (the primary
+/// changelog's markdown) into the component's `changelog_mirror_target`,
+/// above the mirror's latest release. Returns the mirror path and whether it
+/// changed; a section already present for `version` is left alone.
+pub fn mirror_finalized_section(
+    component: &Component,
+    finalized: &str,
+    version: &str,
+) -> Result<Option<(PathBuf, bool)>> {
+    let Some(target) = component.changelog_mirror_target.as_deref() else {
+        return Ok(None);
+    };
+
+    let section = parse_changelog(finalized, &[])
+        .release(version)
+        .cloned()
+        .ok_or_else(|| {
+            Error::internal_unexpected(format!(
+                "Finalized changelog has no section for {}",
+                version
+            ))
+        })?;
+
+    let path = resolve_target_path(&component.local_path, target)?;
+    let file = ChangelogFile::read(&path, changelog_format::resolve_format(component, target))?;
+    let markdown = file.markdown();
+    if parse_changelog(&markdown, &[]).release(version).is_some() {
+        return Ok(Some((path, false)));
+    }
+
+    let out = insert_release_section(&markdown, &section.to_markdown());
+    let changed = file.write(&out)?;
+    Ok(Some((path, changed)))
+}
+
+/// Insert a `##` section before the first versioned section (or at the end).
+fn insert_release_section(markdown: &str, section: &str) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let insert_at = lines
+        .iter()
+        .position(|line| {
+            line.trim()
+                .strip_prefix("## ")
+                .is_some_and(|label| extract_version_from_heading(label).is_some())
+        })
+        .unwrap_or(lines.len());
+
+    let mut out = String::new();
+    for line in &lines[..insert_at] {
+        out.push_str(line);
+        out.push('\n');
+    }
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+    out.push_str(section.trim_end());
+    out.push('\n');
+    if insert_at < lines.len() {
+        out.push('\n');
+        for line in &lines[insert_at..] {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Read the component's changelog through its format.
+pub fn read_changelog(component: &Component) -> Result<ChangelogFile> {
+    let path = resolve_changelog_path(component)?;
+    let target = component.changelog_target.as_deref().unwrap_or_default();
+    ChangelogFile::read(&path, changelog_format::resolve_format(component, target))
+}
+
 fn resolve_target_path(local_path: &str, file: &str) -> Result<PathBuf> {
     let path = if file.starts_with('/') {
         PathBuf::from(file)
@@ -229,19 +306,16 @@ pub fn read_and_add_next_section_item(
     settings: &EffectiveChangelogSettings,
     message: &str,
 ) -> Result<(PathBuf, bool)> {
-    let path = resolve_changelog_path(component)?;
-    let content = fs::read_to_string(&path)
-        .map_err(|e| Error::internal_io(e.to_string(), Some("read changelog".to_string())))?;
+    let file = read_changelog(component)?;
 
     let (new_content, changed) =
-        add_next_section_item(&content, &settings.next_section_aliases, message)?;
+        add_next_section_item(&file.markdown(), &settings.next_section_aliases, message)?;
 
     if changed {
-        fs::write(&path, new_content)
-            .map_err(|e| Error::internal_io(e.to_string(), Some("write changelog".to_string())))?;
+        file.write(&new_content)?;
     }
 
-    Ok((path, changed))
+    Ok((file.path, changed))
 }
 
 pub fn read_and_add_next_section_items(
@@ -249,19 +323,16 @@ pub fn read_and_add_next_section_items(
     settings: &EffectiveChangelogSettings,
     messages: &[String],
 ) -> Result<(PathBuf, bool, usize)> {
-    let path = resolve_changelog_path(component)?;
-    let content = fs::read_to_string(&path)
-        .map_err(|e| Error::internal_io(e.to_string(), Some("read changelog".to_string())))?;
+    let file = read_changelog(component)?;
 
     let (new_content, changed, items_added) =
-        add_next_section_items(&content, &settings.next_section_aliases, messages)?;
+        add_next_section_items(&file.markdown(), &settings.next_section_aliases, messages)?;
 
     if changed {
-        fs::write(&path, new_content)
-            .map_err(|e| Error::internal_io(e.to_string(), Some("write changelog".to_string())))?;
+        file.write(&new_content)?;
     }
 
-    Ok((path, changed, items_added))
+    Ok((file.path, changed, items_added))
 }
 
 pub fn read_and_add_next_section_items_typed(
@@ -270,11 +341,9 @@ pub fn read_and_add_next_section_items_typed(
     messages: &[String],
     entry_type: &str,
 ) -> Result<(PathBuf, bool, usize)> {
-    let path = resolve_changelog_path(component)?;
-    let content = fs::read_to_string(&path)
-        .map_err(|e| Error::internal_io(e.to_string(), Some("read changelog".to_string())))?;
+    let file = read_changelog(component)?;
 
-    let (with_section, _) = ensure_next_section(&content, &settings.next_section_aliases)?;
+    let (with_section, _) = ensure_next_section(&file.markdown(), &settings.next_section_aliases)?;
     let mut current_content = with_section;
     let mut items_added = 0;
     let mut changed = false;
@@ -304,11 +373,10 @@ pub fn read_and_add_next_section_items_typed(
     }
 
     if changed {
-        fs::write(&path, &current_content)
-            .map_err(|e| Error::internal_io(e.to_string(), Some("write changelog".to_string())))?;
+        file.write(&current_content)?;
     }
 
-    Ok((path, changed, items_added))
+    Ok((file.path, changed, items_added))
 }
 
 #[derive(Debug, PartialEq)]
@@ -584,7 +652,8 @@ fn ensure_next_section(content: &str, aliases: &[String]) -> Result<(String, boo
 /// - "[0.1.0] - 2025-01-14" -> Some("0.1.0")
 /// - "Unreleased" -> None
 fn extract_version_from_heading(label: &str) -> Option<String> {
-    let semver_pattern = regex::Regex::new(r"\[?(\d+\.\d+\.\d+)\]?").ok()?;
+    let semver_pattern =
+        regex::Regex::new(&format!(r"\[?({})\]?", crate::version::VERSION_REGEX)).ok()?;
     semver_pattern
         .captures(label)
         .and_then(|caps| caps.get(1))
@@ -595,16 +664,11 @@ fn extract_version_from_heading(label: &str) -> Option<String> {
 /// Supports Keep a Changelog format: `## [X.Y.Z] - YYYY-MM-DD`
 /// Returns None if no version section is found.
 pub fn get_latest_finalized_version(content: &str) -> Option<String> {
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with("## ") {
-            let label = trimmed.trim_start_matches("## ").trim();
-            if let Some(version) = extract_version_from_heading(label) {
-                return Some(version);
-            }
-        }
-    }
-    None
+    parse_changelog(content, &[])
+        .releases
+        .into_iter()
+        .next()
+        .and_then(|release| release.version)
 }
 
 fn append_item_to_next_section(
@@ -973,6 +1037,538 @@ pub fn add_items(
     })
 }
 
+// === Changelog Parsing ===
+
+/// A changelog parsed into its `##` sections.
+#[derive(Debug, Clone, Default, Serialize)]
+
+pub struct ParsedChangelog {
+    /// The configured "next" section, if present
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unreleased: Option<ChangelogSection>,
+    /// Versioned sections in file order (newest first in Keep a Changelog files)
+    pub releases: Vec<ChangelogSection>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+
+pub struct ChangelogSection {
+    pub heading: String,
+    /// 1-based line number of the `##` heading
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Bullets before the first `###` subsection
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub subsections: Vec<ChangelogSubsection>,
+    /// Section markdown below the heading, as written
+    #[serde(skip)]
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+
+pub struct ChangelogSubsection {
+    pub name: String,
+    pub line: usize,
+    pub items: Vec<String>,
+}
+
+impl ChangelogSection {
+    /// Section markdown including its `##` heading.
+    pub fn to_markdown(&self) -> String {
+        let body = self.body.trim();
+        if body.is_empty() {
+            format!("## {}\n", self.heading)
+        } else {
+            format!("## {}\n\n{}\n", self.heading, body)
+        }
+    }
+
+    /// Section markdown below the heading, or `None` if it has no content.
+    pub fn notes(&self) -> Option<String> {
+        let body = self.body.trim();
+        (!body.is_empty()).then(|| body.to_string())
+    }
+
+    fn push_item(&mut self, item: String) {
+        match self.subsections.last_mut() {
+            Some(subsection) => subsection.items.push(item),
+            None => self.items.push(item),
+        }
+    }
+
+    fn last_item_mut(&mut self) -> Option<&mut String> {
+        match self.subsections.last_mut() {
+            Some(subsection) => subsection.items.last_mut(),
+            None => self.items.last_mut(),
+        }
+    }
+}
+
+/// Which releases `ParsedChangelog::query` selects.
+#[derive(Debug, Clone, Default)]
+pub enum ChangelogQuery {
+    /// Every section, including the next section
+    #[default]
+    All,
+    /// The release with exactly this version
+    Version(String),
+    /// Releases newer than this version, plus the next section
+    Since(String),
+    /// Releases newer than the first version, up to and including the second
+    Between(String, String),
+}
+
+impl ParsedChangelog {
+    /// Latest finalized release (the first versioned section).
+    pub fn latest_release(&self) -> Option<&ChangelogSection> {
+        self.releases.first()
+    }
+
+    pub fn release(&self, version: &str) -> Option<&ChangelogSection> {
+        let wanted = version.trim().trim_start_matches(['v', 'V']);
+        self.releases
+            .iter()
+            .find(|release| release.version.as_deref() == Some(wanted))
+    }
+
+    /// Select sections matching `query`. Errors if a version named in the
+    /// query is not in the changelog.
+    pub fn query(&self, query: &ChangelogQuery) -> Result<ParsedChangelog> {
+        let newer_than = |release: &ChangelogSection, floor: &str| {
+            release.version.as_deref().is_some_and(|v| {
+                version::compare_versions(v, floor) == Some(std::cmp::Ordering::Greater)
+            })
+        };
+
+        match query {
+            ChangelogQuery::All => Ok(self.clone()),
+            ChangelogQuery::Version(wanted) => Ok(ParsedChangelog {
+                unreleased: None,
+                releases: vec![self.require_release(wanted)?.clone()],
+            }),
+            ChangelogQuery::Since(floor) => {
+                self.require_release(floor)?;
+                Ok(ParsedChangelog {
+                    unreleased: self.unreleased.clone(),
+                    releases: self
+                        .releases
+                        .iter()
+                        .filter(|release| newer_than(release, floor))
+                        .cloned()
+                        .collect(),
+                })
+            }
+            ChangelogQuery::Between(floor, ceiling) => {
+                self.require_release(floor)?;
+                self.require_release(ceiling)?;
+                Ok(ParsedChangelog {
+                    unreleased: None,
+                    releases: self
+                        .releases
+                        .iter()
+                        .filter(|release| {
+                            newer_than(release, floor) && !newer_than(release, ceiling)
+                        })
+                        .cloned()
+                        .collect(),
+                })
+            }
+        }
+    }
+
+    fn require_release(&self, version: &str) -> Result<&ChangelogSection> {
+        self.release(version).ok_or_else(|| {
+            Error::validation_invalid_argument(
+                "version",
+                format!("Version '{}' not found in changelog", version),
+                None,
+                Some(
+                    self.releases
+                        .iter()
+                        .filter_map(|release| release.version.clone())
+                        .take(10)
+                        .collect(),
+                ),
+            )
+        })
+    }
+
+    /// Markdown for the selected sections, next section first.
+    pub fn to_markdown(&self) -> String {
+        self.unreleased
+            .iter()
+            .chain(self.releases.iter())
+            .map(ChangelogSection::to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Parse a changelog into sections. `##` headings matching `next_section_aliases`
+/// become `unreleased`; headings with a version become `releases`. Other
+/// `##` sections are ignored.
+pub fn parse_changelog(content: &str, next_section_aliases: &[String]) -> ParsedChangelog {
+    static DATE: OnceLock<Regex> = OnceLock::new();
+    let date_pattern =
+        DATE.get_or_init(|| Regex::new(r"\d{4}-\d{2}-\d{2}").expect("valid date pattern"));
+
+    enum Target {
+        Unreleased,
+        Release,
+        Ignored,
+    }
+
+    fn finish(
+        current: Option<(Target, ChangelogSection, Vec<&str>)>,
+        parsed: &mut ParsedChangelog,
+    ) {
+        if let Some((target, mut section, body)) = current {
+            section.body = body.join("\n").trim().to_string();
+            match target {
+                Target::Unreleased if parsed.unreleased.is_none() => {
+                    parsed.unreleased = Some(section)
+                }
+                Target::Release => parsed.releases.push(section),
+                _ => {}
+            }
+        }
+    }
+
+    let mut parsed = ParsedChangelog::default();
+    let mut current: Option<(Target, ChangelogSection, Vec<&str>)> = None;
+
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("## ") {
+            finish(current.take(), &mut parsed);
+            let heading = trimmed.trim_start_matches("## ").trim().to_string();
+            let version = extract_version_from_heading(&heading);
+            let target = if is_matching_next_section_heading(trimmed, next_section_aliases) {
+                Target::Unreleased
+            } else if version.is_some() {
+                Target::Release
+            } else {
+                Target::Ignored
+            };
+            let date = date_pattern.find(&heading).map(|m| m.as_str().to_string());
+            let section = ChangelogSection {
+                heading,
+                line: idx + 1,
+                version,
+                date,
+                ..Default::default()
+            };
+            current = Some((target, section, Vec::new()));
+            continue;
+        }
+
+        let Some((_, section, body)) = current.as_mut() else {
+            continue;
+        };
+        body.push(line);
+
+        if let Some(name) = trimmed.strip_prefix("### ") {
+            section.subsections.push(ChangelogSubsection {
+                name: name.trim().to_string(),
+                line: idx + 1,
+                items: Vec::new(),
+            });
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .filter(|_| !line.starts_with([' ', '\t']))
+        {
+            section.push_item(item.trim().to_string());
+        } else if !trimmed.is_empty() && line.starts_with([' ', '\t']) {
+            // Continuation of a wrapped or nested bullet
+            if let Some(last) = section.last_item_mut() {
+                last.push('\n');
+                last.push_str(trimmed);
+            }
+        }
+    }
+    finish(current.take(), &mut parsed);
+
+    parsed
+}
+
+/// Parse a component's changelog using its configured next-section aliases.
+pub fn read_parsed(component: &Component) -> Result<(PathBuf, ParsedChangelog)> {
+    let settings = resolve_effective_settings(Some(component));
+    let file = read_changelog(component)?;
+    let parsed = parse_changelog(&file.markdown(), &settings.next_section_aliases);
+    Ok((file.path, parsed))
+}
+
+// === Changelog Lint Operations ===
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize)]
+
+pub struct LintFinding {
+    pub rule: String,
+    pub severity: LintSeverity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+
+pub struct LintOutput {
+    pub component_id: String,
+    pub changelog_path: String,
+    pub findings: Vec<LintFinding>,
+    pub errors: usize,
+    pub warnings: usize,
+    pub passed: bool,
+}
+
+impl LintOutput {
+    /// Whether any finding is at or above `fail_on`.
+    pub fn fails(&self, fail_on: LintSeverity) -> bool {
+        match fail_on {
+            LintSeverity::Error => self.errors > 0,
+            LintSeverity::Warning => self.errors + self.warnings > 0,
+        }
+    }
+}
+
+fn finding(
+    rule: &str,
+    severity: LintSeverity,
+    line: Option<usize>,
+    message: String,
+    hint: &str,
+) -> LintFinding {
+    LintFinding {
+        rule: rule.to_string(),
+        severity,
+        line,
+        message,
+        hint: Some(hint.to_string()),
+    }
+}
+
+/// Check a parsed changelog for Keep a Changelog compliance. When `tags` is
+/// given, versions and semver tags (`v1.2.0` or `1.2.0`) are cross-checked.
+///
+/// Errors: duplicate versions, versions out of descending order, malformed
+/// dates, empty finalized sections. Warnings: missing dates, unknown
+/// subsections, versions without a tag, tags without a section. Date rules are
+/// skipped for readme.txt changelogs, whose headings carry no dates.
+pub fn lint_changelog(
+    parsed: &ParsedChangelog,
+    format: ChangelogFormat,
+    tags: Option<&[String]>,
+) -> Vec<LintFinding> {
+    use LintSeverity::{Error as Err, Warning};
+
+    static DATE: OnceLock<Regex> = OnceLock::new();
+    let date_pattern = DATE.get_or_init(|| {
+        Regex::new(r"^(\S+)\s*(?:\[YANKED\])?$").expect("valid heading date pattern")
+    });
+
+    let mut findings = Vec::new();
+    let mut previous: Option<&ChangelogSection> = None;
+
+    for (idx, release) in parsed.releases.iter().enumerate() {
+        let line = Some(release.line);
+        let Some(version) = release.version.as_deref() else {
+            continue;
+        };
+
+        if let Some(first) = parsed.releases[..idx]
+            .iter()
+            .find(|other| other.version.as_deref() == Some(version))
+        {
+            findings.push(finding(
+                "duplicate_version",
+                Err,
+                line,
+                format!("Version {} already appears on line {}", version, first.line),
+                "Merge the duplicate sections into one",
+            ));
+        } else if let Some(prev) = previous {
+            let prev_version = prev.version.as_deref().unwrap_or_default();
+            if version::compare_versions(version, prev_version) != Some(std::cmp::Ordering::Less) {
+                findings.push(finding(
+                    "version_order",
+                    Err,
+                    line,
+                    format!(
+                        "Version {} is not lower than {} on line {}",
+                        version, prev_version, prev.line
+                    ),
+                    "List releases newest first",
+                ));
+            }
+        }
+        previous = Some(release);
+
+        // Whatever follows the version in the heading should be the date
+        let after_version = release
+            .heading
+            .split_once(version)
+            .map(|(_, rest)| {
+                rest.trim_start_matches(']')
+                    .trim()
+                    .trim_start_matches(['-', '–', '—'])
+                    .trim()
+            })
+            .unwrap_or_default();
+        if format == ChangelogFormat::WordpressReadme {
+            // `= X.Y.Z =` headings have no date
+        } else if after_version.is_empty() {
+            findings.push(finding(
+                "missing_date",
+                Warning,
+                line,
+                format!("Version {} has no release date", version),
+                "Use `## [X.Y.Z] - YYYY-MM-DD`",
+            ));
+        } else {
+            let valid = date_pattern
+                .captures(after_version)
+                .and_then(|caps| caps.get(1))
+                .is_some_and(|date| {
+                    chrono::NaiveDate::parse_from_str(date.as_str(), "%Y-%m-%d").is_ok()
+                        && date.as_str().len() == 10
+                });
+            if !valid {
+                findings.push(finding(
+                    "malformed_date",
+                    Err,
+                    line,
+                    format!(
+                        "Version {} has a malformed date '{}'",
+                        version, after_version
+                    ),
+                    "Use an ISO 8601 date: `## [X.Y.Z] - YYYY-MM-DD`",
+                ));
+            }
+        }
+
+        let has_items = !release.items.is_empty()
+            || release.subsections.iter().any(|sub| !sub.items.is_empty());
+        if !has_items {
+            findings.push(finding(
+                "empty_section",
+                Err,
+                line,
+                format!("Version {} has no entries", version),
+                "Add entries or remove the section",
+            ));
+        }
+
+        for subsection in &release.subsections {
+            let header = format!("### {}", subsection.name);
+            if !KEEP_A_CHANGELOG_SUBSECTIONS.contains(&header.as_str()) {
+                findings.push(finding(
+                    "unknown_subsection",
+                    Warning,
+                    Some(subsection.line),
+                    format!(
+                        "Unknown subsection '{}' in version {}",
+                        subsection.name, version
+                    ),
+                    "Use Added, Changed, Deprecated, Removed, Fixed, or Security",
+                ));
+            }
+        }
+    }
+
+    if let Some(tags) = tags {
+        let tagged: Vec<(&String, semver::Version)> = tags
+            .iter()
+            .filter_map(|tag| {
+                let bare = tag.strip_prefix(['v', 'V']).unwrap_or(tag);
+                semver::Version::parse(bare).ok().map(|v| (tag, v))
+            })
+            .collect();
+        let documented: Vec<semver::Version> = parsed
+            .releases
+            .iter()
+            .filter_map(|r| r.version.as_deref().and_then(version::parse_semver))
+            .collect();
+
+        for release in &parsed.releases {
+            let Some(parsed_version) = release.version.as_deref().and_then(version::parse_semver)
+            else {
+                continue;
+            };
+            if !tagged.iter().any(|(_, v)| *v == parsed_version) {
+                findings.push(finding(
+                    "missing_tag",
+                    Warning,
+                    Some(release.line),
+                    format!("Version {} has no git tag", parsed_version),
+                    "Expected for a version that is about to be released; otherwise tag it with `git tag v<version> <commit>`",
+                ));
+            }
+        }
+
+        for (tag, tag_version) in &tagged {
+            if !documented.contains(tag_version) {
+                findings.push(finding(
+                    "untracked_tag",
+                    Warning,
+                    None,
+                    format!("Tag {} has no changelog section", tag),
+                    "Add a section for the release, or ignore if it predates the changelog",
+                ));
+            }
+        }
+    }
+
+    findings
+}
+
+/// Lint a component's changelog, including a cross-check against git tags
+/// when the component is a git repository.
+pub fn lint(component_id: &str) -> Result<LintOutput> {
+    let component = component::load(component_id)?;
+    let settings = resolve_effective_settings(Some(&component));
+    let file = read_changelog(&component)?;
+    let parsed = parse_changelog(&file.markdown(), &settings.next_section_aliases);
+    let tags = if git::is_git_repo(&component.local_path) {
+        Some(git::list_tags(&component.local_path)?)
+    } else {
+        None
+    };
+
+    let findings = lint_changelog(&parsed, file.format, tags.as_deref());
+    let errors = findings
+        .iter()
+        .filter(|f| f.severity == LintSeverity::Error)
+        .count();
+    let warnings = findings.len() - errors;
+
+    Ok(LintOutput {
+        component_id: component_id.to_string(),
+        changelog_path: file.path.to_string_lossy().to_string(),
+        findings,
+        errors,
+        warnings,
+        passed: errors == 0,
+    })
+}
+
 // === Changelog Show Operations ===
 
 #[derive(Debug, Clone, Serialize)]
@@ -982,21 +1578,37 @@ pub struct ShowOutput {
     pub content: String,
 }
 
-pub fn show(component_id: &str) -> Result<ShowOutput> {
+#[derive(Debug, Clone, Serialize)]
+
+pub struct ParsedShowOutput {
+    pub component_id: String,
+    pub changelog_path: String,
+    #[serde(flatten)]
+    pub changelog: ParsedChangelog,
+}
+
+/// Parsed changelog for a component, filtered by `query`.
+pub fn show_parsed(component_id: &str, query: &ChangelogQuery) -> Result<ParsedShowOutput> {
     let component = component::load(component_id)?;
-    let changelog_path = resolve_changelog_path(&component)?;
+    let (path, parsed) = read_parsed(&component)?;
 
-    let content = fs::read_to_string(&changelog_path).map_err(|e| {
-        Error::internal_io(
-            e.to_string(),
-            Some(format!("read changelog at {}", changelog_path.display())),
-        )
-    })?;
+    Ok(ParsedShowOutput {
+        component_id: component_id.to_string(),
+        changelog_path: path.to_string_lossy().to_string(),
+        changelog: parsed.query(query)?,
+    })
+}
+
+/// The component's changelog as Keep a Changelog markdown; for a readme.txt
+/// changelog, only its `== Changelog ==` section.
+pub fn show(component_id: &str) -> Result<ShowOutput> {
+    let component = component::load(component_id)?;
+    let file = read_changelog(&component)?;
 
     Ok(ShowOutput {
         component_id: component_id.to_string(),
-        changelog_path: changelog_path.to_string_lossy().to_string(),
-        content,
+        changelog_path: file.path.to_string_lossy().to_string(),
+        content: file.markdown(),
     })
 }
 
@@ -1041,15 +1653,13 @@ pub fn init(component_id: &str, path: Option<&str>, configure: bool) -> Result<I
     };
 
     // Handle existing file: ensure Unreleased section exists
+    let format = changelog_format::resolve_format(&component, relative_path);
     if changelog_path.exists() {
-        let content = fs::read_to_string(&changelog_path)
-            .map_err(|e| Error::internal_io(e.to_string(), Some("read changelog".to_string())))?;
+        let file = ChangelogFile::read(&changelog_path, format)?;
 
-        let (new_content, changed) = ensure_next_section(&content, &settings.next_section_aliases)?;
-
-        if changed {
-            local_files::local().write(&changelog_path, &new_content)?;
-        }
+        let (new_content, section_added) =
+            ensure_next_section(&file.markdown(), &settings.next_section_aliases)?;
+        let changed = section_added && file.write(&new_content)?;
 
         return Ok(InitOutput {
             component_id: component_id.to_string(),
@@ -1063,6 +1673,20 @@ pub fn init(component_id: &str, path: Option<&str>, configure: bool) -> Result<I
     }
 
     // File doesn't exist: create new changelog with template
+    if format != ChangelogFormat::Markdown {
+        return Err(Error::validation_invalid_argument(
+            "path",
+            format!(
+                "{} does not exist; only markdown changelogs can be created",
+                relative_path
+            ),
+            Some(component_id.to_string()),
+            None,
+        )
+        .with_hint(
+            "Create the plugin readme.txt first, then re-run init to add the next section",
+        ));
+    }
     let version_info = version::read_version(Some(component_id))?;
     let initial_version = version_info.version;
 
@@ -1084,10 +1708,385 @@ pub fn init(component_id: &str, path: Option<&str>, configure: bool) -> Result<I
     })
 }
 
+// === Changelog Generate Operations ===
+
+#[derive(Debug, Clone, Serialize)]
+
+pub struct GeneratedEntry {
+    pub entry_type: String,
+    pub message: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+
+pub struct GenerateOutput {
+    pub component_id: String,
+    pub changelog_path: String,
+    pub next_section_label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since_tag: Option<String>,
+    pub dry_run: bool,
+    /// Entries added (or that would be added with `--dry-run`)
+    pub entries: Vec<GeneratedEntry>,
+    /// Entries already present in the next section
+    pub duplicates: Vec<GeneratedEntry>,
+    /// Commits that don't belong in a changelog (chore, ci, merges)
+    pub skipped: Vec<CommitInfo>,
+    pub items_added: usize,
+    pub changed: bool,
+}
+
+/// Keep a Changelog entry type for a commit, or `None` if it should be skipped.
+///
+/// feat -> Added, fix -> Fixed, `security` type or scope -> Security, breaking
+/// changes -> Removed when they remove/drop something and Changed otherwise.
+/// Everything else that isn't chore/ci/build/release or a merge is Changed.
+fn entry_type_for_commit(commit: &CommitInfo) -> Option<&'static str> {
+    if commit.subject.starts_with("Merge ") {
+        return None;
+    }
+
+    let (commit_type, scope) = conventional_type(&commit.subject);
+    // `release:` commits are the version bumps homeboy makes itself
+    if matches!(
+        commit_type.as_deref(),
+        Some("chore" | "ci" | "build" | "release")
+    ) {
+        return None;
+    }
+    if commit_type.as_deref() == Some("security") || scope.as_deref() == Some("security") {
+        return Some("security");
+    }
+
+    match commit.category {
+        CommitCategory::Chore => None,
+        CommitCategory::Feature => Some("added"),
+        CommitCategory::Fix => Some("fixed"),
+        CommitCategory::Breaking => {
+            let message = git::strip_conventional_prefix(&commit.subject).to_lowercase();
+            if ["remove", "drop", "delete"]
+                .iter()
+                .any(|verb| message.starts_with(verb))
+            {
+                Some("removed")
+            } else {
+                Some("changed")
+            }
+        }
+        CommitCategory::Docs | CommitCategory::Other => Some("changed"),
+    }
+}
+
+/// Lowercased type and scope of a conventional commit subject.
+fn conventional_type(subject: &str) -> (Option<String>, Option<String>) {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(r"^([A-Za-z]+)(?:\(([^)]*)\))?!?:\s").expect("valid conventional pattern")
+    });
+
+    match pattern.captures(subject) {
+        Some(caps) => (
+            caps.get(1).map(|m| m.as_str().to_lowercase()),
+            caps.get(2).map(|m| m.as_str().trim().to_lowercase()),
+        ),
+        None => (None, None),
+    }
+}
+
+struct GeneratedEntries {
+    content: String,
+    entries: Vec<GeneratedEntry>,
+    duplicates: Vec<GeneratedEntry>,
+    skipped: Vec<CommitInfo>,
+}
+
+/// Add entries for `commits` (newest first, as returned by git) to the next
+/// section, oldest first.
+fn add_generated_entries(
+    content: &str,
+    aliases: &[String],
+    commits: &[CommitInfo],
+) -> Result<GeneratedEntries> {
+    let (mut current, _) = ensure_next_section(content, aliases)?;
+    let mut entries = Vec::new();
+    let mut duplicates = Vec::new();
+    let mut skipped = Vec::new();
+
+    for commit in commits.iter().rev() {
+        let Some(entry_type) = entry_type_for_commit(commit) else {
+            skipped.push(commit.clone());
+            continue;
+        };
+        let message = git::strip_conventional_prefix(&commit.subject).trim();
+        if message.is_empty() {
+            skipped.push(commit.clone());
+            continue;
+        }
+
+        let entry = GeneratedEntry {
+            entry_type: entry_type.to_string(),
+            message: message.to_string(),
+            hash: commit.hash.clone(),
+        };
+        let (next, added) = append_item_to_subsection(&current, aliases, message, entry_type)?;
+        if added {
+            entries.push(entry);
+        } else {
+            duplicates.push(entry);
+        }
+        current = next;
+    }
+
+    Ok(GeneratedEntries {
+        content: current,
+        entries,
+        duplicates,
+        skipped,
+    })
+}
+
+/// Generate next-section entries from the conventional commits since `since`
+/// (default: the latest tag).
+pub fn generate(component_id: &str, since: Option<&str>, dry_run: bool) -> Result<GenerateOutput> {
+    let component = component::load(component_id)?;
+    let settings = resolve_effective_settings(Some(&component));
+    let file = read_changelog(&component)?;
+
+    let since_tag = match since {
+        Some(tag) => Some(tag.to_string()),
+        None => git::get_latest_tag(&component.local_path)?,
+    };
+    let commits = git::get_commits_since_tag(&component.local_path, since_tag.as_deref())?;
+
+    let generated =
+        add_generated_entries(&file.markdown(), &settings.next_section_aliases, &commits)?;
+
+    let changed = file.render(&generated.content) != file.content;
+    if changed && !dry_run {
+        file.write(&generated.content)?;
+    }
+
+    Ok(GenerateOutput {
+        component_id: component_id.to_string(),
+        changelog_path: file.path.to_string_lossy().to_string(),
+        next_section_label: settings.next_section_label,
+        since_tag,
+        dry_run,
+        items_added: generated.entries.len(),
+        entries: generated.entries,
+        duplicates: generated.duplicates,
+        skipped: generated.skipped,
+        changed,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const PARSE_FIXTURE: &str = "# Changelog\n\n## [Unreleased]\n\n### Added\n- Export command\n\n## [1.2.0] - 2026-03-04\n\n### Added\n- Deploy summaries\n  spanning two lines\n\n### Fixed\n- Tag detection\n\n## 1.1.0 - 2026-02-01\n- Plain bullet\n\n## Notes\n- Not a release\n\n## [1.0.0-beta.1]\n- First beta\n";
+
+    #[test]
+    fn parse_changelog_builds_sections() {
+        let aliases = vec!["Unreleased".to_string()];
+        let parsed = parse_changelog(PARSE_FIXTURE, &aliases);
+
+        let unreleased = parsed.unreleased.as_ref().unwrap();
+        assert_eq!(unreleased.subsections[0].name, "Added");
+        assert_eq!(unreleased.subsections[0].items, vec!["Export command"]);
+
+        let versions: Vec<&str> = parsed
+            .releases
+            .iter()
+            .filter_map(|r| r.version.as_deref())
+            .collect();
+        assert_eq!(versions, vec!["1.2.0", "1.1.0", "1.0.0-beta.1"]);
+
+        let latest = parsed.latest_release().unwrap();
+        assert_eq!(latest.date.as_deref(), Some("2026-03-04"));
+        assert_eq!(
+            latest.subsections[0].items,
+            vec!["Deploy summaries\nspanning two lines"]
+        );
+        assert_eq!(latest.subsections[1].name, "Fixed");
+        assert!(latest
+            .notes()
+            .unwrap()
+            .starts_with("### Added\n- Deploy summaries"));
+
+        let plain = parsed.release("v1.1.0").unwrap();
+        assert_eq!(plain.items, vec!["Plain bullet"]);
+        assert!(plain.subsections.is_empty());
+        assert_eq!(parsed.releases[2].date, None);
+    }
+
+    #[test]
+    fn lint_changelog_reports_rules_with_lines() {
+        let content = "# Changelog\n\n## Unreleased\n\n## [1.2.0] - 2026-03-04\n### Added\n- Export\n\n## [1.3.0] - 2026-02-30\n### Breaking\n- Config keys\n\n## [1.1.0]\n\n## [1.1.0] - 2026-01-02\n- Again\n";
+        let parsed = parse_changelog(content, &["Unreleased".to_string()]);
+        let tags = vec![
+            "v1.2.0".to_string(),
+            "v1.0.0".to_string(),
+            "nightly".to_string(),
+        ];
+        let findings = lint_changelog(&parsed, ChangelogFormat::Markdown, Some(&tags));
+
+        let rules: Vec<(&str, LintSeverity, Option<usize>)> = findings
+            .iter()
+            .map(|f| (f.rule.as_str(), f.severity, f.line))
+            .collect();
+        use LintSeverity::{Error as E, Warning as W};
+        assert_eq!(
+            rules,
+            vec![
+                ("version_order", E, Some(9)),
+                ("malformed_date", E, Some(9)),
+                ("unknown_subsection", W, Some(10)),
+                ("missing_date", W, Some(13)),
+                ("empty_section", E, Some(13)),
+                ("duplicate_version", E, Some(15)),
+                ("missing_tag", W, Some(9)),
+                ("missing_tag", W, Some(13)),
+                ("missing_tag", W, Some(15)),
+                ("untracked_tag", W, None),
+            ]
+        );
+        assert!(findings.iter().all(|f| f.hint.is_some()));
+
+        let clean = parse_changelog("## [1.0.0] - 2026-01-01 [YANKED]\n### Fixed\n- Bug\n", &[]);
+        assert!(lint_changelog(
+            &clean,
+            ChangelogFormat::Markdown,
+            Some(&["v1.0.0".to_string()])
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn lint_changelog_skips_dates_for_readme_changelogs() {
+        let file = ChangelogFile {
+            path: PathBuf::from("readme.txt"),
+            format: ChangelogFormat::WordpressReadme,
+            content:
+                "=== Plugin ===\n\n== Changelog ==\n\n= 1.1.0 =\n* Export\n\n= 1.0.0 =\n* Initial\n"
+                    .to_string(),
+        };
+        let parsed = parse_changelog(&file.markdown(), &[]);
+
+        let findings = lint_changelog(&parsed, file.format, None);
+        assert!(findings.is_empty(), "{:?}", findings);
+        assert!(lint_changelog(&parsed, ChangelogFormat::Markdown, None)
+            .iter()
+            .all(|f| f.rule == "missing_date"));
+    }
+
+    #[test]
+    fn insert_release_section_goes_above_latest_release() {
+        let finalized = parse_changelog(PARSE_FIXTURE, &["Unreleased".to_string()]);
+        let section = finalized.release("1.2.0").unwrap().to_markdown();
+
+        let mirror = "# Changelog\n\n## Unreleased\n\n## 1.1.0\n- Plain bullet\n";
+        let out = insert_release_section(mirror, &section);
+        assert!(out.starts_with(
+            "# Changelog\n\n## Unreleased\n\n## [1.2.0] - 2026-03-04\n\n### Added\n- Deploy summaries\n"
+        ));
+        assert!(out.ends_with("- Tag detection\n\n## 1.1.0\n- Plain bullet\n"));
+
+        let empty = insert_release_section("# Changelog\n", &section);
+        assert!(empty.starts_with("# Changelog\n\n## [1.2.0] - 2026-03-04\n"));
+    }
+
+    #[test]
+    fn parsed_changelog_queries_versions() {
+        let parsed = parse_changelog(PARSE_FIXTURE, &["Unreleased".to_string()]);
+        let versions = |query: ChangelogQuery| -> Vec<String> {
+            parsed
+                .query(&query)
+                .unwrap()
+                .releases
+                .into_iter()
+                .filter_map(|r| r.version)
+                .collect()
+        };
+
+        assert_eq!(
+            versions(ChangelogQuery::Version("1.1.0".into())),
+            vec!["1.1.0"]
+        );
+        assert_eq!(
+            versions(ChangelogQuery::Since("1.0.0-beta.1".into())),
+            vec!["1.2.0", "1.1.0"]
+        );
+        assert!(parsed
+            .query(&ChangelogQuery::Since("1.1.0".into()))
+            .unwrap()
+            .unreleased
+            .is_some());
+        assert_eq!(
+            versions(ChangelogQuery::Between(
+                "1.0.0-beta.1".into(),
+                "1.1.0".into()
+            )),
+            vec!["1.1.0"]
+        );
+        assert!(parsed
+            .query(&ChangelogQuery::Version("9.9.9".into()))
+            .is_err());
+    }
+
+    fn commit(hash: &str, subject: &str) -> CommitInfo {
+        CommitInfo {
+            hash: hash.to_string(),
+            subject: subject.to_string(),
+            category: git::parse_conventional_commit(subject),
+        }
+    }
+
+    #[test]
+    fn add_generated_entries_maps_categories_and_skips_noise() {
+        let content =
+            "# Changelog\n\n## Unreleased\n\n### Fixed\n- Handle empty tags\n\n## 0.1.0\n";
+        let aliases = vec!["Unreleased".to_string()];
+        // Newest first, as returned by git log
+        let commits = vec![
+            commit("a1", "Merge pull request #4 from fork/branch"),
+            commit("a2", "feat!: Remove legacy config keys"),
+            commit("a3", "fix(security): Escape shell arguments"),
+            commit("a4", "chore: Bump deps"),
+            commit("a5", "fix: Handle empty tags"),
+            commit("a6", "refactor: Simplify deploy planning"),
+            commit("a7", "feat(cli): Add export command"),
+        ];
+
+        let generated = add_generated_entries(content, &aliases, &commits).unwrap();
+
+        let added: Vec<(&str, &str)> = generated
+            .entries
+            .iter()
+            .map(|e| (e.entry_type.as_str(), e.message.as_str()))
+            .collect();
+        assert_eq!(
+            added,
+            vec![
+                ("added", "Add export command"),
+                ("changed", "Simplify deploy planning"),
+                ("security", "Escape shell arguments"),
+                ("removed", "Remove legacy config keys"),
+            ]
+        );
+        assert_eq!(generated.duplicates.len(), 1);
+        assert_eq!(generated.duplicates[0].hash, "a5");
+        assert_eq!(generated.skipped.len(), 2);
+
+        let out = generated.content;
+        assert!(out.contains("### Added\n- Add export command\n"));
+        assert!(out.contains("### Fixed\n- Handle empty tags\n"));
+        assert!(out.find("### Added").unwrap() < out.find("### Changed").unwrap());
+        assert!(out.find("### Removed").unwrap() < out.find("### Fixed").unwrap());
+        assert!(out.find("### Fixed").unwrap() < out.find("### Security").unwrap());
+    }
+
     #[test]
     fn add_next_section_items_appends_multiple_in_order() {
         let content = "# Changelog\n\n## Unreleased\n\n## 0.1.0\n";
@@ -0,0 +1,314 @@
+//! Changelog file formats.
+//!
+//! Changelog operations work on Keep a Changelog markdown. Other formats are
+//! read into an equivalent markdown view and written back in their own syntax,
+//! so adding, finalizing and querying behave the same for every format.
+//!
+//! WordPress `readme.txt` changelogs live in the `== Changelog ==` section:
+//!
+//! ```text
+//! == Changelog ==
+//!
+//! = 1.2.3 =
+//! * Fixed a bug
+//! ```
+//!
+//! `= X =` headings map to `## X`, `*` bullets to `- ` bullets and bold
+//! subsection lines (`**Fixed**`) to `### Fixed`. The rest of the readme is
+//! left untouched.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::component::Component;
+use crate::error::{Error, Result};
+use crate::module;
+
+const README_CHANGELOG_HEADING: &str = "== Changelog ==";
+
+/// Title line prepended to readme views so the first version heading is not
+/// mistaken for the changelog title.
+const VIEW_TITLE: &str = "# Changelog";
+
+/// Subsections that round-trip through readme bold lines.
+const README_SUBSECTIONS: &[&str] = &[
+    "Added",
+    "Changed",
+    "Deprecated",
+    "Removed",
+    "Fixed",
+    "Security",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogFormat {
+    /// Keep a Changelog markdown (`## [1.2.3] - YYYY-MM-DD`)
+    #[default]
+    Markdown,
+    /// The `== Changelog ==` section of a WordPress plugin `readme.txt`
+    WordpressReadme,
+}
+
+/// Format for a changelog file: the component's `changelog_format` (for its
+/// `changelog_target`), else the first module declaring a format for the file
+/// name, else `wordpress_readme` for `readme.txt` and markdown otherwise.
+pub fn resolve_format(component: &Component, file: &str) -> ChangelogFormat {
+    if component.changelog_target.as_deref() == Some(file) {
+        if let Some(format) = component.changelog_format {
+            return format;
+        }
+    }
+    module::load_all_modules()
+        .iter()
+        .flat_map(|module| module.changelog_formats.iter())
+        .find(|config| file_matches(file, &config.file))
+        .map(|config| config.format)
+        .unwrap_or_else(|| {
+            if file_matches(file, "readme.txt") {
+                ChangelogFormat::WordpressReadme
+            } else {
+                ChangelogFormat::Markdown
+            }
+        })
+}
+
+fn file_matches(file: &str, declared: &str) -> bool {
+    let file = file.to_lowercase();
+    let declared = declared.to_lowercase();
+    file == declared || file.ends_with(&format!("/{}", declared))
+}
+
+/// A changelog file read through its format.
+#[derive(Debug, Clone)]
+pub struct ChangelogFile {
+    pub path: PathBuf,
+    pub format: ChangelogFormat,
+    /// File content as read from disk
+    pub content: String,
+}
+
+impl ChangelogFile {
+    pub fn read(path: &Path, format: ChangelogFormat) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            Error::internal_io(
+                e.to_string(),
+                Some(format!("read changelog at {}", path.display())),
+            )
+        })?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            format,
+            content,
+        })
+    }
+
+    /// Keep a Changelog markdown view of the file.
+    pub fn markdown(&self) -> String {
+        match self.format {
+            ChangelogFormat::Markdown => self.content.clone(),
+            ChangelogFormat::WordpressReadme => readme_to_markdown(&self.content),
+        }
+    }
+
+    /// File content for an edited markdown view.
+    pub fn render(&self, markdown: &str) -> String {
+        match self.format {
+            ChangelogFormat::Markdown => markdown.to_string(),
+            ChangelogFormat::WordpressReadme => markdown_to_readme(&self.content, markdown),
+        }
+    }
+
+    /// Write an edited markdown view back to disk. Returns whether the file
+    /// changed.
+    pub fn write(&self, markdown: &str) -> Result<bool> {
+        let rendered = self.render(markdown);
+        if rendered == self.content {
+            return Ok(false);
+        }
+        fs::write(&self.path, rendered)
+            .map_err(|e| Error::internal_io(e.to_string(), Some("write changelog".to_string())))?;
+        Ok(true)
+    }
+}
+
+/// Line range of the readme changelog body: after the `== Changelog ==`
+/// heading, up to the next `== ... ==` section.
+fn readme_changelog_range(lines: &[&str]) -> Option<(usize, usize)> {
+    let heading = lines
+        .iter()
+        .position(|line| line.trim().eq_ignore_ascii_case(README_CHANGELOG_HEADING))?;
+    let end = lines[heading + 1..]
+        .iter()
+        .position(|line| is_readme_section_heading(line))
+        .map(|offset| heading + 1 + offset)
+        .unwrap_or(lines.len());
+    Some((heading + 1, end))
+}
+
+fn is_readme_section_heading(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.len() > 4 && trimmed.starts_with("== ") && trimmed.ends_with(" ==")
+}
+
+fn readme_to_markdown(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let body = match readme_changelog_range(&lines) {
+        Some((start, end)) => &lines[start..end],
+        None => &[][..],
+    };
+
+    let mut out = format!("{}\n\n", VIEW_TITLE);
+    for line in body {
+        let trimmed = line.trim();
+        let converted = if let Some(label) = readme_version_label(trimmed) {
+            format!("## {}", label)
+        } else if let Some(name) = readme_subsection(trimmed) {
+            format!("### {}", name)
+        } else if let Some(item) = line.strip_prefix("* ") {
+            format!("- {}", item)
+        } else {
+            (*line).to_string()
+        };
+        out.push_str(&converted);
+        out.push('\n');
+    }
+    out
+}
+
+fn markdown_to_readme(original: &str, markdown: &str) -> String {
+    let mut body = String::new();
+    let mut lines = markdown.lines().peekable();
+    if lines.peek().is_some_and(|line| line.trim() == VIEW_TITLE) {
+        lines.next();
+    }
+    for line in lines {
+        let trimmed = line.trim();
+        let converted = if let Some(label) = trimmed.strip_prefix("## ") {
+            format!("= {} =", readme_heading_label(label))
+        } else if let Some(name) = trimmed.strip_prefix("### ") {
+            format!("**{}**", name.trim())
+        } else if let Some(item) = line.strip_prefix("- ") {
+            format!("* {}", item)
+        } else {
+            line.to_string()
+        };
+        body.push_str(&converted);
+        body.push('\n');
+    }
+    let body = body.trim();
+
+    let lines: Vec<&str> = original.lines().collect();
+    let mut out = String::new();
+    match readme_changelog_range(&lines) {
+        Some((start, end)) => {
+            for line in &lines[..start] {
+                out.push_str(line);
+                out.push('\n');
+            }
+            out.push('\n');
+            if !body.is_empty() {
+                out.push_str(body);
+                out.push('\n');
+            }
+            if end < lines.len() {
+                out.push('\n');
+                for line in &lines[end..] {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+        None => {
+            out.push_str(original.trim_end());
+            if !out.is_empty() {
+                out.push_str("\n\n");
+            }
+            out.push_str(README_CHANGELOG_HEADING);
+            out.push_str("\n\n");
+            if !body.is_empty() {
+                out.push_str(body);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// `= 1.2.3 =` -> `1.2.3`
+fn readme_version_label(trimmed: &str) -> Option<&str> {
+    if trimmed.starts_with("==") {
+        return None;
+    }
+    trimmed
+        .strip_prefix("= ")
+        .and_then(|rest| rest.strip_suffix(" ="))
+        .map(str::trim)
+}
+
+/// `**Fixed**` -> `Fixed`, only for Keep a Changelog subsection names.
+fn readme_subsection(trimmed: &str) -> Option<&str> {
+    let name = trimmed.strip_prefix("**")?.strip_suffix("**")?.trim();
+    README_SUBSECTIONS.contains(&name).then_some(name)
+}
+
+/// Readme headings don't use Keep a Changelog brackets:
+/// `[1.2.3] - 2026-01-02` -> `1.2.3 - 2026-01-02`.
+fn readme_heading_label(label: &str) -> String {
+    let label = label.trim();
+    match label
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+    {
+        Some((inner, rest)) => format!("{}{}", inner, rest),
+        None => label.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = "=== My Plugin ===\nStable tag: 1.2.0\n\n== Description ==\n\nDoes things.\n\n== Changelog ==\n\n= 1.2.0 =\n**Fixed**\n* Tag detection\n\n= 1.1.0 =\n* First\n  continued\n\n== Upgrade Notice ==\n\n= 1.2.0 =\nUpgrade.\n";
+
+    fn readme_file(content: &str) -> ChangelogFile {
+        ChangelogFile {
+            path: PathBuf::from("readme.txt"),
+            format: ChangelogFormat::WordpressReadme,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn readme_view_round_trips() {
+        let file = readme_file(README);
+        let markdown = file.markdown();
+
+        assert!(markdown.starts_with("# Changelog\n\n\n## 1.2.0\n### Fixed\n- Tag detection\n"));
+        assert!(markdown.contains("## 1.1.0\n- First\n  continued\n"));
+        assert!(!markdown.contains("Upgrade"));
+        assert_eq!(file.render(&markdown), README);
+    }
+
+    #[test]
+    fn readme_render_maps_markdown_edits_back() {
+        let file = readme_file(README);
+        let markdown = file.markdown().replace(
+            "## 1.2.0",
+            "## [1.3.0] - 2026-10-17\n- New thing\n\n## 1.2.0",
+        );
+
+        let rendered = file.render(&markdown);
+        assert!(rendered.contains(
+            "== Changelog ==\n\n= 1.3.0 - 2026-10-17 =\n* New thing\n\n= 1.2.0 =\n**Fixed**"
+        ));
+        assert!(rendered.ends_with("== Upgrade Notice ==\n\n= 1.2.0 =\nUpgrade.\n"));
+
+        let without_section = readme_file("=== My Plugin ===\n");
+        assert_eq!(
+            without_section.render("# Changelog\n\n## Unreleased\n\n- Item\n"),
+            "=== My Plugin ===\n\n== Changelog ==\n\n= Unreleased =\n\n* Item\n"
+        );
+    }
+}
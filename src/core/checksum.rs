@@ -0,0 +1,220 @@
+use sha2::{Digest, Sha256, Sha512};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+/// SHA-256 of a file's contents as lowercase hex.
+pub fn sha256_file(path: &Path) -> Result<String> {
+    digest_file::<Sha256>(path)
+}
+
+/// SHA-512 of a file's contents as lowercase hex.
+pub fn sha512_file(path: &Path) -> Result<String> {
+    digest_file::<Sha512>(path)
+}
+
+fn digest_file<D: Digest>(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("open {}", path.display()))))?;
+
+    let mut hasher = D::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buf).map_err(|e| {
+            Error::internal_io(e.to_string(), Some(format!("read {}", path.display())))
+        })?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// SHA-256 of an artifact path. Files hash their contents; directories hash
+/// the sorted list of `<relative path> <file hash>` lines so renames count as changes.
+pub fn sha256_path(path: &Path) -> Result<String> {
+    if !path.is_dir() {
+        return sha256_file(path);
+    }
+
+    let mut hasher = Sha256::new();
+    for (relative, hash) in file_hashes(path)? {
+        hasher.update(format!("{} {}\n", relative, hash).as_bytes());
+    }
+
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// SHA-256 of every file under a directory, keyed by `/`-separated relative path, sorted.
+pub fn file_hashes(root: &Path) -> Result<Vec<(String, String)>> {
+    let mut entries = Vec::new();
+    collect_file_hashes(root, root, &mut entries)?;
+    entries.sort();
+    Ok(entries)
+}
+
+/// Write a `sha256sum`-compatible manifest: one `<hash>  <name>` line per entry.
+pub fn write_sums_file(path: &Path, entries: &[(String, String)]) -> Result<()> {
+    let content: String = entries
+        .iter()
+        .map(|(name, hash)| format!("{}  {}\n", hash, name))
+        .collect();
+    fs::write(path, content)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("write {}", path.display()))))
+}
+
+/// Sign a file with an SSH key (`ssh-keygen -Y sign`), producing `<file>.sig`.
+///
+/// Verify with `ssh-keygen -Y verify -f allowed_signers -I <identity> -n <namespace> -s <file>.sig < <file>`.
+pub fn ssh_sign(path: &Path, key: &Path, namespace: &str) -> Result<std::path::PathBuf> {
+    let signature = std::path::PathBuf::from(format!("{}.sig", path.display()));
+    // ssh-keygen refuses to overwrite an existing signature.
+    if signature.exists() {
+        fs::remove_file(&signature).map_err(|e| {
+            Error::internal_io(
+                e.to_string(),
+                Some(format!("remove {}", signature.display())),
+            )
+        })?;
+    }
+
+    let output = crate::cancel::output(
+        std::process::Command::new("ssh-keygen")
+            .arg("-Y")
+            .arg("sign")
+            .arg("-f")
+            .arg(key)
+            .arg("-n")
+            .arg(namespace)
+            .arg(path),
+    )
+    .map_err(|e| Error::internal_io(e.to_string(), Some("run ssh-keygen".to_string())))?;
+
+    if !output.status.success() {
+        return Err(Error::other(format!(
+            "ssh-keygen -Y sign failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(signature)
+}
+
+fn collect_file_hashes(root: &Path, dir: &Path, out: &mut Vec<(String, String)>) -> Result<()> {
+    let read_dir = fs::read_dir(dir)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("read {}", dir.display()))))?;
+
+    for entry in read_dir {
+        let entry = entry.map_err(|e| Error::internal_io(e.to_string(), None))?;
+        let path = entry.path();
+        if path.is_dir() {
+            collect_file_hashes(root, &path, out)?;
+        } else if path.is_file() {
+            let relative = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            out.push((relative, sha256_file(&path)?));
+        }
+    }
+
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_file_matches_known_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        fs::write(&file, "abc").unwrap();
+
+        assert_eq!(
+            sha256_file(&file).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn writes_sha512_sums_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        fs::write(&file, "abc").unwrap();
+        let sums = dir.path().join("SHA512SUMS");
+
+        let hash = sha512_file(&file).unwrap();
+        write_sums_file(&sums, &[("a.txt".to_string(), hash.clone())]).unwrap();
+
+        assert!(hash.starts_with("ddaf35a193617aba"));
+        assert_eq!(
+            fs::read_to_string(&sums).unwrap(),
+            format!("{}  a.txt\n", hash)
+        );
+    }
+
+    #[test]
+    fn sha256_path_for_directory_changes_when_a_file_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("a.txt"), "a").unwrap();
+        fs::write(dir.path().join("sub/b.txt"), "b").unwrap();
+
+        let before = sha256_path(dir.path()).unwrap();
+        fs::write(dir.path().join("sub/b.txt"), "changed").unwrap();
+        let after = sha256_path(dir.path()).unwrap();
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn file_hashes_uses_sorted_relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("z.txt"), "z").unwrap();
+        fs::write(dir.path().join("sub/b.txt"), "b").unwrap();
+
+        let paths: Vec<String> = file_hashes(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+
+        assert_eq!(paths, vec!["sub/b.txt", "z.txt"]);
+    }
+
+    #[test]
+    fn ssh_sign_replaces_existing_signature() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = dir.path().join("id_ed25519");
+        let generated = std::process::Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+            .arg(&key)
+            .status();
+        if !generated.map(|s| s.success()).unwrap_or(false) {
+            return; // ssh-keygen unavailable
+        }
+        let sums = dir.path().join("SHA256SUMS");
+        fs::write(&sums, "abc  a.txt\n").unwrap();
+        fs::write(dir.path().join("SHA256SUMS.sig"), "stale").unwrap();
+
+        let signature = ssh_sign(&sums, &key, "file").unwrap();
+
+        assert_eq!(signature, dir.path().join("SHA256SUMS.sig"));
+        assert!(fs::read_to_string(&signature)
+            .unwrap()
+            .starts_with("-----BEGIN SSH SIGNATURE-----"));
+    }
+}
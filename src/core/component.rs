@@ -8,12 +8,33 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 
 pub struct VersionTarget {
     pub file: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
+    /// JSON pointer to the version string (e.g. `/version`), instead of `pattern`
+    #[serde(
+        rename = "jsonPointer",
+        alias = "json_pointer",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_pointer: Option<String>,
+    /// Dotted TOML key path (e.g. `package.version`), instead of `pattern`
+    #[serde(
+        rename = "tomlKey",
+        alias = "toml_key",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub toml_key: Option<String>,
+    /// Dotted YAML key path (e.g. `version`), instead of `pattern`
+    #[serde(
+        rename = "yamlKey",
+        alias = "yaml_key",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub yaml_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -37,20 +58,53 @@ pub struct Component {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version_targets: Option<Vec<VersionTarget>>,
+    /// Versioning scheme for bumps (default: semver)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_scheme: Option<crate::version::VersionScheme>,
+    /// While the version is 0.x, `auto` bumps treat breaking changes as minor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_1_0_breaking_minor: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub changelog_target: Option<String>,
+    /// Format of `changelog_target` (default: declared by a module, else markdown)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog_format: Option<crate::changelog_format::ChangelogFormat>,
+    /// Second changelog (e.g. `readme.txt`) that receives a copy of each
+    /// section finalized in `changelog_target`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog_mirror_target: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub changelog_next_section_label: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub changelog_next_section_aliases: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub release: Option<crate::release::ReleaseConfig>,
+    /// Components this one requires, with semver ranges checked by
+    /// `version check` and deploy/release preflight
+    #[serde(
+        rename = "dependsOn",
+        alias = "depends_on",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub depends_on: Option<Vec<crate::version_constraints::ComponentDependency>>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub post_version_bump_commands: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub build_command: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extract_command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub atomic_deploy: Option<crate::deploy::AtomicDeployConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload: Option<crate::deploy::UploadConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<crate::health::HealthCheckConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_deploy_commands: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_deploy_commands: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_deploy_failure: Vec<String>,
 }
 
 impl Component {
@@ -67,13 +121,24 @@ impl Component {
             build_artifact,
             modules: None,
             version_targets: None,
+            version_scheme: None,
+            pre_1_0_breaking_minor: None,
             changelog_target: None,
+            changelog_format: None,
+            changelog_mirror_target: None,
             changelog_next_section_label: None,
             changelog_next_section_aliases: None,
             release: None,
+            depends_on: None,
             post_version_bump_commands: Vec::new(),
             build_command: None,
             extract_command: None,
+            atomic_deploy: None,
+            upload: None,
+            health_check: None,
+            pre_deploy_commands: Vec::new(),
+            post_deploy_commands: Vec::new(),
+            on_deploy_failure: Vec::new(),
         }
     }
 }
@@ -206,6 +271,7 @@ pub fn parse_version_targets(targets: &[String]) -> Result<Vec<VersionTarget>> {
         parsed.push(VersionTarget {
             file: file.to_string(),
             pattern: pattern.map(|p| p.to_string()),
+            ..Default::default()
         });
     }
     Ok(parsed)
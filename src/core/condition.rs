@@ -0,0 +1,369 @@
+//! `when` expressions for pipeline steps.
+//!
+//! Grammar:
+//!
+//! ```text
+//! expr    := and ( "||" and )*
+//! and     := unary ( "&&" unary )*
+//! unary   := "!" unary | "(" expr ")" | compare
+//! compare := operand ( ( "==" | "!=" ) operand )?
+//! operand := 'string' | "string" | number | true | false | null | path
+//! ```
+//!
+//! Paths are dotted lookups into the pipeline context (`branch`,
+//! `steps.build.status`, `env.CI`). A bare operand is true unless it is
+//! missing, `null`, `false`, `0`, or an empty string.
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(Value),
+    Path(String),
+    Eq,
+    Ne,
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(Value),
+    Path(String),
+    Eq(Box<Expr>, Box<Expr>),
+    Ne(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+/// Parsed `when` condition.
+#[derive(Debug, Clone)]
+pub(crate) struct Condition {
+    expr: Expr,
+}
+
+impl Condition {
+    pub(crate) fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        if tokens.is_empty() {
+            return Err("condition is empty".to_string());
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(format!(
+                "unexpected {:?} at token {}",
+                parser.tokens[parser.pos],
+                parser.pos + 1
+            ));
+        }
+        Ok(Self { expr })
+    }
+
+    /// Evaluate against a context object. `env.NAME` paths read the process
+    /// environment.
+    pub(crate) fn evaluate(&self, context: &Value) -> bool {
+        truthy(&eval(&self.expr, context))
+    }
+
+    /// Whether the expression reads exactly this path.
+    pub(crate) fn references(&self, path: &str) -> bool {
+        references(&self.expr, path)
+    }
+}
+
+fn references(expr: &Expr, path: &str) -> bool {
+    match expr {
+        Expr::Literal(_) => false,
+        Expr::Path(p) => p == path,
+        Expr::Eq(a, b) | Expr::Ne(a, b) | Expr::And(a, b) | Expr::Or(a, b) => {
+            references(a, path) || references(b, path)
+        }
+        Expr::Not(inner) => references(inner, path),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' | '\t' | '\n' | '\r' => i += 1,
+            '(' => {
+                tokens.push(Token::Open);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                i += 1;
+            }
+            '=' | '!' | '&' | '|' => {
+                let next = chars.get(i + 1).copied();
+                let token = match (c, next) {
+                    ('=', Some('=')) => Token::Eq,
+                    ('!', Some('=')) => Token::Ne,
+                    ('&', Some('&')) => Token::And,
+                    ('|', Some('|')) => Token::Or,
+                    ('!', _) => {
+                        tokens.push(Token::Not);
+                        i += 1;
+                        continue;
+                    }
+                    _ => return Err(format!("unexpected '{}' at position {}", c, i + 1)),
+                };
+                tokens.push(token);
+                i += 2;
+            }
+            '\'' | '"' => {
+                let start = i + 1;
+                let end = chars[start..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .map(|offset| start + offset)
+                    .ok_or_else(|| format!("unterminated string at position {}", i + 1))?;
+                let text: String = chars[start..end].iter().collect();
+                tokens.push(Token::Literal(Value::String(text)));
+                i = end + 1;
+            }
+            _ if c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '_' | '-' | '.'))
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(word_token(word));
+            }
+            _ => return Err(format!("unexpected '{}' at position {}", c, i + 1)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn word_token(word: String) -> Token {
+    match word.as_str() {
+        "true" => Token::Literal(Value::Bool(true)),
+        "false" => Token::Literal(Value::Bool(false)),
+        "null" => Token::Literal(Value::Null),
+        _ => match word.parse::<f64>() {
+            Ok(n) if word.starts_with(|c: char| c.is_ascii_digit() || c == '-') => {
+                Token::Literal(serde_json::json!(n))
+            }
+            _ => Token::Path(word),
+        },
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut left = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            left = Expr::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.pos += 1;
+                Ok(Expr::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::Open) => {
+                self.pos += 1;
+                let inner = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err("missing ')'".to_string()),
+                }
+            }
+            _ => self.compare(),
+        }
+    }
+
+    fn compare(&mut self) -> Result<Expr, String> {
+        let left = self.operand()?;
+        match self.peek() {
+            Some(Token::Eq) => {
+                self.pos += 1;
+                Ok(Expr::Eq(Box::new(left), Box::new(self.operand()?)))
+            }
+            Some(Token::Ne) => {
+                self.pos += 1;
+                Ok(Expr::Ne(Box::new(left), Box::new(self.operand()?)))
+            }
+            _ => Ok(left),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Literal(value)) => Ok(Expr::Literal(value)),
+            Some(Token::Path(path)) => Ok(Expr::Path(path)),
+            Some(other) => Err(format!("expected a value, found {:?}", other)),
+            None => Err("expected a value at end of condition".to_string()),
+        }
+    }
+}
+
+fn eval(expr: &Expr, context: &Value) -> Value {
+    match expr {
+        Expr::Literal(value) => value.clone(),
+        Expr::Path(path) => lookup(context, path),
+        Expr::Eq(a, b) => Value::Bool(values_equal(&eval(a, context), &eval(b, context))),
+        Expr::Ne(a, b) => Value::Bool(!values_equal(&eval(a, context), &eval(b, context))),
+        Expr::And(a, b) => Value::Bool(truthy(&eval(a, context)) && truthy(&eval(b, context))),
+        Expr::Or(a, b) => Value::Bool(truthy(&eval(a, context)) || truthy(&eval(b, context))),
+        Expr::Not(inner) => Value::Bool(!truthy(&eval(inner, context))),
+    }
+}
+
+fn lookup(context: &Value, path: &str) -> Value {
+    if let Some(name) = path.strip_prefix("env.") {
+        return std::env::var(name)
+            .map(Value::String)
+            .unwrap_or(Value::Null);
+    }
+    lookup_path(context, path).unwrap_or(Value::Null)
+}
+
+/// Resolve a dotted path (`steps.git.tag.data.tag`) in a JSON value.
+///
+/// Keys may themselves contain dots, so each level matches the longest run
+/// of segments that names a key. Array elements are addressed by index.
+pub(crate) fn lookup_path(value: &Value, path: &str) -> Option<Value> {
+    let segments: Vec<&str> = path.split('.').collect();
+    lookup_segments(value, &segments)
+}
+
+fn lookup_segments(value: &Value, segments: &[&str]) -> Option<Value> {
+    if segments.is_empty() {
+        return Some(value.clone());
+    }
+    match value {
+        Value::Object(map) => (1..=segments.len()).rev().find_map(|n| {
+            map.get(&segments[..n].join("."))
+                .and_then(|child| lookup_segments(child, &segments[n..]))
+        }),
+        Value::Array(items) => segments[0]
+            .parse::<usize>()
+            .ok()
+            .and_then(|idx| items.get(idx))
+            .and_then(|child| lookup_segments(child, &segments[1..])),
+        _ => None,
+    }
+}
+
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x.as_f64() == y.as_f64(),
+        // Environment variables and settings are often strings; compare them
+        // to numbers and booleans by value so `env.DRY_RUN == 1` works.
+        (Value::String(s), Value::Number(n)) | (Value::Number(n), Value::String(s)) => {
+            s.trim().parse::<f64>().ok() == n.as_f64()
+        }
+        (Value::String(s), Value::Bool(b)) | (Value::Bool(b), Value::String(s)) => {
+            s.trim() == if *b { "true" } else { "false" }
+        }
+        _ => a == b,
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(source: &str, context: &Value) -> bool {
+        Condition::parse(source).unwrap().evaluate(context)
+    }
+
+    #[test]
+    fn evaluates_comparisons_and_boolean_operators() {
+        let context = json!({
+            "branch": "main",
+            "prerelease": false,
+            "steps": {
+                "git.tag": { "status": "success", "data": { "tag": "v1.2.0" } },
+                "build": { "status": "failed" }
+            }
+        });
+
+        assert!(check("branch == 'main' && !prerelease", &context));
+        assert!(check("steps.build.status == 'failed'", &context));
+        assert!(check("steps.git.tag.data.tag == \"v1.2.0\"", &context));
+        assert!(check(
+            "(prerelease || branch != 'main') || steps.git.tag",
+            &context
+        ));
+        assert!(!check("steps.unknown.status == 'success'", &context));
+
+        std::env::set_var("HOMEBOY_CONDITION_TEST", "1");
+        assert!(check("env.HOMEBOY_CONDITION_TEST == 1", &context));
+        assert!(!check("env.HOMEBOY_CONDITION_TEST_UNSET", &context));
+    }
+
+    #[test]
+    fn finds_referenced_paths() {
+        let condition =
+            Condition::parse("!prerelease && (steps.git.tag.status != 'success')").unwrap();
+
+        assert!(condition.references("steps.git.tag.status"));
+        assert!(condition.references("prerelease"));
+        assert!(!condition.references("steps.git.status"));
+    }
+
+    #[test]
+    fn rejects_malformed_conditions() {
+        assert!(Condition::parse("").is_err());
+        assert!(Condition::parse("branch ==").is_err());
+        assert!(Condition::parse("(branch == 'main'").is_err());
+        assert!(Condition::parse("branch = 'main'").is_err());
+        assert!(Condition::parse("branch == 'main").is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 
+use crate::component::VersionTarget;
 use crate::paths;
+use crate::version::VERSION_REGEX;
 
 /// Root configuration structure for homeboy.json
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -17,7 +19,7 @@ pub struct Defaults {
     pub install_methods: InstallMethodsConfig,
 
     #[serde(default = "default_version_candidates")]
-    pub version_candidates: Vec<VersionCandidateConfig>,
+    pub version_candidates: Vec<VersionTarget>,
 
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub post_version_bump_commands: Vec<String>,
@@ -62,13 +64,6 @@ pub struct InstallMethodConfig {
     pub list_command: Option<String>,
 }
 
-/// Configuration for version file detection
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VersionCandidateConfig {
-    pub file: String,
-    pub pattern: String,
-}
-
 /// Configuration for deploy operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeployConfig {
@@ -80,6 +75,35 @@ pub struct DeployConfig {
 
     #[serde(default = "default_ssh_port")]
     pub default_ssh_port: u16,
+
+    /// Pre-deploy remote backups to keep per component (0 disables backups)
+    #[serde(default)]
+    pub keep_backups: usize,
+
+    #[serde(default)]
+    pub upload_strategy: UploadStrategy,
+
+    #[serde(default = "default_rsync_flags")]
+    pub rsync_flags: Vec<String>,
+
+    /// Seconds after which a remote deploy lock is considered stale (0 = never)
+    #[serde(default = "default_lock_ttl_seconds")]
+    pub lock_ttl_seconds: u64,
+
+    /// Directory for deploy lock files (default: `.homeboy/` next to the base path)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_dir: Option<String>,
+}
+
+/// How directory artifacts are uploaded during deploy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadStrategy {
+    /// Copy the whole directory with `scp -r`
+    #[default]
+    Scp,
+    /// Delta-transfer changed files with `rsync`
+    Rsync,
 }
 
 /// Configuration for file permissions
@@ -134,23 +158,38 @@ fn default_source_config() -> InstallMethodConfig {
     }
 }
 
-fn default_version_candidates() -> Vec<VersionCandidateConfig> {
+/// Well-known version files checked by version target detection.
+fn default_version_candidates() -> Vec<VersionTarget> {
     vec![
-        VersionCandidateConfig {
+        VersionTarget {
             file: "Cargo.toml".to_string(),
-            pattern: r#"version\s*=\s*"(\d+\.\d+\.\d+)""#.to_string(),
+            toml_key: Some("package.version".to_string()),
+            ..Default::default()
         },
-        VersionCandidateConfig {
+        VersionTarget {
+            file: "Cargo.toml".to_string(),
+            toml_key: Some("workspace.package.version".to_string()),
+            ..Default::default()
+        },
+        VersionTarget {
             file: "package.json".to_string(),
-            pattern: r#""version"\s*:\s*"(\d+\.\d+\.\d+)""#.to_string(),
+            json_pointer: Some("/version".to_string()),
+            ..Default::default()
         },
-        VersionCandidateConfig {
+        VersionTarget {
             file: "composer.json".to_string(),
-            pattern: r#""version"\s*:\s*"(\d+\.\d+\.\d+)""#.to_string(),
+            json_pointer: Some("/version".to_string()),
+            ..Default::default()
+        },
+        VersionTarget {
+            file: "pubspec.yaml".to_string(),
+            yaml_key: Some("version".to_string()),
+            ..Default::default()
         },
-        VersionCandidateConfig {
+        VersionTarget {
             file: "style.css".to_string(),
-            pattern: r"Version:\s*(\d+\.\d+\.\d+)".to_string(),
+            pattern: Some(format!(r"Version:\s*({})", VERSION_REGEX)),
+            ..Default::default()
         },
     ]
 }
@@ -160,6 +199,11 @@ fn default_deploy() -> DeployConfig {
         scp_flags: default_scp_flags(),
         artifact_prefix: default_artifact_prefix(),
         default_ssh_port: default_ssh_port(),
+        keep_backups: 0,
+        upload_strategy: UploadStrategy::default(),
+        rsync_flags: default_rsync_flags(),
+        lock_ttl_seconds: default_lock_ttl_seconds(),
+        lock_dir: None,
     }
 }
 
@@ -167,6 +211,14 @@ fn default_scp_flags() -> Vec<String> {
     vec!["-O".to_string()]
 }
 
+fn default_lock_ttl_seconds() -> u64 {
+    1800
+}
+
+fn default_rsync_flags() -> Vec<String> {
+    vec!["-az".to_string()]
+}
+
 fn default_artifact_prefix() -> String {
     ".homeboy-".to_string()
 }
@@ -1,22 +1,56 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 
 use crate::base_path;
 use crate::build;
-use crate::component::{self, Component};
+use crate::checksum;
+use crate::component::{self, Component, VersionTarget};
 use crate::config;
-use crate::context::{resolve_project_ssh_with_base_path, RemoteProjectContext};
-use crate::defaults;
+use crate::context::require_project_base_path;
+use crate::defaults::{self, UploadStrategy};
+use crate::deploy_history;
+use crate::deploy_hooks::{self, HookResult, HookStage};
+use crate::deploy_lock;
+use crate::deploy_plan::{
+    self, DeployPlan, PlannedCommands, PlannedComponent, PlannedHooks, PlannedStrategy,
+    PLAN_FORMAT_VERSION,
+};
+use crate::drift::{self, DriftReport};
 use crate::error::{Error, Result};
+use crate::health::{self, HealthCheckResult};
 use crate::module::{load_all_modules, DeployOverride, DeployVerification, ModuleManifest};
 use crate::permissions;
 use crate::project::{self, Project};
 use crate::shell;
 use crate::ssh::SshClient;
 use crate::template::{render_map, TemplateVars};
+use crate::transport::Transport;
 use crate::version;
+use crate::version_constraints;
+
+thread_local! {
+    /// Component ID prefixed to deploy log lines when deploying in parallel.
+    pub(crate) static LOG_COMPONENT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Log prefix for a deploy stage, e.g. `[build]`, or `[build:<id>]` inside a
+/// parallel component deploy.
+pub(crate) fn log_prefix(stage: &str) -> String {
+    LOG_COMPONENT.with(|c| match c.borrow().as_deref() {
+        Some(id) => format!("[{}:{}]", stage, id),
+        None => format!("[{}]", stage),
+    })
+}
+
+macro_rules! deploy_log {
+    ($($arg:tt)*) => {
+        eprintln!("{} {}", $crate::deploy::log_prefix("deploy"), format!($($arg)*))
+    };
+}
+pub(crate) use deploy_log;
 
 /// Parse bulk component IDs from a JSON spec.
 pub fn parse_bulk_component_ids(json_spec: &str) -> Result<Vec<String>> {
@@ -28,6 +62,8 @@ pub struct DeployResult {
     pub success: bool,
     pub exit_code: i32,
     pub error: Option<String>,
+    pub release_dir: Option<String>,
+    pub transfer: Option<TransferStats>,
 }
 
 impl DeployResult {
@@ -36,6 +72,8 @@ impl DeployResult {
             success: true,
             exit_code,
             error: None,
+            release_dir: None,
+            transfer: None,
         }
     }
 
@@ -44,24 +82,92 @@ impl DeployResult {
             success: false,
             exit_code,
             error: Some(error),
+            release_dir: None,
+            transfer: None,
         }
     }
+
+    fn with_release_dir(mut self, release_dir: String) -> Self {
+        self.release_dir = Some(release_dir);
+        self
+    }
+
+    fn with_transfer(mut self, transfer: Option<TransferStats>) -> Self {
+        self.transfer = transfer;
+        self
+    }
+}
+
+/// Upload settings for a component's directory artifacts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UploadConfig {
+    /// Upload strategy (default: `defaults.deploy.upload_strategy`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<UploadStrategy>,
+    /// rsync only: delete remote files that no longer exist locally
+    #[serde(default)]
+    pub delete: bool,
+    /// rsync only: patterns excluded from the transfer (and protected from delete)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// rsync only: compare file checksums instead of size and modification time
+    #[serde(default)]
+    pub checksum: bool,
+}
+
+impl UploadConfig {
+    fn effective_strategy(&self) -> UploadStrategy {
+        self.strategy
+            .unwrap_or_else(|| defaults::load_defaults().deploy.upload_strategy)
+    }
+}
+
+/// File transfer counts reported by delta uploads.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TransferStats {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files_total: Option<u64>,
+    pub files_transferred: u64,
+    pub files_deleted: u64,
+}
+
+/// Atomic release-directory deploy settings for a component.
+///
+/// When configured, artifacts are deployed into `<remotePath>/releases/<timestamp>/`
+/// and `<remotePath>/current` is switched to the new release only after
+/// extraction and verification succeed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AtomicDeployConfig {
+    /// Number of release directories to retain (including the current one)
+    #[serde(default = "default_keep_releases")]
+    pub keep_releases: usize,
+}
+
+fn default_keep_releases() -> usize {
+    5
 }
 
+const RELEASES_DIR: &str = "releases";
+const CURRENT_LINK: &str = "current";
+
 /// Main entry point - uploads artifact and runs extract command if configured
 pub fn deploy_artifact(
-    ssh_client: &SshClient,
+    transport: &Transport,
     local_path: &Path,
     remote_path: &str,
     extract_command: Option<&str>,
     verification: Option<&DeployVerification>,
+    upload: &UploadConfig,
 ) -> Result<DeployResult> {
+    let mut transfer = None;
+
     // Step 1: Upload (directory or file)
     if local_path.is_dir() {
-        let result = upload_directory(ssh_client, local_path, remote_path)?;
+        let result = upload_directory(transport, local_path, remote_path, upload)?;
         if !result.success {
             return Ok(result);
         }
+        transfer = result.transfer;
     } else {
         // Validate: archive artifacts require an extract command
         let is_archive = local_path
@@ -82,21 +188,7 @@ pub fn deploy_artifact(
         }
 
         // For archives, upload to temp location in target directory
-        let deploy_defaults = defaults::load_defaults().deploy;
-        let artifact_prefix = &deploy_defaults.artifact_prefix;
-        let artifact_filename = local_path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .ok_or_else(|| {
-                Error::validation_invalid_argument(
-                    "buildArtifact",
-                    "Build artifact path must include a file name",
-                    Some(local_path.display().to_string()),
-                    None,
-                )
-            })?
-            .to_string();
-        let artifact_filename = format!("{}{}", artifact_prefix, artifact_filename);
+        let artifact_filename = uploaded_artifact_name(local_path)?;
 
         let upload_path = if extract_command.is_some() {
             format!("{}/{}", remote_path, artifact_filename)
@@ -106,8 +198,8 @@ pub fn deploy_artifact(
 
         // Create target directory
         let mkdir_cmd = format!("mkdir -p {}", shell::quote_path(remote_path));
-        eprintln!("[deploy] Creating directory: {}", remote_path);
-        let mkdir_output = ssh_client.execute(&mkdir_cmd);
+        deploy_log!("Creating directory: {}", remote_path);
+        let mkdir_output = transport.execute(&mkdir_cmd);
         if !mkdir_output.success {
             return Ok(DeployResult::failure(
                 mkdir_output.exit_code,
@@ -115,23 +207,20 @@ pub fn deploy_artifact(
             ));
         }
 
-        let result = upload_file(ssh_client, local_path, &upload_path)?;
+        let result = upload_file(transport, local_path, &upload_path)?;
         if !result.success {
             return Ok(result);
         }
 
         // Step 2: Execute extract command if configured
         if let Some(cmd_template) = extract_command {
-            let mut vars = HashMap::new();
-            vars.insert("artifact".to_string(), artifact_filename);
-            vars.insert("targetDir".to_string(), remote_path.to_string());
-
-            let rendered_cmd = render_extract_command(cmd_template, &vars);
+            let rendered_cmd =
+                render_extract_command(cmd_template, &artifact_filename, remote_path);
 
             let extract_cmd = format!("cd {} && {}", shell::quote_path(remote_path), rendered_cmd);
-            eprintln!("[deploy] Extracting: {}", rendered_cmd);
+            deploy_log!("Extracting: {}", rendered_cmd);
 
-            let extract_output = ssh_client.execute(&extract_cmd);
+            let extract_output = transport.execute(&extract_cmd);
             if !extract_output.success {
                 let error_detail = if extract_output.stderr.is_empty() {
                     extract_output.stdout.clone()
@@ -148,48 +237,259 @@ pub fn deploy_artifact(
             }
 
             // Fix file permissions after extraction
-            eprintln!("[deploy] Fixing file permissions");
-            permissions::fix_deployed_permissions(ssh_client, remote_path)?;
+            deploy_log!("Fixing file permissions");
+            permissions::fix_deployed_permissions(transport, remote_path)?;
         }
     }
 
     // Step 3: Run verification if configured
-    if let Some(v) = verification {
-        if let Some(ref verify_cmd_template) = v.verify_command {
-            let mut vars = HashMap::new();
-            vars.insert(
-                TemplateVars::TARGET_DIR.to_string(),
-                remote_path.to_string(),
-            );
-            let verify_cmd = render_map(verify_cmd_template, &vars);
-
-            let verify_output = ssh_client.execute(&verify_cmd);
-            if !verify_output.success || verify_output.stdout.trim().is_empty() {
-                let error_msg = v
-                    .verify_error_message
-                    .as_ref()
-                    .map(|msg| render_map(msg, &vars))
-                    .unwrap_or_else(|| format!("Deploy verification failed for {}", remote_path));
-                return Ok(DeployResult::failure(1, error_msg));
-            }
-        }
+    if let Some(failure) = run_verification(transport, verification, remote_path) {
+        return Ok(failure);
     }
 
-    Ok(DeployResult::success(0))
+    Ok(DeployResult::success(0).with_transfer(transfer))
+}
+
+/// Run module deploy verification against a target directory.
+/// Returns a failed result when verification is configured and does not pass.
+fn run_verification(
+    transport: &Transport,
+    verification: Option<&DeployVerification>,
+    target_dir: &str,
+) -> Option<DeployResult> {
+    let v = verification?;
+    let verify_cmd_template = v.verify_command.as_ref()?;
+
+    let mut vars = HashMap::new();
+    vars.insert(TemplateVars::TARGET_DIR.to_string(), target_dir.to_string());
+    let verify_cmd = render_map(verify_cmd_template, &vars);
+
+    let verify_output = transport.execute(&verify_cmd);
+    if !verify_output.success || verify_output.stdout.trim().is_empty() {
+        let error_msg = v
+            .verify_error_message
+            .as_ref()
+            .map(|msg| render_map(msg, &vars))
+            .unwrap_or_else(|| format!("Deploy verification failed for {}", target_dir));
+        return Some(DeployResult::failure(1, error_msg));
+    }
+
+    None
 }
 
-fn render_extract_command(template: &str, vars: &HashMap<String, String>) -> String {
+fn render_extract_command(template: &str, artifact_filename: &str, target_dir: &str) -> String {
+    let mut vars = HashMap::new();
+    vars.insert("artifact".to_string(), artifact_filename.to_string());
+    vars.insert("targetDir".to_string(), target_dir.to_string());
+
     let mut result = template.to_string();
-    for (key, value) in vars {
+    for (key, value) in &vars {
         result = result.replace(&format!("{{{}}}", key), value);
     }
     result
 }
 
+/// File name an archive artifact is uploaded as (with the configured artifact prefix).
+fn uploaded_artifact_name(local_path: &Path) -> Result<String> {
+    let artifact_prefix = defaults::load_defaults().deploy.artifact_prefix;
+    Ok(format!(
+        "{}{}",
+        artifact_prefix,
+        artifact_file_name(local_path)?
+    ))
+}
+
+fn artifact_file_name(local_path: &Path) -> Result<&str> {
+    local_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| {
+            Error::validation_invalid_argument(
+                "buildArtifact",
+                "Build artifact path must include a file name",
+                Some(local_path.display().to_string()),
+                None,
+            )
+        })
+}
+
+/// Atomic deploy - uploads into a fresh release directory, verifies it there,
+/// then switches the `current` symlink and trims old releases.
+pub fn deploy_artifact_atomic(
+    transport: &Transport,
+    local_path: &Path,
+    remote_path: &str,
+    extract_command: Option<&str>,
+    verification: Option<&DeployVerification>,
+    upload: &UploadConfig,
+    atomic: &AtomicDeployConfig,
+) -> Result<DeployResult> {
+    let release_name = new_release_name();
+    let releases_root = format!("{}/{}", remote_path, RELEASES_DIR);
+    let release_dir = format!("{}/{}", releases_root, release_name);
+
+    deploy_log!("Atomic release: {}", release_dir);
+
+    // Claim the release directory. `mkdir` without -p refuses an existing
+    // release (which may be `current`), so the cleanup below only ever removes
+    // a directory this deploy created.
+    let claim = transport.execute(&format!(
+        "mkdir -p {} && mkdir {}",
+        shell::quote_path(&releases_root),
+        shell::quote_path(&release_dir)
+    ));
+    if !claim.success {
+        return Ok(DeployResult::failure(
+            claim.exit_code,
+            format!(
+                "Could not create release directory {}: {}",
+                release_dir, claim.stderr
+            ),
+        ));
+    }
+    // `scp -r` and `cp -R` copy a directory into an existing target, so
+    // directory uploads need the (uniquely named) path to be absent again
+    if local_path.is_dir() && upload.effective_strategy() != UploadStrategy::Rsync {
+        let _ = transport.execute(&format!("rmdir {}", shell::quote_path(&release_dir)));
+    }
+
+    // Step 1: Upload, extract and verify inside the release directory
+    let result = deploy_artifact(
+        transport,
+        local_path,
+        &release_dir,
+        extract_command,
+        verification,
+        upload,
+    )?;
+    if !result.success {
+        deploy_log!("Removing failed release: {}", release_dir);
+        let _ = transport.execute(&format!("rm -rf {}", shell::quote_path(&release_dir)));
+        return Ok(result);
+    }
+
+    // Step 2: Atomically switch the current symlink
+    let switch_result = switch_current_release(transport, remote_path, &release_name);
+    if !switch_result.success {
+        deploy_log!("Removing unswitched release: {}", release_dir);
+        let _ = transport.execute(&format!("rm -rf {}", shell::quote_path(&release_dir)));
+        return Ok(switch_result);
+    }
+
+    // Step 3: Trim old releases (best effort - the deploy itself succeeded)
+    let keep = atomic.keep_releases.max(1);
+    deploy_log!("Keeping {} most recent releases", keep);
+    let trim_output = transport.execute(&trim_entries_command(&releases_root, keep));
+    if !trim_output.success {
+        deploy_log!(
+            "Warning: failed to trim old releases: {}",
+            trim_output.stderr
+        );
+    }
+
+    Ok(DeployResult::success(0)
+        .with_release_dir(release_dir)
+        .with_transfer(result.transfer))
+}
+
+/// Release directory name: a millisecond timestamp (so names sort by age)
+/// plus a random suffix so concurrent deploys never share a directory.
+fn new_release_name() -> String {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    format!(
+        "{}-{}",
+        chrono::Utc::now().format("%Y%m%d%H%M%S%3f"),
+        &suffix[..6]
+    )
+}
+
+/// Point `<remote_path>/current` at `releases/<release_name>`.
+/// Uses a temporary link plus rename so the switch is atomic (`ln -sfn` alone is not).
+/// `mv -T` is GNU-only; BSD/macOS `mv -h` does the same.
+fn switch_current_release(
+    transport: &Transport,
+    remote_path: &str,
+    release_name: &str,
+) -> DeployResult {
+    let link_target = format!("{}/{}", RELEASES_DIR, release_name);
+    let tmp_link = format!(".{}.tmp", CURRENT_LINK);
+    let switch_cmd = format!(
+        "cd {} && ln -sfn {} {tmp} && {{ mv -Tf {tmp} {current} 2>/dev/null || mv -fh {tmp} {current}; }}",
+        shell::quote_path(remote_path),
+        shell::quote_path(&link_target),
+        tmp = shell::quote_path(&tmp_link),
+        current = shell::quote_path(CURRENT_LINK)
+    );
+    deploy_log!("Switching {} -> {}", CURRENT_LINK, link_target);
+
+    let switch_output = transport.execute(&switch_cmd);
+    if !switch_output.success {
+        return DeployResult::failure(
+            switch_output.exit_code,
+            format!(
+                "Failed to switch '{}' symlink: {}",
+                CURRENT_LINK, switch_output.stderr
+            ),
+        );
+    }
+
+    DeployResult::success(0)
+}
+
+/// Shell command that removes all but the `keep` newest entries of a directory.
+/// Entries are timestamp-named, so reverse name order is newest first.
+fn trim_entries_command(dir: &str, keep: usize) -> String {
+    format!(
+        "cd {} && ls -1 | sort -r | tail -n +{} | while IFS= read -r entry; do rm -rf -- \"$entry\"; done",
+        shell::quote_path(dir),
+        keep + 1
+    )
+}
+
+/// Remote backup directory for a project component.
+/// Relative to the home directory of the target (SSH user or local user), outside any web root.
+fn backup_root(project_id: &str, component_id: &str) -> String {
+    format!(".homeboy/backups/{}/{}", project_id, component_id)
+}
+
+/// Copy the currently deployed component directory into a timestamped backup
+/// so it can be restored by `deploy rollback`. No-op when nothing is deployed yet.
+fn backup_before_deploy(
+    transport: &Transport,
+    project_id: &str,
+    component_id: &str,
+    install_dir: &str,
+    keep: usize,
+) -> DeployResult {
+    let root = backup_root(project_id, component_id);
+    // Named like releases so back-to-back deploys never share a backup
+    let backup_dir = format!("{}/{}", root, new_release_name());
+
+    let backup_cmd = format!(
+        "if [ -e {install} ]; then mkdir -p {root} && cp -a {install} {backup} && ({trim}); fi",
+        install = shell::quote_path(install_dir),
+        root = shell::quote_path(&root),
+        backup = shell::quote_path(&backup_dir),
+        trim = trim_entries_command(&root, keep)
+    );
+    deploy_log!("Backing up {} -> ~/{}", install_dir, backup_dir);
+
+    let output = transport.execute(&backup_cmd);
+    if !output.success {
+        return DeployResult::failure(
+            output.exit_code,
+            format!("Pre-deploy backup failed: {}", output.stderr),
+        );
+    }
+
+    DeployResult::success(0)
+}
+
 fn upload_directory(
-    ssh_client: &SshClient,
+    transport: &Transport,
     local_path: &Path,
     remote_path: &str,
+    upload: &UploadConfig,
 ) -> Result<DeployResult> {
     let parent = Path::new(remote_path)
         .parent()
@@ -197,8 +497,8 @@ fn upload_directory(
         .unwrap_or(remote_path);
 
     let mkdir_cmd = format!("mkdir -p {}", shell::quote_path(parent));
-    eprintln!("[deploy] Creating parent directory: {}", parent);
-    let mkdir_output = ssh_client.execute(&mkdir_cmd);
+    deploy_log!("Creating parent directory: {}", parent);
+    let mkdir_output = transport.execute(&mkdir_cmd);
     if !mkdir_output.success {
         return Ok(DeployResult::failure(
             mkdir_output.exit_code,
@@ -206,15 +506,54 @@ fn upload_directory(
         ));
     }
 
-    scp_recursive(ssh_client, local_path, remote_path)
+    match (upload.effective_strategy(), transport) {
+        (UploadStrategy::Rsync, _) => rsync_directory(transport, local_path, remote_path, upload),
+        (UploadStrategy::Scp, Transport::Ssh(client)) => {
+            scp_recursive(client, local_path, remote_path)
+        }
+        (UploadStrategy::Scp, Transport::Local { .. }) => {
+            copy_local(transport, local_path, remote_path)
+        }
+    }
 }
 
 fn upload_file(
-    ssh_client: &SshClient,
+    transport: &Transport,
     local_path: &Path,
     remote_path: &str,
 ) -> Result<DeployResult> {
-    scp_file(ssh_client, local_path, remote_path)
+    match transport {
+        Transport::Ssh(client) => scp_file(client, local_path, remote_path),
+        Transport::Local { .. } => copy_local(transport, local_path, remote_path),
+    }
+}
+
+/// Copy a file or directory to a local target with `cp -R`, which places
+/// directories the same way `scp -r` does.
+fn copy_local(transport: &Transport, local_path: &Path, target_path: &str) -> Result<DeployResult> {
+    let source = std::path::absolute(local_path).map_err(|e| {
+        Error::internal_io(
+            e.to_string(),
+            Some(format!("resolve {}", local_path.display())),
+        )
+    })?;
+
+    deploy_log!(
+        "Copying: {} -> {}",
+        local_path.display(),
+        transport.destination(target_path)
+    );
+
+    let output = transport.execute(&format!(
+        "cp -R {} {}",
+        shell::quote_path(&source.to_string_lossy()),
+        shell::quote_path(target_path)
+    ));
+    if !output.success {
+        return Ok(DeployResult::failure(output.exit_code, output.stderr));
+    }
+
+    Ok(DeployResult::success(0))
 }
 
 fn scp_file(ssh_client: &SshClient, local_path: &Path, remote_path: &str) -> Result<DeployResult> {
@@ -240,8 +579,8 @@ fn scp_file(ssh_client: &SshClient, local_path: &Path, remote_path: &str) -> Res
         shell::quote_path(remote_path)
     ));
 
-    eprintln!(
-        "[deploy] Uploading: {} -> {}@{}:{}",
+    deploy_log!(
+        "Uploading: {} -> {}@{}:{}",
         local_path.display(),
         ssh_client.user,
         ssh_client.host,
@@ -288,8 +627,8 @@ fn scp_recursive(
         shell::quote_path(remote_path)
     ));
 
-    eprintln!(
-        "[deploy] Uploading directory: {} -> {}@{}:{}",
+    deploy_log!(
+        "Uploading directory: {} -> {}@{}:{}",
         local_path.display(),
         ssh_client.user,
         ssh_client.host,
@@ -308,6 +647,112 @@ fn scp_recursive(
     }
 }
 
+fn rsync_directory(
+    transport: &Transport,
+    local_path: &Path,
+    remote_path: &str,
+    upload: &UploadConfig,
+) -> Result<DeployResult> {
+    let deploy_defaults = defaults::load_defaults().deploy;
+
+    // -s keeps remote paths with spaces intact without shell quoting
+    let mut rsync_args: Vec<String> = deploy_defaults.rsync_flags.clone();
+    rsync_args.push("-s".to_string());
+    rsync_args.push("--stats".to_string());
+    if upload.delete {
+        rsync_args.push("--delete".to_string());
+    }
+    if upload.checksum {
+        rsync_args.push("--checksum".to_string());
+    }
+    for pattern in &upload.exclude {
+        rsync_args.push(format!("--exclude={}", pattern));
+    }
+    if let Transport::Ssh(client) = transport {
+        // Reuse the SSH identity and port for rsync's transport
+        let mut ssh_command = vec!["ssh".to_string()];
+        if let Some(identity_file) = &client.identity_file {
+            ssh_command.push("-i".to_string());
+            ssh_command.push(shell::quote_arg(identity_file));
+        }
+        if client.port != deploy_defaults.default_ssh_port {
+            ssh_command.push("-p".to_string());
+            ssh_command.push(client.port.to_string());
+        }
+        rsync_args.push("-e".to_string());
+        rsync_args.push(ssh_command.join(" "));
+    }
+
+    // Trailing slashes sync directory contents, matching scp -r into a new target
+    rsync_args.push(format!(
+        "{}/",
+        local_path.to_string_lossy().trim_end_matches('/')
+    ));
+    rsync_args.push(format!(
+        "{}/",
+        transport.destination(remote_path.trim_end_matches('/'))
+    ));
+
+    deploy_log!(
+        "Syncing directory: {} -> {}",
+        local_path.display(),
+        transport.destination(remote_path)
+    );
+
+    let output = Command::new("rsync").args(&rsync_args).output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            let stats = parse_rsync_stats(&String::from_utf8_lossy(&output.stdout));
+            deploy_log!(
+                "Transferred {} file(s), deleted {}",
+                stats.files_transferred,
+                stats.files_deleted
+            );
+            Ok(DeployResult::success(0).with_transfer(Some(stats)))
+        }
+        Ok(output) => Ok(DeployResult::failure(
+            output.status.code().unwrap_or(1),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        )),
+        Err(err) => Ok(DeployResult::failure(
+            1,
+            format!("Failed to run rsync: {}", err),
+        )),
+    }
+}
+
+/// Parse file counts from `rsync --stats` output.
+/// Handles both 3.1+ ("regular files transferred") and older ("files transferred") labels.
+fn parse_rsync_stats(output: &str) -> TransferStats {
+    let mut stats = TransferStats::default();
+
+    let leading_number = |value: &str| -> Option<u64> {
+        value
+            .trim()
+            .split(|c: char| !c.is_ascii_digit() && c != ',')
+            .next()
+            .map(|n| n.replace(',', ""))
+            .and_then(|n| n.parse().ok())
+    };
+
+    for line in output.lines() {
+        let Some((label, value)) = line.split_once(':') else {
+            continue;
+        };
+        match label.trim() {
+            "Number of files" => stats.files_total = leading_number(value),
+            "Number of regular files transferred" | "Number of files transferred" => {
+                stats.files_transferred = leading_number(value).unwrap_or(0)
+            }
+            "Number of deleted files" => stats.files_deleted = leading_number(value).unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    stats
+}
+
 // =============================================================================
 // Deploy Orchestration
 // =============================================================================
@@ -320,10 +765,16 @@ pub struct DeployConfig {
     pub outdated: bool,
     pub dry_run: bool,
     pub check: bool,
+    /// Compare file hashes against the server instead of only version strings
+    pub deep: bool,
+    /// Maximum components built and uploaded concurrently (0 or 1 = sequential)
+    pub parallel: usize,
+    /// Replace an existing remote deploy lock
+    pub force_unlock: bool,
 }
 
 /// Reason why a component was selected for deployment.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DeployReason {
     /// Component was explicitly specified by ID
@@ -336,10 +787,12 @@ pub enum DeployReason {
     UnknownLocalVersion,
     /// Could not determine remote version (not deployed or no version file)
     UnknownRemoteVersion,
+    /// Versions match but deployed files differ from the local build (--deep)
+    Drifted,
 }
 
 /// Status indicator for component version comparison.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ComponentStatus {
     /// Local and remote versions match
@@ -348,6 +801,8 @@ pub enum ComponentStatus {
     NeedsUpdate,
     /// Remote version ahead of local (local behind)
     BehindRemote,
+    /// Versions match but deployed files differ from the local build
+    Drifted,
     /// Cannot determine status
     Unknown,
 }
@@ -366,14 +821,29 @@ pub struct ComponentDeployResult {
     pub remote_version: Option<String>,
     pub error: Option<String>,
     pub artifact_path: Option<String>,
+    /// Checksum of the artifact as uploaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact_sha256: Option<String>,
     pub remote_path: Option<String>,
     pub build_command: Option<String>,
     pub build_exit_code: Option<i32>,
     pub deploy_exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer: Option<TransferStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drift: Option<DriftReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub health_checks: Vec<HealthCheckResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rolled_back: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<HookResult>,
 }
 
 impl ComponentDeployResult {
-    fn new(component: &Component, base_path: &str) -> Self {
+    pub(crate) fn new(component: &Component, base_path: &str) -> Self {
         Self {
             id: component.id.clone(),
             status: String::new(),
@@ -383,19 +853,26 @@ impl ComponentDeployResult {
             remote_version: None,
             error: None,
             artifact_path: Some(component.build_artifact.clone()),
+            artifact_sha256: None,
             remote_path: base_path::join_remote_path(Some(base_path), &component.remote_path).ok(),
             build_command: component.build_command.clone(),
             build_exit_code: None,
             deploy_exit_code: None,
+            release_dir: None,
+            transfer: None,
+            drift: None,
+            health_checks: Vec::new(),
+            rolled_back: None,
+            hooks: Vec::new(),
         }
     }
 
-    fn with_status(mut self, status: &str) -> Self {
+    pub(crate) fn with_status(mut self, status: &str) -> Self {
         self.status = status.to_string();
         self
     }
 
-    fn with_versions(mut self, local: Option<String>, remote: Option<String>) -> Self {
+    pub(crate) fn with_versions(mut self, local: Option<String>, remote: Option<String>) -> Self {
         self.local_version = local;
         self.remote_version = remote;
         self
@@ -416,19 +893,49 @@ impl ComponentDeployResult {
         self
     }
 
+    fn with_deploy_reason(mut self, reason: DeployReason) -> Self {
+        self.deploy_reason = Some(reason);
+        self
+    }
+
     fn with_component_status(mut self, status: ComponentStatus) -> Self {
         self.component_status = Some(status);
         self
     }
 
+    fn with_artifact_sha256(mut self, sha256: Option<String>) -> Self {
+        self.artifact_sha256 = sha256;
+        self
+    }
+
     fn with_remote_path(mut self, path: String) -> Self {
         self.remote_path = Some(path);
         self
     }
+
+    fn with_release_dir(mut self, release_dir: Option<String>) -> Self {
+        self.release_dir = release_dir;
+        self
+    }
+
+    fn with_transfer(mut self, transfer: Option<TransferStats>) -> Self {
+        self.transfer = transfer;
+        self
+    }
+
+    fn with_drift(mut self, drift: Option<DriftReport>) -> Self {
+        self.drift = drift;
+        self
+    }
+
+    fn with_health_checks(mut self, health_checks: Vec<HealthCheckResult>) -> Self {
+        self.health_checks = health_checks;
+        self
+    }
 }
 
 /// Summary of deploy orchestration.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 
 pub struct DeploySummary {
     pub total: u32,
@@ -443,16 +950,32 @@ pub struct DeploySummary {
 pub struct DeployOrchestrationResult {
     pub results: Vec<ComponentDeployResult>,
     pub summary: DeploySummary,
+    /// Project-level hook output
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<HookResult>,
 }
 
-/// High-level deploy entry point. Resolves SSH context internally.
+/// High-level deploy entry point. Resolves the deploy transport internally.
 ///
 /// This is the preferred entry point for callers - it handles project loading
-/// and SSH context resolution, keeping those details encapsulated.
+/// and transport resolution (SSH, or local for projects without a server),
+/// keeping those details encapsulated.
 pub fn run(project_id: &str, config: &DeployConfig) -> Result<DeployOrchestrationResult> {
-    let project = project::load(project_id)?;
-    let (ctx, base_path) = resolve_project_ssh_with_base_path(project_id)?;
-    deploy_components(config, &project, &ctx, &base_path)
+    let (project, transport, base_path) = resolve_deploy_target(project_id)?;
+    let result = deploy_components(config, &project, &transport, &base_path)?;
+
+    if !config.dry_run && !config.check && !result.results.is_empty() {
+        record_history(project_id, "deploy", &result);
+    }
+
+    Ok(result)
+}
+
+/// Append to the project's deploy history. Never fails the deploy itself.
+fn record_history(project_id: &str, action: &str, result: &DeployOrchestrationResult) {
+    if let Err(err) = deploy_history::record(project_id, action, result) {
+        deploy_log!("Warning: failed to record deploy history: {}", err);
+    }
 }
 
 /// Main deploy orchestration entry point.
@@ -460,7 +983,7 @@ pub fn run(project_id: &str, config: &DeployConfig) -> Result<DeployOrchestratio
 pub fn deploy_components(
     config: &DeployConfig,
     project: &Project,
-    ctx: &RemoteProjectContext,
+    transport: &Transport,
     base_path: &str,
 ) -> Result<DeployOrchestrationResult> {
     let all_components = load_project_components(&project.component_ids)?;
@@ -470,7 +993,7 @@ pub fn deploy_components(
         ));
     }
 
-    let components_to_deploy = plan_components(config, &all_components, base_path, &ctx.client)?;
+    let components_to_deploy = plan_components(config, &all_components, base_path, transport)?;
 
     if components_to_deploy.is_empty() {
         return Ok(DeployOrchestrationResult {
@@ -481,6 +1004,7 @@ pub fn deploy_components(
                 failed: 0,
                 skipped: 0,
             },
+            hooks: Vec::new(),
         });
     }
 
@@ -492,23 +1016,38 @@ pub fn deploy_components(
 
     // Gather remote versions if needed (for --outdated, --dry-run, or --check)
     let remote_versions = if config.outdated || config.dry_run || config.check {
-        fetch_remote_versions(&components_to_deploy, base_path, &ctx.client)
+        fetch_remote_versions(&components_to_deploy, base_path, transport)
     } else {
         HashMap::new()
     };
 
     // Check mode: return status results without building or deploying
     if config.check {
+        let mut drift = if config.deep {
+            detect_drift(&components_to_deploy, base_path, transport)?
+        } else {
+            HashMap::new()
+        };
+
         let results: Vec<ComponentDeployResult> = components_to_deploy
             .iter()
             .map(|c| {
                 let local_version = local_versions.get(&c.id).cloned();
                 let remote_version = remote_versions.get(&c.id).cloned();
-                let status = calculate_component_status(c, &remote_versions);
+                let report = drift.remove(&c.id);
+                let status = match calculate_component_status(c, &remote_versions) {
+                    ComponentStatus::UpToDate | ComponentStatus::Unknown
+                        if report.as_ref().is_some_and(|r| !r.is_clean()) =>
+                    {
+                        ComponentStatus::Drifted
+                    }
+                    status => status,
+                };
                 ComponentDeployResult::new(c, base_path)
                     .with_status("checked")
                     .with_versions(local_version, remote_version)
                     .with_component_status(status)
+                    .with_drift(report)
             })
             .collect();
 
@@ -521,6 +1060,7 @@ pub fn deploy_components(
                 failed: 0,
                 skipped: 0,
             },
+            hooks: Vec::new(),
         });
     }
 
@@ -536,8 +1076,10 @@ pub fn deploy_components(
                 } else {
                     ComponentStatus::Unknown
                 };
+                let reason = deploy_reason(config, local_version.as_ref(), remote_version.as_ref());
                 let mut result = ComponentDeployResult::new(c, base_path)
                     .with_status("planned")
+                    .with_deploy_reason(reason)
                     .with_versions(local_version, remote_version);
                 if config.check {
                     result = result.with_component_status(status);
@@ -555,136 +1097,45 @@ pub fn deploy_components(
                 failed: 0,
                 skipped: 0,
             },
+            hooks: Vec::new(),
         });
     }
 
-    // Execute deployments
-    let mut results: Vec<ComponentDeployResult> = vec![];
-    let mut succeeded: u32 = 0;
-    let mut failed: u32 = 0;
-
-    for component in &components_to_deploy {
-        let local_version = local_versions.get(&component.id).cloned();
-        let remote_version = remote_versions.get(&component.id).cloned();
-
-        // Build is mandatory before deploy
-        let (build_exit_code, build_error) = build::build_component(component);
-
-        if let Some(ref error) = build_error {
-            results.push(
-                ComponentDeployResult::new(component, base_path)
-                    .with_status("failed")
-                    .with_versions(local_version, remote_version)
-                    .with_error(error.clone())
-                    .with_build_exit_code(build_exit_code),
-            );
-            failed += 1;
-            continue;
-        }
-
-        // Check artifact exists after build
-        if !Path::new(&component.build_artifact).exists() {
-            results.push(
-                ComponentDeployResult::new(component, base_path)
-                    .with_status("failed")
-                    .with_versions(local_version, remote_version)
-                    .with_error(format!("Artifact not found: {}", component.build_artifact))
-                    .with_build_exit_code(build_exit_code),
-            );
-            failed += 1;
-            continue;
-        }
-
-        // Calculate install directory
-        let install_dir = match base_path::join_remote_path(Some(base_path), &component.remote_path)
-        {
-            Ok(v) => v,
-            Err(err) => {
-                results.push(
-                    ComponentDeployResult::new(component, base_path)
-                        .with_status("failed")
-                        .with_versions(local_version, remote_version)
-                        .with_error(err.to_string())
-                        .with_build_exit_code(build_exit_code),
-                );
-                failed += 1;
-                continue;
-            }
-        };
+    check_dependency_constraints(&components_to_deploy, &all_components, base_path, transport)?;
+    let keep_backups = defaults::load_defaults().deploy.keep_backups;
+    check_rollback_targets(project, &components_to_deploy, keep_backups)?;
 
-        // Look up verification from modules
-        let verification = find_deploy_verification(&install_dir);
-
-        // Check for module-defined deploy override
-        let deploy_result =
-            if let Some((override_config, module)) = find_deploy_override(&install_dir) {
-                deploy_with_override(
-                    &ctx.client,
-                    Path::new(&component.build_artifact),
-                    &install_dir,
-                    &override_config,
-                    &module,
-                    verification.as_ref(),
-                    Some(base_path),
-                    project.domain.as_deref(),
-                )
-            } else {
-                // Standard deploy
-                deploy_artifact(
-                    &ctx.client,
-                    Path::new(&component.build_artifact),
-                    &install_dir,
-                    component.extract_command.as_deref(),
-                    verification.as_ref(),
-                )
-            };
+    // Execute deployments
+    let _lock = deploy_lock::acquire(transport, &project.id, base_path, config.force_unlock)?;
+    let target = DeployTarget {
+        project,
+        transport,
+        base_path,
+    };
+    let deploy_one = |component: &Component| {
+        deploy_component(
+            component,
+            &target,
+            local_versions.get(&component.id).cloned(),
+            remote_versions.get(&component.id).cloned(),
+            keep_backups,
+        )
+    };
 
-        match deploy_result {
-            Ok(DeployResult {
-                success: true,
-                exit_code,
-                ..
-            }) => {
-                results.push(
-                    ComponentDeployResult::new(component, base_path)
-                        .with_status("deployed")
-                        .with_versions(local_version.clone(), local_version)
-                        .with_remote_path(install_dir)
-                        .with_build_exit_code(build_exit_code)
-                        .with_deploy_exit_code(Some(exit_code)),
-                );
-                succeeded += 1;
-            }
-            Ok(DeployResult {
-                success: false,
-                exit_code,
-                error,
-            }) => {
-                let mut result = ComponentDeployResult::new(component, base_path)
-                    .with_status("failed")
-                    .with_versions(local_version, remote_version)
-                    .with_remote_path(install_dir)
-                    .with_build_exit_code(build_exit_code)
-                    .with_deploy_exit_code(Some(exit_code));
-                if let Some(e) = error {
-                    result = result.with_error(e);
-                }
-                results.push(result);
-                failed += 1;
-            }
-            Err(err) => {
-                results.push(
-                    ComponentDeployResult::new(component, base_path)
-                        .with_status("failed")
-                        .with_versions(local_version, remote_version)
-                        .with_remote_path(install_dir)
-                        .with_error(err.to_string())
-                        .with_build_exit_code(build_exit_code),
-                );
-                failed += 1;
+    let (results, hooks) = with_project_hooks(&target, &components_to_deploy, || {
+        if config.parallel > 1 {
+            let mut results = Vec::with_capacity(components_to_deploy.len());
+            for batch in components_to_deploy.chunks(config.parallel) {
+                results.extend(execute_batch(batch, base_path, &deploy_one));
             }
+            Ok(results)
+        } else {
+            Ok(components_to_deploy.iter().map(deploy_one).collect())
         }
-    }
+    })?;
+
+    let succeeded = results.iter().filter(|r| r.status == "deployed").count() as u32;
+    let failed = results.len() as u32 - succeeded;
 
     Ok(DeployOrchestrationResult {
         results,
@@ -694,26 +1145,1492 @@ pub fn deploy_components(
             failed,
             skipped: 0,
         },
+        hooks,
     })
 }
 
-// =============================================================================
-// Helper Functions
-// =============================================================================
+/// Run project pre-deploy hooks, the component deploys, then post-deploy or
+/// failure hooks. Components are not deployed when a pre-deploy hook fails.
+fn with_project_hooks<F>(
+    target: &DeployTarget,
+    components: &[Component],
+    deploy: F,
+) -> Result<(Vec<ComponentDeployResult>, Vec<HookResult>)>
+where
+    F: FnOnce() -> Result<Vec<ComponentDeployResult>>,
+{
+    let project = target.project;
+    let vars = hook_vars(target, None, None);
+    let run_hooks = |stage, commands: &[String]| {
+        deploy_hooks::run(
+            target.transport,
+            stage,
+            commands,
+            &[target.base_path],
+            &vars,
+        )
+    };
 
-/// Plan which components to deploy based on config flags.
-fn plan_components(
-    config: &DeployConfig,
-    all_components: &[Component],
-    base_path: &str,
-    client: &SshClient,
-) -> Result<Vec<Component>> {
-    if !config.component_ids.is_empty() {
-        let selected: Vec<Component> = all_components
+    let mut hooks = run_hooks(HookStage::PreDeploy, &project.pre_deploy_commands);
+    let results = match deploy_hooks::failure_message(&hooks) {
+        Some(error) => components
             .iter()
-            .filter(|c| config.component_ids.contains(&c.id))
-            .cloned()
-            .collect();
+            .map(|c| {
+                ComponentDeployResult::new(c, target.base_path)
+                    .with_status("failed")
+                    .with_error(format!("Project {}", error))
+            })
+            .collect(),
+        None => deploy()?,
+    };
+
+    if results.iter().all(|r| r.status != "failed") {
+        hooks.extend(run_hooks(
+            HookStage::PostDeploy,
+            &project.post_deploy_commands,
+        ));
+    }
+    if hooks.iter().any(|h| !h.success) || results.iter().any(|r| r.status == "failed") {
+        hooks.extend(run_hooks(
+            HookStage::OnDeployFailure,
+            &project.on_deploy_failure,
+        ));
+    }
+
+    Ok((results, hooks))
+}
+
+/// Template variables available to deploy hooks.
+fn hook_vars(
+    target: &DeployTarget,
+    component: Option<&Component>,
+    install_dir: Option<&str>,
+) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+    vars.insert(
+        TemplateVars::PROJECT_ID.to_string(),
+        target.project.id.clone(),
+    );
+    vars.insert(
+        TemplateVars::DOMAIN.to_string(),
+        target.project.domain.clone().unwrap_or_default(),
+    );
+    vars.insert(
+        TemplateVars::SITE_PATH.to_string(),
+        target.base_path.to_string(),
+    );
+    if let Some(component) = component {
+        vars.insert(TemplateVars::COMPONENT_ID.to_string(), component.id.clone());
+        if let Some(version) = version::get_component_version(component) {
+            vars.insert(TemplateVars::VERSION.to_string(), version);
+        }
+    }
+    if let Some(dir) = install_dir {
+        vars.insert(TemplateVars::TARGET_DIR.to_string(), dir.to_string());
+    }
+    vars
+}
+
+/// Deploy a batch of components concurrently, one thread per component.
+/// Log lines from each thread are prefixed with the component ID. A thread
+/// that panics yields a failed result for its component.
+fn execute_batch<F>(
+    batch: &[Component],
+    base_path: &str,
+    deploy_one: &F,
+) -> Vec<ComponentDeployResult>
+where
+    F: Fn(&Component) -> ComponentDeployResult + Sync,
+{
+    use std::thread;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = batch
+            .iter()
+            .map(|component| {
+                scope.spawn(move || {
+                    LOG_COMPONENT.with(|c| *c.borrow_mut() = Some(component.id.clone()));
+                    deploy_one(component)
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .zip(batch)
+            .map(|(handle, component)| {
+                handle.join().unwrap_or_else(|panic| {
+                    let message = panic
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| panic.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "unknown panic".to_string());
+                    ComponentDeployResult::new(component, base_path)
+                        .with_status("failed")
+                        .with_error(format!("Deploy panicked: {}", message))
+                })
+            })
+            .collect()
+    })
+}
+
+/// Build and deploy a single component, returning its result (never fails the batch).
+fn deploy_component(
+    component: &Component,
+    target: &DeployTarget,
+    local_version: Option<String>,
+    remote_version: Option<String>,
+    keep_backups: usize,
+) -> ComponentDeployResult {
+    let base_path = target.base_path;
+
+    // Build is mandatory before deploy
+    let (build_exit_code, build_error) = build::build_component(component);
+
+    if let Some(ref error) = build_error {
+        return ComponentDeployResult::new(component, base_path)
+            .with_status("failed")
+            .with_versions(local_version, remote_version)
+            .with_error(error.clone())
+            .with_build_exit_code(build_exit_code);
+    }
+
+    // Check artifact exists after build
+    if !Path::new(&component.build_artifact).exists() {
+        return ComponentDeployResult::new(component, base_path)
+            .with_status("failed")
+            .with_versions(local_version, remote_version)
+            .with_error(format!("Artifact not found: {}", component.build_artifact))
+            .with_build_exit_code(build_exit_code);
+    }
+
+    upload_component(
+        component,
+        target,
+        local_version,
+        remote_version,
+        keep_backups,
+        build_exit_code,
+    )
+}
+
+/// Where a component is deployed: a project, its transport and base path.
+struct DeployTarget<'a> {
+    project: &'a Project,
+    transport: &'a Transport,
+    base_path: &'a str,
+}
+
+/// Upload an already-built component artifact to a project, running the
+/// component's deploy hooks around it.
+fn upload_component(
+    component: &Component,
+    target: &DeployTarget,
+    local_version: Option<String>,
+    remote_version: Option<String>,
+    keep_backups: usize,
+    build_exit_code: Option<i32>,
+) -> ComponentDeployResult {
+    let install_dir = base_path::join_remote_path(Some(target.base_path), &component.remote_path)
+        .unwrap_or_else(|_| target.base_path.to_string());
+    let vars = hook_vars(target, Some(component), Some(&install_dir));
+    let run_hooks = |stage, commands: &[String]| {
+        deploy_hooks::run(
+            target.transport,
+            stage,
+            commands,
+            &[&install_dir, target.base_path],
+            &vars,
+        )
+    };
+
+    let mut hooks = run_hooks(HookStage::PreDeploy, &component.pre_deploy_commands);
+    let mut result = match deploy_hooks::failure_message(&hooks) {
+        Some(error) => ComponentDeployResult::new(component, target.base_path)
+            .with_status("failed")
+            .with_versions(local_version, remote_version)
+            .with_build_exit_code(build_exit_code)
+            .with_error(error),
+        None => upload_component_artifact(
+            component,
+            target,
+            local_version,
+            remote_version,
+            keep_backups,
+            build_exit_code,
+        ),
+    };
+
+    if result.status == "deployed" {
+        let post = run_hooks(HookStage::PostDeploy, &component.post_deploy_commands);
+        if let Some(error) = deploy_hooks::failure_message(&post) {
+            result = result.with_status("failed").with_error(error);
+        }
+        hooks.extend(post);
+    }
+    if result.status == "failed" {
+        hooks.extend(run_hooks(
+            HookStage::OnDeployFailure,
+            &component.on_deploy_failure,
+        ));
+    }
+
+    result.hooks = hooks;
+    result
+}
+
+fn upload_component_artifact(
+    component: &Component,
+    target: &DeployTarget,
+    local_version: Option<String>,
+    remote_version: Option<String>,
+    keep_backups: usize,
+    build_exit_code: Option<i32>,
+) -> ComponentDeployResult {
+    let DeployTarget {
+        project,
+        transport,
+        base_path,
+    } = *target;
+
+    // Calculate install directory
+    let install_dir = match base_path::join_remote_path(Some(base_path), &component.remote_path) {
+        Ok(v) => v,
+        Err(err) => {
+            return ComponentDeployResult::new(component, base_path)
+                .with_status("failed")
+                .with_versions(local_version, remote_version)
+                .with_error(err.to_string())
+                .with_build_exit_code(build_exit_code);
+        }
+    };
+
+    // Back up the live directory so direct deploys can be rolled back
+    if component.atomic_deploy.is_none() && keep_backups > 0 {
+        let backup = backup_before_deploy(
+            transport,
+            &project.id,
+            &component.id,
+            &install_dir,
+            keep_backups,
+        );
+        if !backup.success {
+            let mut result = ComponentDeployResult::new(component, base_path)
+                .with_status("failed")
+                .with_versions(local_version, remote_version)
+                .with_remote_path(install_dir)
+                .with_build_exit_code(build_exit_code)
+                .with_deploy_exit_code(Some(backup.exit_code));
+            if let Some(e) = backup.error {
+                result = result.with_error(e);
+            }
+            return result;
+        }
+    }
+
+    // Look up verification from modules
+    let verification = find_deploy_verification(&install_dir);
+    let upload = component.upload.clone().unwrap_or_default();
+    let artifact_sha256 = checksum::sha256_path(Path::new(&component.build_artifact)).ok();
+
+    // Check for module-defined deploy override
+    let deploy_result = if let Some((override_config, module)) = find_deploy_override(&install_dir)
+    {
+        deploy_with_override(
+            transport,
+            Path::new(&component.build_artifact),
+            &install_dir,
+            &override_config,
+            &module,
+            verification.as_ref(),
+            Some(base_path),
+            project.domain.as_deref(),
+        )
+    } else if let Some(atomic) = component.atomic_deploy.as_ref() {
+        // Atomic release-directory deploy
+        deploy_artifact_atomic(
+            transport,
+            Path::new(&component.build_artifact),
+            &install_dir,
+            component.extract_command.as_deref(),
+            verification.as_ref(),
+            &upload,
+            atomic,
+        )
+    } else {
+        // Standard deploy
+        deploy_artifact(
+            transport,
+            Path::new(&component.build_artifact),
+            &install_dir,
+            component.extract_command.as_deref(),
+            verification.as_ref(),
+            &upload,
+        )
+    };
+
+    match deploy_result {
+        Ok(DeployResult {
+            success: true,
+            exit_code,
+            release_dir,
+            transfer,
+            ..
+        }) => {
+            let result = ComponentDeployResult::new(component, base_path)
+                .with_status("deployed")
+                .with_versions(local_version.clone(), local_version)
+                .with_artifact_sha256(artifact_sha256)
+                .with_remote_path(install_dir)
+                .with_release_dir(release_dir)
+                .with_transfer(transfer)
+                .with_build_exit_code(build_exit_code)
+                .with_deploy_exit_code(Some(exit_code));
+            run_health_checks(result, component, target)
+        }
+        Ok(DeployResult {
+            success: false,
+            exit_code,
+            error,
+            ..
+        }) => {
+            let mut result = ComponentDeployResult::new(component, base_path)
+                .with_status("failed")
+                .with_versions(local_version, remote_version)
+                .with_remote_path(install_dir)
+                .with_build_exit_code(build_exit_code)
+                .with_deploy_exit_code(Some(exit_code));
+            if let Some(e) = error {
+                result = result.with_error(e);
+            }
+            result
+        }
+        Err(err) => ComponentDeployResult::new(component, base_path)
+            .with_status("failed")
+            .with_versions(local_version, remote_version)
+            .with_remote_path(install_dir)
+            .with_error(err.to_string())
+            .with_build_exit_code(build_exit_code),
+    }
+}
+
+/// Refuse to deploy components whose health checks roll back on failure when
+/// there is nothing to roll back to: non-atomic components restore from
+/// pre-deploy backups, which `deploy.keep_backups: 0` disables.
+fn check_rollback_targets(
+    project: &Project,
+    components: &[Component],
+    keep_backups: usize,
+) -> Result<()> {
+    match rollback_target_problem(project, components, keep_backups) {
+        None => Ok(()),
+        Some(problem) => Err(Error::config_invalid_value(
+            "deploy.keep_backups",
+            Some(keep_backups.to_string()),
+            problem,
+        )
+        .with_hint(
+            "Set deploy.keep_backups to 1 or more, or enable atomic_deploy on the component",
+        )),
+    }
+}
+
+fn rollback_target_problem(
+    project: &Project,
+    components: &[Component],
+    keep_backups: usize,
+) -> Option<String> {
+    if keep_backups > 0 {
+        return None;
+    }
+
+    let unrecoverable: Vec<&str> = components
+        .iter()
+        .filter(|component| component.atomic_deploy.is_none())
+        .filter(|component| {
+            let configs: Vec<_> = [
+                project.health_check.as_ref(),
+                component.health_check.as_ref(),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            configs.iter().any(|c| c.rollback_on_failure)
+                && configs.iter().any(|c| !c.checks.is_empty())
+        })
+        .map(|component| component.id.as_str())
+        .collect();
+
+    (!unrecoverable.is_empty()).then(|| {
+        format!(
+            "health_check.rollback_on_failure has no rollback target for {}: backups are disabled",
+            unrecoverable.join(", ")
+        )
+    })
+}
+
+/// Run project and component HTTP health checks after a successful deploy.
+///
+/// A failing check marks the component failed and, when `rollback_on_failure`
+/// is set on either config, restores the previous release or backup.
+fn run_health_checks(
+    result: ComponentDeployResult,
+    component: &Component,
+    target: &DeployTarget,
+) -> ComponentDeployResult {
+    let configs: Vec<_> = [
+        target.project.health_check.as_ref(),
+        component.health_check.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let checks: Vec<_> = configs.iter().flat_map(|c| c.checks.clone()).collect();
+    if checks.is_empty() {
+        return result;
+    }
+
+    deploy_log!("Running {} health check(s)", checks.len());
+    let outcomes = health::run_checks(&checks, target.project);
+    let failures: Vec<String> = outcomes
+        .iter()
+        .filter(|o| !o.passed)
+        .map(|o| format!("{}: {}", o.url, o.error.as_deref().unwrap_or("failed")))
+        .collect();
+
+    let mut result = result.with_health_checks(outcomes);
+    if failures.is_empty() {
+        return result;
+    }
+
+    let mut error = format!("Health check failed: {}", failures.join("; "));
+    result = result.with_status("failed");
+
+    if configs.iter().any(|c| c.rollback_on_failure) {
+        deploy_log!("Health check failed, rolling back {}", component.id);
+        let rollback = rollback_component(
+            target.transport,
+            target.project,
+            component,
+            target.base_path,
+            None,
+            true,
+        );
+        let rolled_back = rollback.status == "rolled_back";
+        if rolled_back {
+            result.remote_version = rollback.remote_version;
+            result.release_dir = rollback.release_dir;
+            error.push_str("; rolled back to the previous deploy");
+        } else if let Some(rollback_error) = rollback.error {
+            error.push_str(&format!("; rollback failed: {}", rollback_error));
+        }
+        result.rolled_back = Some(rolled_back);
+    }
+
+    result.with_error(error)
+}
+
+// =============================================================================
+// Fan-out Deploy
+// =============================================================================
+
+/// Configuration for deploying one component to every project that uses it.
+#[derive(Debug, Clone)]
+pub struct FanOutConfig {
+    pub component_id: String,
+    pub outdated: bool,
+    pub dry_run: bool,
+    /// Replace existing remote deploy locks
+    pub force_unlock: bool,
+}
+
+/// Deploy results for one project in a fan-out deploy.
+#[derive(Debug, Clone, Serialize)]
+
+pub struct ProjectDeployResult {
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub results: Vec<ComponentDeployResult>,
+    pub summary: DeploySummary,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<HookResult>,
+}
+
+impl ProjectDeployResult {
+    fn from_results(project_id: String, results: Vec<ComponentDeployResult>) -> Self {
+        let summary = summarize(&results);
+        Self {
+            project_id,
+            error: None,
+            results,
+            summary,
+            hooks: Vec::new(),
+        }
+    }
+
+    fn unresolved(project_id: String, error: String) -> Self {
+        Self {
+            project_id,
+            error: Some(error),
+            results: vec![],
+            summary: DeploySummary {
+                total: 1,
+                succeeded: 0,
+                failed: 1,
+                skipped: 0,
+            },
+            hooks: Vec::new(),
+        }
+    }
+}
+
+/// Result of deploying one component to every consuming project.
+#[derive(Debug, Clone, Serialize)]
+
+pub struct FanOutResult {
+    pub component_id: String,
+    pub local_version: Option<String>,
+    pub build_exit_code: Option<i32>,
+    pub projects: Vec<ProjectDeployResult>,
+    pub summary: DeploySummary,
+}
+
+enum FanOutTarget {
+    Unresolved(String),
+    /// Remote version is current or newer (base path kept for the result)
+    Skipped {
+        base_path: String,
+        remote_version: Option<String>,
+        status: ComponentStatus,
+    },
+    Deploy(Box<FanOutProject>),
+}
+
+struct FanOutProject {
+    project: Project,
+    transport: Transport,
+    base_path: String,
+    remote_version: Option<String>,
+}
+
+/// Deploy one component to every project that references it.
+///
+/// The artifact is built once and uploaded to each project's server in turn.
+/// With `outdated`, projects already running the local version or a newer one
+/// are skipped.
+pub fn deploy_to_all_projects(config: &FanOutConfig) -> Result<FanOutResult> {
+    let component = load_project_components(std::slice::from_ref(&config.component_id))?.remove(0);
+
+    let project_ids = component::projects_using(&component.id)?;
+    if project_ids.is_empty() {
+        return Err(Error::validation_invalid_argument(
+            "component",
+            format!("Component '{}' is not used by any project", component.id),
+            Some(component.id.clone()),
+            None,
+        ));
+    }
+
+    let local_version = version::get_component_version(&component);
+    let fetch_versions = config.outdated || config.dry_run;
+
+    let targets: Vec<(String, FanOutTarget)> = project_ids
+        .into_iter()
+        .map(|project_id| {
+            let target = match resolve_deploy_target(&project_id) {
+                Err(err) => FanOutTarget::Unresolved(err.to_string()),
+                Ok((project, transport, base_path)) => {
+                    let remote_version = if fetch_versions {
+                        fetch_remote_versions(
+                            std::slice::from_ref(&component),
+                            &base_path,
+                            &transport,
+                        )
+                        .remove(&component.id)
+                    } else {
+                        None
+                    };
+
+                    if let Some(status) = fan_out_skip_status(
+                        config.outdated,
+                        local_version.as_deref(),
+                        remote_version.as_deref(),
+                    ) {
+                        FanOutTarget::Skipped {
+                            base_path,
+                            remote_version,
+                            status,
+                        }
+                    } else {
+                        FanOutTarget::Deploy(Box::new(FanOutProject {
+                            project,
+                            transport,
+                            base_path,
+                            remote_version,
+                        }))
+                    }
+                }
+            };
+            (project_id, target)
+        })
+        .collect();
+
+    let has_deploys = targets
+        .iter()
+        .any(|(_, t)| matches!(t, FanOutTarget::Deploy(_)));
+
+    // Build once for all projects
+    let mut build_exit_code = None;
+    let mut build_error = None;
+    if has_deploys && !config.dry_run {
+        deploy_log!("Building {} once for all projects", component.id);
+        let (exit_code, error) = build::build_component(&component);
+        build_exit_code = exit_code;
+        build_error = error.or_else(|| {
+            (!Path::new(&component.build_artifact).exists())
+                .then(|| format!("Artifact not found: {}", component.build_artifact))
+        });
+    }
+
+    let keep_backups = defaults::load_defaults().deploy.keep_backups;
+    let mut projects = Vec::new();
+
+    for (project_id, target) in targets {
+        let project_result = match target {
+            FanOutTarget::Unresolved(error) => ProjectDeployResult::unresolved(project_id, error),
+            FanOutTarget::Skipped {
+                base_path,
+                remote_version,
+                status,
+            } => {
+                let result = ComponentDeployResult::new(&component, &base_path)
+                    .with_status("skipped")
+                    .with_versions(local_version.clone(), remote_version)
+                    .with_component_status(status);
+                ProjectDeployResult::from_results(project_id, vec![result])
+            }
+            FanOutTarget::Deploy(target) => {
+                let FanOutProject {
+                    project,
+                    transport,
+                    base_path,
+                    remote_version,
+                } = *target;
+                let (result, hooks) = if config.dry_run {
+                    let result = ComponentDeployResult::new(&component, &base_path)
+                        .with_status("planned")
+                        .with_versions(local_version.clone(), remote_version);
+                    (result, Vec::new())
+                } else if let Some(error) = &build_error {
+                    let result = ComponentDeployResult::new(&component, &base_path)
+                        .with_status("failed")
+                        .with_versions(local_version.clone(), remote_version)
+                        .with_error(error.clone())
+                        .with_build_exit_code(build_exit_code);
+                    (result, Vec::new())
+                } else {
+                    deploy_log!("Deploying {} to project {}", component.id, project_id);
+                    let target = DeployTarget {
+                        project: &project,
+                        transport: &transport,
+                        base_path: &base_path,
+                    };
+                    let preflight = match rollback_target_problem(
+                        &project,
+                        std::slice::from_ref(&component),
+                        keep_backups,
+                    ) {
+                        Some(problem) => Err(problem),
+                        None => load_project_components(&project.component_ids)
+                            .and_then(|all_components| {
+                                check_dependency_constraints(
+                                    std::slice::from_ref(&component),
+                                    &all_components,
+                                    &base_path,
+                                    &transport,
+                                )
+                            })
+                            .and_then(|()| {
+                                deploy_lock::acquire(
+                                    &transport,
+                                    &project.id,
+                                    &base_path,
+                                    config.force_unlock,
+                                )
+                            })
+                            .map_err(|err| error_text(&err)),
+                    };
+                    match preflight {
+                        Ok(_lock) => {
+                            let (mut results, hooks) = with_project_hooks(
+                                &target,
+                                std::slice::from_ref(&component),
+                                || {
+                                    Ok(vec![upload_component(
+                                        &component,
+                                        &target,
+                                        local_version.clone(),
+                                        remote_version,
+                                        keep_backups,
+                                        build_exit_code,
+                                    )])
+                                },
+                            )?;
+                            (results.remove(0), hooks)
+                        }
+                        Err(error) => {
+                            let result = ComponentDeployResult::new(&component, &base_path)
+                                .with_status("failed")
+                                .with_versions(local_version.clone(), remote_version)
+                                .with_error(error)
+                                .with_build_exit_code(build_exit_code);
+                            (result, Vec::new())
+                        }
+                    }
+                };
+
+                let mut project_result =
+                    ProjectDeployResult::from_results(project_id, vec![result]);
+                project_result.hooks = hooks;
+                if !config.dry_run {
+                    record_history(
+                        &project_result.project_id,
+                        "deploy",
+                        &DeployOrchestrationResult {
+                            results: project_result.results.clone(),
+                            summary: project_result.summary.clone(),
+                            hooks: project_result.hooks.clone(),
+                        },
+                    );
+                }
+                project_result
+            }
+        };
+        projects.push(project_result);
+    }
+
+    let summary = DeploySummary {
+        total: projects.iter().map(|p| p.summary.total).sum(),
+        succeeded: projects.iter().map(|p| p.summary.succeeded).sum(),
+        failed: projects.iter().map(|p| p.summary.failed).sum(),
+        skipped: projects.iter().map(|p| p.summary.skipped).sum(),
+    };
+
+    Ok(FanOutResult {
+        component_id: component.id,
+        local_version,
+        build_exit_code,
+        projects,
+        summary,
+    })
+}
+
+/// With `outdated`, the status a project is skipped with: its remote version
+/// is the same as or newer than the local one. `None` means deploy.
+fn fan_out_skip_status(
+    outdated: bool,
+    local_version: Option<&str>,
+    remote_version: Option<&str>,
+) -> Option<ComponentStatus> {
+    if !outdated || local_version.is_none() {
+        return None;
+    }
+    match version_status(local_version, remote_version) {
+        status @ (ComponentStatus::UpToDate | ComponentStatus::BehindRemote) => Some(status),
+        _ => None,
+    }
+}
+
+/// Text for a failed per-project result: a validation problem with what it
+/// refers to, the underlying error, or else the error message.
+pub(crate) fn error_text(err: &Error) -> String {
+    let detail = ["problem", "error"]
+        .iter()
+        .find_map(|key| err.details.get(key).and_then(|v| v.as_str()));
+    let Some(problem) = detail else {
+        return err.message.clone();
+    };
+    let tried: Vec<&str> = err
+        .details
+        .get("tried")
+        .and_then(|t| t.as_array())
+        .map(|items| items.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    if tried.is_empty() {
+        problem.to_string()
+    } else {
+        format!("{}: {}", problem, tried.join("; "))
+    }
+}
+
+/// Load a project with the transport and base path its deploys use.
+/// Projects without a `server_id` deploy to the local filesystem.
+fn resolve_deploy_target(project_id: &str) -> Result<(Project, Transport, String)> {
+    let project = project::load(project_id)?;
+    let base_path = require_project_base_path(project_id, &project)?;
+    let transport = Transport::for_project(&project)?;
+    Ok((project, transport, base_path))
+}
+
+/// Count component results by status.
+fn summarize(results: &[ComponentDeployResult]) -> DeploySummary {
+    let count = |status: &str| results.iter().filter(|r| r.status == status).count() as u32;
+    DeploySummary {
+        total: results.len() as u32,
+        succeeded: count("deployed"),
+        failed: count("failed"),
+        skipped: count("skipped"),
+    }
+}
+
+// =============================================================================
+// Plan and Apply
+// =============================================================================
+
+/// Stands in for the release directory name in planned atomic deploy commands.
+const RELEASE_PLACEHOLDER: &str = "<release>";
+
+/// Build the selected components and freeze what would be deployed into a plan.
+///
+/// Nothing is uploaded. A component that fails to build fails the whole plan.
+pub fn plan(project_id: &str, config: &DeployConfig) -> Result<DeployPlan> {
+    let (project, transport, base_path) = resolve_deploy_target(project_id)?;
+    let all_components = load_project_components(&project.component_ids)?;
+    if all_components.is_empty() {
+        return Err(Error::other(
+            "No components configured for project".to_string(),
+        ));
+    }
+
+    let components = plan_components(config, &all_components, &base_path, &transport)?;
+    check_dependency_constraints(&components, &all_components, &base_path, &transport)?;
+    let remote_versions = fetch_remote_versions(&components, &base_path, &transport);
+    let target = DeployTarget {
+        project: &project,
+        transport: &transport,
+        base_path: &base_path,
+    };
+
+    let mut planned = Vec::with_capacity(components.len());
+    for component in &components {
+        let (_, build_error) = build::build_component(component);
+        if let Some(error) = build_error {
+            return Err(Error::other(format!(
+                "Build failed for {}: {}",
+                component.id, error
+            )));
+        }
+
+        let artifact = Path::new(&component.build_artifact);
+        if !artifact.exists() {
+            return Err(Error::other(format!(
+                "Artifact not found: {}",
+                component.build_artifact
+            )));
+        }
+
+        let local_version = version::get_component_version(component);
+        let remote_version = remote_versions.get(&component.id).cloned();
+        let remote_path = base_path::join_remote_path(Some(&base_path), &component.remote_path)?;
+
+        planned.push(PlannedComponent {
+            id: component.id.clone(),
+            deploy_reason: deploy_reason(config, local_version.as_ref(), remote_version.as_ref()),
+            local_version,
+            remote_version,
+            artifact_path: component.build_artifact.clone(),
+            artifact_sha256: checksum::sha256_path(artifact)?,
+            commands: planned_commands(component, &target, &remote_path)?,
+            strategy: planned_strategy(component),
+            remote_path,
+        });
+    }
+
+    let project_hooks = planned_project_hooks(&target);
+    Ok(DeployPlan {
+        format_version: PLAN_FORMAT_VERSION,
+        project_id: project.id.clone(),
+        target: transport.label(),
+        base_path,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        created_by: deploy_history::operator(),
+        project_hooks,
+        components: planned,
+    })
+}
+
+/// Deploy exactly what a saved plan describes, without rebuilding.
+///
+/// Refuses to run when the target, an artifact, a remote version or a rendered
+/// command no longer matches the plan. The check runs under the deploy lock.
+pub fn apply(plan: &DeployPlan, force_unlock: bool) -> Result<DeployOrchestrationResult> {
+    let (project, transport, base_path) = resolve_deploy_target(&plan.project_id)?;
+    let component_ids: Vec<String> = plan.components.iter().map(|c| c.id.clone()).collect();
+    let components = load_project_components(&component_ids)?;
+
+    let _lock = deploy_lock::acquire(&transport, &project.id, &base_path, force_unlock)?;
+    let target = DeployTarget {
+        project: &project,
+        transport: &transport,
+        base_path: &base_path,
+    };
+
+    let mut stale = Vec::new();
+    if transport.label() != plan.target || base_path != plan.base_path {
+        stale.push(format!(
+            "project deploys to {}:{} (planned against {}:{})",
+            transport.label(),
+            base_path,
+            plan.target,
+            plan.base_path
+        ));
+    }
+    if planned_project_hooks(&target) != plan.project_hooks {
+        stale.push("project deploy hooks changed since planning".to_string());
+    }
+
+    let remote_versions = fetch_remote_versions(&components, &base_path, &transport);
+    for (planned, component) in plan.components.iter().zip(&components) {
+        if !project.component_ids.contains(&component.id) {
+            stale.push(format!("{}: no longer a project component", component.id));
+            continue;
+        }
+
+        let remote_path = base_path::join_remote_path(Some(&base_path), &component.remote_path)?;
+        if remote_path != planned.remote_path || component.build_artifact != planned.artifact_path {
+            stale.push(format!(
+                "{}: artifact or remote path changed since planning",
+                component.id
+            ));
+            continue;
+        }
+
+        let artifact = Path::new(&planned.artifact_path);
+        let artifact_sha256 = if artifact.exists() {
+            Some(checksum::sha256_path(artifact)?)
+        } else {
+            None
+        };
+        let commands = planned_commands(component, &target, &remote_path)?;
+        stale.extend(deploy_plan::stale_reasons(
+            planned,
+            artifact_sha256.as_deref(),
+            remote_versions.get(&component.id).map(String::as_str),
+            &planned_strategy(component),
+            &commands,
+        ));
+    }
+
+    if !stale.is_empty() {
+        return Err(Error::validation_invalid_argument(
+            "plan",
+            "Deploy plan is stale; create and review a new plan",
+            Some(plan.project_id.clone()),
+            Some(stale),
+        ));
+    }
+
+    // Dependencies outside the plan may have been redeployed since planning.
+    let all_components = load_project_components(&project.component_ids)?;
+    let planned_versions: HashMap<String, String> = plan
+        .components
+        .iter()
+        .filter_map(|c| c.local_version.clone().map(|v| (c.id.clone(), v)))
+        .collect();
+    check_shipping_constraints(
+        &components,
+        &planned_versions,
+        &all_components,
+        &base_path,
+        &transport,
+    )?;
+
+    let keep_backups = defaults::load_defaults().deploy.keep_backups;
+    check_rollback_targets(&project, &components, keep_backups)?;
+    let (results, hooks) = with_project_hooks(&target, &components, || {
+        Ok(plan
+            .components
+            .iter()
+            .zip(&components)
+            .map(|(planned, component)| {
+                upload_component(
+                    component,
+                    &target,
+                    planned.local_version.clone(),
+                    planned.remote_version.clone(),
+                    keep_backups,
+                    None,
+                )
+                .with_deploy_reason(planned.deploy_reason.clone())
+            })
+            .collect())
+    })?;
+
+    let result = DeployOrchestrationResult {
+        summary: summarize(&results),
+        results,
+        hooks,
+    };
+    record_history(&plan.project_id, "deploy", &result);
+
+    Ok(result)
+}
+
+/// Atomic and upload settings a deploy of `component` will use, with the
+/// upload strategy resolved so a changed default also makes a plan stale.
+fn planned_strategy(component: &Component) -> PlannedStrategy {
+    let mut upload = component.upload.clone().unwrap_or_default();
+    upload.strategy = Some(upload.effective_strategy());
+    PlannedStrategy {
+        atomic_deploy: component.atomic_deploy.clone(),
+        upload,
+    }
+}
+
+/// Render the commands a deploy of `component` into `install_dir` will run,
+/// following the same override -> atomic -> standard order as the deploy itself.
+fn planned_commands(
+    component: &Component,
+    target: &DeployTarget,
+    install_dir: &str,
+) -> Result<PlannedCommands> {
+    let local_path = Path::new(&component.build_artifact);
+    let hooks = planned_hooks(
+        &component.pre_deploy_commands,
+        &component.post_deploy_commands,
+        &component.on_deploy_failure,
+        &hook_vars(target, Some(component), Some(install_dir)),
+    );
+    let (project, base_path) = (target.project, target.base_path);
+
+    if let Some((override_config, module)) = find_deploy_override(install_dir) {
+        let vars = override_vars(
+            &override_config,
+            &module,
+            artifact_file_name(local_path)?,
+            install_dir,
+            Some(base_path),
+            project.domain.as_deref(),
+        );
+        return Ok(PlannedCommands {
+            override_module: Some(module.id.clone()),
+            install_command: Some(render_map(&override_config.install_command, &vars)),
+            cleanup_command: override_config
+                .cleanup_command
+                .as_ref()
+                .map(|template| render_map(template, &vars)),
+            hooks,
+            ..Default::default()
+        });
+    }
+
+    // Directory artifacts are copied as-is; extract commands only apply to files
+    let Some(template) = component.extract_command.as_deref() else {
+        return Ok(PlannedCommands {
+            hooks,
+            ..Default::default()
+        });
+    };
+    if local_path.is_dir() {
+        return Ok(PlannedCommands {
+            hooks,
+            ..Default::default()
+        });
+    }
+
+    let target_dir = if component.atomic_deploy.is_some() {
+        format!("{}/{}/{}", install_dir, RELEASES_DIR, RELEASE_PLACEHOLDER)
+    } else {
+        install_dir.to_string()
+    };
+
+    Ok(PlannedCommands {
+        extract_command: Some(render_extract_command(
+            template,
+            &uploaded_artifact_name(local_path)?,
+            &target_dir,
+        )),
+        hooks,
+        ..Default::default()
+    })
+}
+
+/// Hook commands as the deploy will run them.
+fn planned_hooks(
+    pre_deploy: &[String],
+    post_deploy: &[String],
+    on_failure: &[String],
+    vars: &HashMap<String, String>,
+) -> PlannedHooks {
+    let render = |commands: &[String]| -> Vec<String> {
+        commands
+            .iter()
+            .map(|template| render_map(template, vars))
+            .collect()
+    };
+    PlannedHooks {
+        pre_deploy_commands: render(pre_deploy),
+        post_deploy_commands: render(post_deploy),
+        on_deploy_failure: render(on_failure),
+    }
+}
+
+/// Project hook commands as the deploy will run them.
+fn planned_project_hooks(target: &DeployTarget) -> PlannedHooks {
+    planned_hooks(
+        &target.project.pre_deploy_commands,
+        &target.project.post_deploy_commands,
+        &target.project.on_deploy_failure,
+        &hook_vars(target, None, None),
+    )
+}
+
+// =============================================================================
+// Rollback
+// =============================================================================
+
+/// Configuration for deploy rollback.
+#[derive(Debug, Clone)]
+pub struct RollbackConfig {
+    pub component_ids: Vec<String>,
+    /// Release directory name or version to restore (default: previous release)
+    pub to: Option<String>,
+    /// Replace an existing remote deploy lock
+    pub force_unlock: bool,
+}
+
+/// A retained release directory or pre-deploy backup that can be restored.
+struct RollbackTarget {
+    name: String,
+    dir: String,
+    version: Option<String>,
+}
+
+/// High-level rollback entry point. Resolves the deploy transport internally.
+///
+/// Atomic components switch `current` back to a retained release directory;
+/// other components are restored from their latest pre-deploy backup.
+pub fn rollback(project_id: &str, config: &RollbackConfig) -> Result<DeployOrchestrationResult> {
+    let (project, transport, base_path) = resolve_deploy_target(project_id)?;
+    let result = rollback_components(config, &project, &transport, &base_path)?;
+
+    if !result.results.is_empty() {
+        record_history(project_id, "rollback", &result);
+    }
+
+    Ok(result)
+}
+
+/// Roll back the selected components (all project components when none are given).
+pub fn rollback_components(
+    config: &RollbackConfig,
+    project: &Project,
+    transport: &Transport,
+    base_path: &str,
+) -> Result<DeployOrchestrationResult> {
+    let all_components = load_project_components(&project.component_ids)?;
+    if all_components.is_empty() {
+        return Err(Error::other(
+            "No components configured for project".to_string(),
+        ));
+    }
+
+    let explicit = !config.component_ids.is_empty();
+    let selection = DeployConfig {
+        component_ids: config.component_ids.clone(),
+        all: true,
+        outdated: false,
+        dry_run: false,
+        check: false,
+        deep: false,
+        parallel: 1,
+        force_unlock: false,
+    };
+    let components = plan_components(&selection, &all_components, base_path, transport)?;
+    let _lock = deploy_lock::acquire(transport, &project.id, base_path, config.force_unlock)?;
+
+    let mut results: Vec<ComponentDeployResult> = vec![];
+    let mut succeeded: u32 = 0;
+    let mut failed: u32 = 0;
+    let mut skipped: u32 = 0;
+
+    for component in &components {
+        let result = rollback_component(
+            transport,
+            project,
+            component,
+            base_path,
+            config.to.as_deref(),
+            explicit,
+        );
+        match result.status.as_str() {
+            "rolled_back" => succeeded += 1,
+            "skipped" => skipped += 1,
+            _ => failed += 1,
+        }
+        results.push(result);
+    }
+
+    Ok(DeployOrchestrationResult {
+        results,
+        summary: DeploySummary {
+            total: succeeded + failed + skipped,
+            succeeded,
+            failed,
+            skipped,
+        },
+        hooks: Vec::new(),
+    })
+}
+
+fn rollback_component(
+    transport: &Transport,
+    project: &Project,
+    component: &Component,
+    base_path: &str,
+    to: Option<&str>,
+    explicit: bool,
+) -> ComponentDeployResult {
+    let local_version = version::get_component_version(component);
+    let result = ComponentDeployResult::new(component, base_path);
+
+    let install_dir = match base_path::join_remote_path(Some(base_path), &component.remote_path) {
+        Ok(v) => v,
+        Err(err) => return result.with_status("failed").with_error(err.to_string()),
+    };
+    let result = result.with_remote_path(install_dir.clone());
+    let atomic = component.atomic_deploy.is_some();
+
+    // Non-atomic rollbacks skip backups of the version that is live now, so a
+    // second rollback goes one backup further back
+    let live_version = if atomic {
+        None
+    } else {
+        read_remote_version(transport, component, &install_dir)
+    };
+
+    let (candidates, current) = if atomic {
+        let releases_root = format!("{}/{}", install_dir, RELEASES_DIR);
+        let current = transport
+            .execute(&format!(
+                "readlink {}",
+                shell::quote_path(&format!("{}/{}", install_dir, CURRENT_LINK))
+            ))
+            .stdout
+            .trim()
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+        (list_remote_entries(transport, &releases_root), current)
+    } else {
+        let root = backup_root(&project.id, &component.id);
+        (list_remote_entries(transport, &root), None)
+    };
+
+    let Some(target) = select_rollback_target(
+        transport,
+        component,
+        &candidates,
+        current.as_deref(),
+        live_version.as_deref(),
+        to,
+    ) else {
+        let reason = match to {
+            Some(to) => format!("No release or backup matching '{}'", to),
+            None if atomic => "No previous release to roll back to".to_string(),
+            None if !candidates.is_empty() => format!(
+                "Every pre-deploy backup holds the deployed version {}. \
+                 Pick one explicitly with --to <backup>",
+                live_version.as_deref().unwrap_or("unknown")
+            ),
+            None => "No pre-deploy backup to roll back to. \
+                     Enable backups with defaults.deploy.keep_backups or use atomic_deploy"
+                .to_string(),
+        };
+        let status = if explicit || to.is_some() {
+            "failed"
+        } else {
+            "skipped"
+        };
+        return result
+            .with_status(status)
+            .with_versions(local_version, None)
+            .with_error(reason);
+    };
+
+    deploy_log!(
+        "Rolling back {} to {}{}",
+        component.id,
+        target.name,
+        target
+            .version
+            .as_ref()
+            .map(|v| format!(" ({})", v))
+            .unwrap_or_default()
+    );
+
+    let verification = find_deploy_verification(&install_dir);
+    let outcome = restore_rollback_target(
+        transport,
+        &install_dir,
+        &target,
+        atomic,
+        verification.as_ref(),
+    );
+
+    let release_dir = atomic.then(|| target.dir.clone());
+    let mut result = result
+        .with_versions(local_version, target.version)
+        .with_release_dir(release_dir)
+        .with_deploy_exit_code(Some(outcome.exit_code));
+
+    if outcome.success {
+        result.with_status("rolled_back")
+    } else {
+        if let Some(e) = outcome.error {
+            result = result.with_error(e);
+        }
+        result.with_status("failed")
+    }
+}
+
+/// Put a rollback target in place. The target is verified before it goes
+/// live: atomic releases before `current` is switched, backups before they
+/// replace the install directory (and again once restored).
+fn restore_rollback_target(
+    transport: &Transport,
+    install_dir: &str,
+    target: &RollbackTarget,
+    atomic: bool,
+    verification: Option<&DeployVerification>,
+) -> DeployResult {
+    if let Some(failure) = run_verification(transport, verification, &target.dir) {
+        return failure;
+    }
+    if atomic {
+        return switch_current_release(transport, install_dir, &target.name);
+    }
+
+    let restored = restore_backup(transport, &target.dir, install_dir);
+    if !restored.success {
+        return restored;
+    }
+    run_verification(transport, verification, install_dir).unwrap_or(restored)
+}
+
+/// List entries of a remote directory, newest (highest timestamp) first.
+fn list_remote_entries(transport: &Transport, dir: &str) -> Vec<String> {
+    let output = transport.execute(&format!(
+        "ls -1 {} 2>/dev/null | sort -r",
+        shell::quote_path(dir)
+    ));
+    output
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| format!("{}/{}", dir, line))
+        .collect()
+}
+
+/// Pick the entry to restore: an explicit `--to` release name or version,
+/// otherwise the release before `current` (atomic) or the newest backup whose
+/// version differs from the live one.
+fn select_rollback_target(
+    transport: &Transport,
+    component: &Component,
+    candidates: &[String],
+    current: Option<&str>,
+    live_version: Option<&str>,
+    to: Option<&str>,
+) -> Option<RollbackTarget> {
+    let to_target = |dir: &String| {
+        let name = dir.rsplit('/').next().unwrap_or(dir).to_string();
+        RollbackTarget {
+            version: read_remote_version(transport, component, dir),
+            name,
+            dir: dir.clone(),
+        }
+    };
+
+    if let Some(to) = to {
+        let wanted = to.trim_start_matches('v');
+        if let Some(dir) = candidates
+            .iter()
+            .find(|dir| dir.ends_with(&format!("/{}", to)))
+        {
+            return Some(to_target(dir));
+        }
+        return candidates
+            .iter()
+            .map(to_target)
+            .find(|t| t.version.as_deref() == Some(wanted));
+    }
+
+    match current {
+        Some(current) => candidates
+            .iter()
+            .find(|dir| dir.rsplit('/').next().is_some_and(|name| name < current))
+            .map(to_target),
+        None if component.atomic_deploy.is_some() => None,
+        None => candidates
+            .iter()
+            .map(to_target)
+            .find(|t| live_version.is_none() || t.version.as_deref() != live_version),
+    }
+}
+
+/// Replace the live component directory with a backup copy.
+/// Copies into a staging directory first so a failed copy leaves the live directory intact.
+fn restore_backup(transport: &Transport, backup_dir: &str, install_dir: &str) -> DeployResult {
+    let staging = format!("{}.homeboy-rollback", install_dir.trim_end_matches('/'));
+    let restore_cmd = format!(
+        "rm -rf {staging} && cp -a {backup} {staging} && rm -rf {install} && mv {staging} {install}",
+        staging = shell::quote_path(&staging),
+        backup = shell::quote_path(backup_dir),
+        install = shell::quote_path(install_dir)
+    );
+
+    let output = transport.execute(&restore_cmd);
+    if !output.success {
+        return DeployResult::failure(
+            output.exit_code,
+            format!("Failed to restore backup: {}", output.stderr),
+        );
+    }
+
+    DeployResult::success(0)
+}
+
+// =============================================================================
+// Helper Functions
+// =============================================================================
+
+/// Plan which components to deploy based on config flags.
+fn plan_components(
+    config: &DeployConfig,
+    all_components: &[Component],
+    base_path: &str,
+    transport: &Transport,
+) -> Result<Vec<Component>> {
+    if config.deep && !config.check && !config.outdated {
+        return Err(Error::validation_invalid_argument(
+            "deep",
+            "--deep only applies with --check or --outdated",
+            None,
+            None,
+        ));
+    }
+
+    if !config.component_ids.is_empty() {
+        let selected: Vec<Component> = all_components
+            .iter()
+            .filter(|c| config.component_ids.contains(&c.id))
+            .cloned()
+            .collect();
 
         let missing: Vec<String> = config
             .component_ids
@@ -752,11 +2669,20 @@ fn plan_components(
     }
 
     if config.outdated {
-        let remote_versions = fetch_remote_versions(all_components, base_path, client);
+        let remote_versions = fetch_remote_versions(all_components, base_path, transport);
+        let drift = if config.deep {
+            detect_drift(all_components, base_path, transport)?
+        } else {
+            HashMap::new()
+        };
 
         let selected: Vec<Component> = all_components
             .iter()
             .filter(|c| {
+                if drift.get(&c.id).is_some_and(|r| !r.is_clean()) {
+                    return true;
+                }
+
                 let Some(local_version) = version::get_component_version(c) else {
                     return true;
                 };
@@ -765,7 +2691,8 @@ fn plan_components(
                     return true;
                 };
 
-                local_version != *remote_version
+                version_status(Some(&local_version), Some(remote_version))
+                    == ComponentStatus::NeedsUpdate
             })
             .cloned()
             .collect();
@@ -787,25 +2714,59 @@ fn plan_components(
     ))
 }
 
+/// Why `plan_components` selected a component.
+fn deploy_reason(
+    config: &DeployConfig,
+    local_version: Option<&String>,
+    remote_version: Option<&String>,
+) -> DeployReason {
+    if !config.component_ids.is_empty() {
+        return DeployReason::ExplicitlySelected;
+    }
+    if config.all {
+        return DeployReason::AllSelected;
+    }
+
+    match (local_version, remote_version) {
+        (None, _) => DeployReason::UnknownLocalVersion,
+        (_, None) => DeployReason::UnknownRemoteVersion,
+        (Some(local), Some(remote))
+            if version_status(Some(local), Some(remote)) != ComponentStatus::UpToDate =>
+        {
+            DeployReason::VersionMismatch
+        }
+        _ => DeployReason::Drifted,
+    }
+}
+
 /// Calculate component status based on local and remote versions.
 fn calculate_component_status(
     component: &Component,
     remote_versions: &HashMap<String, String>,
 ) -> ComponentStatus {
     let local_version = version::get_component_version(component);
-    let remote_version = remote_versions.get(&component.id);
+    version_status(
+        local_version.as_deref(),
+        remote_versions.get(&component.id).map(String::as_str),
+    )
+}
 
-    match (local_version, remote_version) {
+/// Compare a local and remote version. Versions that differ only in a `v`
+/// prefix or build metadata are up to date; unparseable versions fall back
+/// to string equality.
+fn version_status(local: Option<&str>, remote: Option<&str>) -> ComponentStatus {
+    use std::cmp::Ordering;
+
+    match (local, remote) {
         (None, None) => ComponentStatus::Unknown,
-        (None, Some(_)) => ComponentStatus::NeedsUpdate,
-        (Some(_), None) => ComponentStatus::NeedsUpdate,
-        (Some(local), Some(remote)) => {
-            if local == *remote {
-                ComponentStatus::UpToDate
-            } else {
-                ComponentStatus::NeedsUpdate
-            }
-        }
+        (None, Some(_)) | (Some(_), None) => ComponentStatus::NeedsUpdate,
+        (Some(local), Some(remote)) => match version::compare_versions(local, remote) {
+            Some(Ordering::Equal) => ComponentStatus::UpToDate,
+            Some(Ordering::Less) => ComponentStatus::BehindRemote,
+            Some(Ordering::Greater) => ComponentStatus::NeedsUpdate,
+            None if local == remote => ComponentStatus::UpToDate,
+            None => ComponentStatus::NeedsUpdate,
+        },
     }
 }
 
@@ -829,55 +2790,182 @@ fn load_project_components(component_ids: &[String]) -> Result<Vec<Component>> {
 fn fetch_remote_versions(
     components: &[Component],
     base_path: &str,
-    client: &SshClient,
+    transport: &Transport,
 ) -> HashMap<String, String> {
     let mut versions = HashMap::new();
 
     for component in components {
-        let Some(version_file) = component
-            .version_targets
-            .as_ref()
-            .and_then(|targets| targets.first())
-            .map(|t| t.file.as_str())
-        else {
+        let Ok(deployed_dir) = deployed_dir(component, base_path) else {
             continue;
         };
 
-        let remote_path = match base_path::join_remote_child(
-            Some(base_path),
-            &component.remote_path,
-            version_file,
-        ) {
-            Ok(value) => value,
-            Err(_) => continue,
-        };
+        if let Some(ver) = read_remote_version(transport, component, &deployed_dir) {
+            versions.insert(component.id.clone(), ver);
+        }
+    }
 
-        let output = client.execute(&format!("cat '{}' 2>/dev/null", remote_path));
+    versions
+}
 
-        if output.success {
-            let pattern = component
-                .version_targets
-                .as_ref()
-                .and_then(|targets| targets.first())
-                .and_then(|t| t.pattern.as_deref());
+/// Versions currently deployed for the given components of a project.
+pub fn deployed_versions(
+    project_id: &str,
+    component_ids: &[String],
+) -> Result<HashMap<String, String>> {
+    let (_, transport, base_path) = resolve_deploy_target(project_id)?;
+    let components = load_project_components(component_ids)?;
+    Ok(fetch_remote_versions(&components, &base_path, &transport))
+}
+
+/// Refuse to ship components whose `dependsOn` ranges are not met. A
+/// dependency shipping in the same run counts at its local version; any other
+/// dependency at its deployed version.
+fn check_dependency_constraints(
+    shipping: &[Component],
+    all_components: &[Component],
+    base_path: &str,
+    transport: &Transport,
+) -> Result<()> {
+    let shipping_versions: HashMap<String, String> = shipping
+        .iter()
+        .filter_map(|c| version::get_component_version(c).map(|v| (c.id.clone(), v)))
+        .collect();
+    check_shipping_constraints(
+        shipping,
+        &shipping_versions,
+        all_components,
+        base_path,
+        transport,
+    )
+}
+
+/// Like [`check_dependency_constraints`], with the shipping components at the
+/// given versions (e.g. the ones frozen in a deploy plan).
+fn check_shipping_constraints(
+    shipping: &[Component],
+    shipping_versions: &HashMap<String, String>,
+    all_components: &[Component],
+    base_path: &str,
+    transport: &Transport,
+) -> Result<()> {
+    let required: Vec<&str> = shipping
+        .iter()
+        .flat_map(|c| c.depends_on.iter().flatten())
+        .map(|dep| dep.component.as_str())
+        .collect();
+    if required.is_empty() {
+        return Ok(());
+    }
+
+    let dependencies: Vec<Component> = all_components
+        .iter()
+        .filter(|c| required.contains(&c.id.as_str()))
+        .cloned()
+        .collect();
+    let mut versions = fetch_remote_versions(&dependencies, base_path, transport);
+    versions.extend(
+        shipping_versions
+            .iter()
+            .map(|(id, version)| (id.clone(), version.clone())),
+    );
+
+    let members: Vec<String> = all_components.iter().map(|c| c.id.clone()).collect();
+    let checks: Vec<_> = shipping
+        .iter()
+        .flat_map(|c| version_constraints::check_component(c, &members, &versions))
+        .collect();
+    version_constraints::ensure_satisfied(&checks, "deploy")
+}
+
+/// Directory the deployed files are served from.
+fn deployed_dir(component: &Component, base_path: &str) -> Result<String> {
+    // Atomic deploys serve files from the `current` release
+    if component.atomic_deploy.is_some() {
+        base_path::join_remote_child(Some(base_path), &component.remote_path, CURRENT_LINK)
+    } else {
+        base_path::join_remote_path(Some(base_path), &component.remote_path)
+    }
+}
+
+/// Upload `exclude` patterns; excluded paths are left out of drift reports.
+fn upload_excludes(component: &Component) -> Vec<String> {
+    component
+        .upload
+        .as_ref()
+        .map(|upload| upload.exclude.clone())
+        .unwrap_or_default()
+}
+
+/// Compare local build output with deployed files by content hash.
+///
+/// Components without a local artifact (or with an unsupported artifact type) are
+/// left out of the result. All remote directories are hashed in one command.
+fn detect_drift(
+    components: &[Component],
+    base_path: &str,
+    transport: &Transport,
+) -> Result<HashMap<String, DriftReport>> {
+    let mut local_manifests = HashMap::new();
+    let mut remote_dirs = Vec::new();
+
+    for component in components {
+        let Ok(dir) = deployed_dir(component, base_path) else {
+            continue;
+        };
+        let artifact = Path::new(&component.build_artifact);
+        if !artifact.exists() {
+            deploy_log!(
+                "Skipping drift check for {}: artifact not built ({})",
+                component.id,
+                component.build_artifact
+            );
+            continue;
+        }
 
-            if let Some(ver) = parse_component_version(&output.stdout, pattern, version_file) {
-                versions.insert(component.id.clone(), ver);
+        match drift::local_manifest(artifact, &dir) {
+            Ok(manifest) => {
+                local_manifests
+                    .insert(component.id.clone(), (manifest, upload_excludes(component)));
+                remote_dirs.push((component.id.clone(), dir));
             }
+            Err(err) => deploy_log!("Skipping drift check for {}: {}", component.id, err),
         }
     }
 
-    versions
+    let mut remote_manifests = drift::remote_manifests(transport, &remote_dirs)?;
+
+    Ok(local_manifests
+        .into_iter()
+        .map(|(id, (local, exclude))| {
+            let remote = remote_manifests.remove(&id).flatten().unwrap_or_default();
+            let report = drift::compare(
+                &drift::without_excluded(local, &exclude),
+                &drift::without_excluded(remote, &exclude),
+            );
+            (id, report)
+        })
+        .collect())
 }
 
-/// Parse version from content using pattern or module defaults.
-fn parse_component_version(content: &str, pattern: Option<&str>, filename: &str) -> Option<String> {
-    let pattern_str = match pattern {
-        Some(p) => p.replace("\\\\", "\\"),
-        None => version::default_pattern_for_file(filename)?,
-    };
+/// Read a component's version from a remote directory using its first version target.
+fn read_remote_version(transport: &Transport, component: &Component, dir: &str) -> Option<String> {
+    let target = component
+        .version_targets
+        .as_ref()
+        .and_then(|targets| targets.first())?;
+
+    let remote_path = format!("{}/{}", dir.trim_end_matches('/'), target.file);
+    let output = transport.execute(&format!("cat '{}' 2>/dev/null", remote_path));
+    if !output.success {
+        return None;
+    }
 
-    version::parse_version(content, &pattern_str)
+    // Tolerate double-escaped patterns from hand-edited configs
+    let target = VersionTarget {
+        pattern: target.pattern.as_ref().map(|p| p.replace("\\\\", "\\")),
+        ..target.clone()
+    };
+    version::parse_target_version(&output.stdout, &target)
 }
 
 /// Find deploy verification config from modules.
@@ -906,7 +2994,7 @@ fn find_deploy_override(target_path: &str) -> Option<(DeployOverride, ModuleMani
 
 /// Deploy using module-defined override strategy.
 fn deploy_with_override(
-    ssh_client: &SshClient,
+    transport: &Transport,
     local_path: &Path,
     remote_path: &str,
     override_config: &DeployOverride,
@@ -915,31 +3003,28 @@ fn deploy_with_override(
     site_root: Option<&str>,
     domain: Option<&str>,
 ) -> Result<DeployResult> {
-    let artifact_filename = local_path
-        .file_name()
-        .and_then(|name| name.to_str())
-        .ok_or_else(|| {
-            Error::validation_invalid_argument(
-                "buildArtifact",
-                "Build artifact path must include a file name",
-                Some(local_path.display().to_string()),
-                None,
-            )
-        })?;
-
-    let staging_artifact = format!("{}/{}", override_config.staging_path, artifact_filename);
+    let artifact_filename = artifact_file_name(local_path)?;
+    let vars = override_vars(
+        override_config,
+        module,
+        artifact_filename,
+        remote_path,
+        site_root,
+        domain,
+    );
+    let staging_artifact = &vars["stagingArtifact"];
 
     // Step 1: Create staging directory
     let mkdir_cmd = format!(
         "mkdir -p {}",
         shell::quote_path(&override_config.staging_path)
     );
-    eprintln!("[deploy] Using module deploy override: {}", module.id);
-    eprintln!(
-        "[deploy] Creating staging directory: {}",
+    deploy_log!("Using module deploy override: {}", module.id);
+    deploy_log!(
+        "Creating staging directory: {}",
         override_config.staging_path
     );
-    let mkdir_output = ssh_client.execute(&mkdir_cmd);
+    let mkdir_output = transport.execute(&mkdir_cmd);
     if !mkdir_output.success {
         return Ok(DeployResult::failure(
             mkdir_output.exit_code,
@@ -951,30 +3036,16 @@ fn deploy_with_override(
     }
 
     // Step 2: Upload artifact to staging
-    let upload_result = scp_file(ssh_client, local_path, &staging_artifact)?;
+    let upload_result = upload_file(transport, local_path, staging_artifact)?;
     if !upload_result.success {
         return Ok(upload_result);
     }
 
     // Step 3: Render and execute install command
-    let cli_path = module
-        .cli
-        .as_ref()
-        .and_then(|c| c.default_cli_path.as_deref())
-        .unwrap_or("wp");
-
-    let mut vars = HashMap::new();
-    vars.insert("artifact".to_string(), artifact_filename.to_string());
-    vars.insert("stagingArtifact".to_string(), staging_artifact.clone());
-    vars.insert("targetDir".to_string(), remote_path.to_string());
-    vars.insert("siteRoot".to_string(), site_root.unwrap_or("").to_string());
-    vars.insert("cliPath".to_string(), cli_path.to_string());
-    vars.insert("domain".to_string(), domain.unwrap_or("").to_string());
-
     let install_cmd = render_map(&override_config.install_command, &vars);
-    eprintln!("[deploy] Running install command: {}", install_cmd);
+    deploy_log!("Running install command: {}", install_cmd);
 
-    let install_output = ssh_client.execute(&install_cmd);
+    let install_output = transport.execute(&install_cmd);
     if !install_output.success {
         let error_detail = if install_output.stderr.is_empty() {
             install_output.stdout.clone()
@@ -993,37 +3064,479 @@ fn deploy_with_override(
     // Step 4: Run cleanup command if configured
     if let Some(cleanup_cmd_template) = &override_config.cleanup_command {
         let cleanup_cmd = render_map(cleanup_cmd_template, &vars);
-        eprintln!("[deploy] Running cleanup: {}", cleanup_cmd);
-        let _ = ssh_client.execute(&cleanup_cmd); // Best effort cleanup
+        deploy_log!("Running cleanup: {}", cleanup_cmd);
+        let _ = transport.execute(&cleanup_cmd); // Best effort cleanup
     }
 
     // Step 5: Fix permissions unless skipped
     if !override_config.skip_permissions_fix {
-        eprintln!("[deploy] Fixing file permissions");
-        permissions::fix_deployed_permissions(ssh_client, remote_path)?;
+        deploy_log!("Fixing file permissions");
+        permissions::fix_deployed_permissions(transport, remote_path)?;
     }
 
     // Step 6: Run verification if configured
-    if let Some(v) = verification {
-        if let Some(ref verify_cmd_template) = v.verify_command {
-            let mut verify_vars = HashMap::new();
-            verify_vars.insert(
-                TemplateVars::TARGET_DIR.to_string(),
-                remote_path.to_string(),
-            );
-            let verify_cmd = render_map(verify_cmd_template, &verify_vars);
-
-            let verify_output = ssh_client.execute(&verify_cmd);
-            if !verify_output.success || verify_output.stdout.trim().is_empty() {
-                let error_msg = v
-                    .verify_error_message
-                    .as_ref()
-                    .map(|msg| render_map(msg, &verify_vars))
-                    .unwrap_or_else(|| format!("Deploy verification failed for {}", remote_path));
-                return Ok(DeployResult::failure(1, error_msg));
-            }
-        }
+    if let Some(failure) = run_verification(transport, verification, remote_path) {
+        return Ok(failure);
     }
 
     Ok(DeployResult::success(0))
 }
+
+/// Template variables for a module deploy override's install and cleanup commands.
+fn override_vars(
+    override_config: &DeployOverride,
+    module: &ModuleManifest,
+    artifact_filename: &str,
+    remote_path: &str,
+    site_root: Option<&str>,
+    domain: Option<&str>,
+) -> HashMap<String, String> {
+    let staging_artifact = format!("{}/{}", override_config.staging_path, artifact_filename);
+    let cli_path = module
+        .cli
+        .as_ref()
+        .and_then(|c| c.default_cli_path.as_deref())
+        .unwrap_or("wp");
+
+    let mut vars = HashMap::new();
+    vars.insert("artifact".to_string(), artifact_filename.to_string());
+    vars.insert("stagingArtifact".to_string(), staging_artifact);
+    vars.insert("targetDir".to_string(), remote_path.to_string());
+    vars.insert("siteRoot".to_string(), site_root.unwrap_or("").to_string());
+    vars.insert("cliPath".to_string(), cli_path.to_string());
+    vars.insert("domain".to_string(), domain.unwrap_or("").to_string());
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rsync_stats_reads_modern_output() {
+        let output = "\
+Number of files: 1,204 (reg: 1,100, dir: 104)
+Number of created files: 2 (reg: 2)
+Number of deleted files: 1 (reg: 1)
+Number of regular files transferred: 3
+Total file size: 12,345,678 bytes
+";
+        assert_eq!(
+            parse_rsync_stats(output),
+            TransferStats {
+                files_total: Some(1204),
+                files_transferred: 3,
+                files_deleted: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_rsync_stats_reads_legacy_output() {
+        let output = "Number of files: 42\nNumber of files transferred: 5\n";
+        assert_eq!(
+            parse_rsync_stats(output),
+            TransferStats {
+                files_total: Some(42),
+                files_transferred: 5,
+                files_deleted: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn deploy_artifact_copies_directory_to_local_target() {
+        let home = tempfile::tempdir().unwrap();
+        let artifact = tempfile::tempdir().unwrap();
+        std::fs::write(artifact.path().join("plugin.php"), "<?php").unwrap();
+        let transport = Transport::Local {
+            home: home.path().to_path_buf(),
+        };
+
+        let result = deploy_artifact(
+            &transport,
+            artifact.path(),
+            "site/wp-content/plugins/my-plugin",
+            None,
+            None,
+            &UploadConfig::default(),
+        )
+        .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert!(home
+            .path()
+            .join("site/wp-content/plugins/my-plugin/plugin.php")
+            .is_file());
+    }
+
+    fn deploy_atomic(transport: &Transport, artifact: &Path) -> DeployResult {
+        deploy_artifact_atomic(
+            transport,
+            artifact,
+            "site/app",
+            None,
+            None,
+            &UploadConfig::default(),
+            &AtomicDeployConfig { keep_releases: 5 },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn atomic_deploys_get_distinct_releases() {
+        let home = tempfile::tempdir().unwrap();
+        let artifact = tempfile::tempdir().unwrap();
+        std::fs::write(artifact.path().join("index.php"), "<?php").unwrap();
+        let transport = Transport::Local {
+            home: home.path().to_path_buf(),
+        };
+
+        let first = deploy_atomic(&transport, artifact.path());
+        let second = deploy_atomic(&transport, artifact.path());
+
+        assert!(first.success && second.success, "{:?}", second.error);
+        assert_ne!(first.release_dir, second.release_dir);
+        let releases = std::fs::read_dir(home.path().join("site/app/releases"))
+            .unwrap()
+            .count();
+        assert_eq!(releases, 2);
+        let current = std::fs::read_link(home.path().join("site/app/current")).unwrap();
+        assert!(second
+            .release_dir
+            .unwrap()
+            .ends_with(current.to_str().unwrap()));
+        assert!(home.path().join("site/app/current/index.php").is_file());
+    }
+
+    fn versioned_component() -> Component {
+        let mut component = Component::new(
+            "app".to_string(),
+            String::new(),
+            "app".to_string(),
+            String::new(),
+        );
+        component.version_targets = Some(vec![VersionTarget {
+            file: "VERSION".to_string(),
+            pattern: Some(r"(\d+\.\d+\.\d+)".to_string()),
+            ..Default::default()
+        }]);
+        component
+    }
+
+    fn write_version(dir: &Path, version: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("VERSION"), version).unwrap();
+    }
+
+    #[test]
+    fn atomic_rollback_verifies_before_switching() {
+        let home = tempfile::tempdir().unwrap();
+        let app = home.path().join("site/app");
+        write_version(&app.join("releases/r1"), "1.0.0");
+        write_version(&app.join("releases/r2"), "2.0.0");
+        std::os::unix::fs::symlink("releases/r2", app.join("current")).unwrap();
+        let transport = Transport::Local {
+            home: home.path().to_path_buf(),
+        };
+        let target = RollbackTarget {
+            name: "r1".to_string(),
+            dir: "site/app/releases/r1".to_string(),
+            version: Some("1.0.0".to_string()),
+        };
+        let failing = DeployVerification {
+            path_pattern: String::new(),
+            verify_command: Some("test -f {{targetDir}}/ok && echo ok".to_string()),
+            verify_error_message: None,
+        };
+
+        let result = restore_rollback_target(&transport, "site/app", &target, true, Some(&failing));
+        assert!(!result.success);
+        assert_eq!(
+            std::fs::read_link(app.join("current")).unwrap(),
+            Path::new("releases/r2")
+        );
+
+        std::fs::write(app.join("releases/r1/ok"), "").unwrap();
+        let result = restore_rollback_target(&transport, "site/app", &target, true, Some(&failing));
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            std::fs::read_link(app.join("current")).unwrap(),
+            Path::new("releases/r1")
+        );
+    }
+
+    #[test]
+    fn backup_rollback_skips_the_live_version() {
+        let home = tempfile::tempdir().unwrap();
+        let backups = home.path().join("backups");
+        write_version(&backups.join("20260101000000"), "1.0.0");
+        write_version(&backups.join("20260102000000"), "1.1.0");
+        let transport = Transport::Local {
+            home: home.path().to_path_buf(),
+        };
+        let component = versioned_component();
+        let candidates = list_remote_entries(&transport, "backups");
+
+        // First rollback from 1.2.0 restores the newest backup; once 1.1.0 is
+        // live, the next rollback goes to the older one
+        let first = select_rollback_target(
+            &transport,
+            &component,
+            &candidates,
+            None,
+            Some("1.2.0"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(first.name, "20260102000000");
+        let second = select_rollback_target(
+            &transport,
+            &component,
+            &candidates,
+            None,
+            Some("1.1.0"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(second.name, "20260101000000");
+        assert!(select_rollback_target(
+            &transport,
+            &component,
+            &candidates[1..],
+            None,
+            Some("1.0.0"),
+            None
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn atomic_deploy_removes_release_when_switch_fails() {
+        let home = tempfile::tempdir().unwrap();
+        let artifact = tempfile::tempdir().unwrap();
+        std::fs::write(artifact.path().join("index.php"), "<?php").unwrap();
+        // A non-empty directory named `current` cannot be replaced by the link
+        std::fs::create_dir_all(home.path().join("site/app/current/keep")).unwrap();
+        let transport = Transport::Local {
+            home: home.path().to_path_buf(),
+        };
+
+        let result = deploy_atomic(&transport, artifact.path());
+
+        assert!(!result.success);
+        let releases = std::fs::read_dir(home.path().join("site/app/releases"))
+            .unwrap()
+            .count();
+        assert_eq!(releases, 0);
+        assert!(home.path().join("site/app/current/keep").is_dir());
+    }
+
+    #[test]
+    fn parallel_batch_keeps_order_and_prefixes_logs_per_component() {
+        let batch: Vec<Component> = ["a", "b", "c"]
+            .iter()
+            .map(|id| Component::new(id.to_string(), String::new(), id.to_string(), String::new()))
+            .collect();
+
+        // Later components finish first; results must still follow batch order
+        let results = execute_batch(&batch, "site", &|component: &Component| {
+            let delay = match component.id.as_str() {
+                "a" => 60,
+                "b" => 30,
+                _ => 0,
+            };
+            std::thread::sleep(std::time::Duration::from_millis(delay));
+            ComponentDeployResult::new(component, "site").with_status(&format!(
+                "{} {}",
+                log_prefix("deploy"),
+                log_prefix("build")
+            ))
+        });
+
+        let statuses: Vec<(&str, &str)> = results
+            .iter()
+            .map(|r| (r.id.as_str(), r.status.as_str()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("a", "[deploy:a] [build:a]"),
+                ("b", "[deploy:b] [build:b]"),
+                ("c", "[deploy:c] [build:c]"),
+            ]
+        );
+        assert_eq!(log_prefix("deploy"), "[deploy]");
+    }
+
+    #[test]
+    fn parallel_batch_reports_panicked_component() {
+        let batch: Vec<Component> = ["a", "b"]
+            .iter()
+            .map(|id| Component::new(id.to_string(), String::new(), id.to_string(), String::new()))
+            .collect();
+
+        let results = execute_batch(&batch, "site", &|component: &Component| {
+            if component.id == "a" {
+                panic!("boom");
+            }
+            ComponentDeployResult::new(component, "site").with_status("deployed")
+        });
+
+        let outcomes: Vec<(&str, &str, Option<&str>)> = results
+            .iter()
+            .map(|r| (r.id.as_str(), r.status.as_str(), r.error.as_deref()))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("a", "failed", Some("Deploy panicked: boom")),
+                ("b", "deployed", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn version_status_compares_by_precedence() {
+        let status = |local, remote| version_status(Some(local), Some(remote));
+        assert_eq!(status("1.2.0", "v1.2.0"), ComponentStatus::UpToDate);
+        assert_eq!(status("1.2.0+build.7", "1.2.0"), ComponentStatus::UpToDate);
+        assert_eq!(status("1.10.0", "1.9.0"), ComponentStatus::NeedsUpdate);
+        assert_eq!(status("1.9.0", "1.10.0"), ComponentStatus::BehindRemote);
+        assert_eq!(status("abc", "abc"), ComponentStatus::UpToDate);
+        assert_eq!(status("abc", "abd"), ComponentStatus::NeedsUpdate);
+        assert_eq!(version_status(None, None), ComponentStatus::Unknown);
+    }
+
+    #[test]
+    fn fan_out_outdated_only_deploys_newer_local_versions() {
+        assert_eq!(
+            fan_out_skip_status(true, Some("v2.0.0"), Some("2.0.0")),
+            Some(ComponentStatus::UpToDate)
+        );
+        assert_eq!(
+            fan_out_skip_status(true, Some("2.0.0"), Some("2.1.0")),
+            Some(ComponentStatus::BehindRemote)
+        );
+        assert_eq!(
+            fan_out_skip_status(true, Some("2.1.0"), Some("2.0.0")),
+            None
+        );
+        assert_eq!(fan_out_skip_status(true, Some("2.1.0"), None), None);
+        assert_eq!(fan_out_skip_status(true, None, Some("2.0.0")), None);
+        assert_eq!(
+            fan_out_skip_status(false, Some("2.0.0"), Some("2.1.0")),
+            None
+        );
+    }
+
+    #[test]
+    fn rollback_on_failure_needs_a_rollback_target() {
+        let health = health::HealthCheckConfig {
+            checks: vec![serde_json::from_str("{}").unwrap()],
+            rollback_on_failure: true,
+        };
+        let project = Project {
+            health_check: Some(health),
+            ..Default::default()
+        };
+        let plain = Component::new(
+            "plain".to_string(),
+            String::new(),
+            "plain".to_string(),
+            String::new(),
+        );
+        let mut atomic = plain.clone();
+        atomic.id = "atomic".to_string();
+        atomic.atomic_deploy = Some(AtomicDeployConfig { keep_releases: 5 });
+
+        let problem = rollback_target_problem(&project, &[plain.clone(), atomic.clone()], 0);
+        assert!(problem.unwrap().contains("no rollback target for plain:"));
+        assert!(rollback_target_problem(&project, &[atomic], 0).is_none());
+        assert!(rollback_target_problem(&project, std::slice::from_ref(&plain), 1).is_none());
+        assert!(check_rollback_targets(&Project::default(), &[plain], 0).is_ok());
+    }
+
+    #[test]
+    fn dependency_constraints_use_shipping_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let transport = Transport::Local {
+            home: dir.path().to_path_buf(),
+        };
+        let base_path = dir.path().to_string_lossy().to_string();
+        let plugin = Component::new(
+            "plugin".to_string(),
+            String::new(),
+            "plugin".to_string(),
+            String::new(),
+        );
+        let mut theme = plugin.clone();
+        theme.id = "theme".to_string();
+        theme.remote_path = "theme".to_string();
+        theme.depends_on = Some(vec![version_constraints::ComponentDependency {
+            component: "plugin".to_string(),
+            range: ">=2.0".to_string(),
+        }]);
+        let all = vec![plugin.clone(), theme.clone()];
+        let versions = |plugin_version: &str| -> HashMap<String, String> {
+            [("plugin", plugin_version), ("theme", "1.0.0")]
+                .iter()
+                .map(|(id, v)| (id.to_string(), v.to_string()))
+                .collect()
+        };
+        let shipping = [theme.clone(), plugin];
+
+        assert!(check_shipping_constraints(
+            &shipping,
+            &versions("2.1.0"),
+            &all,
+            &base_path,
+            &transport
+        )
+        .is_ok());
+        let err =
+            check_shipping_constraints(&shipping, &versions("1.9.0"), &all, &base_path, &transport)
+                .unwrap_err();
+        assert_eq!(
+            error_text(&err),
+            "Refusing to deploy: 1 dependency constraint(s) not satisfied: \
+             theme requires plugin >=2.0: plugin 1.9.0 does not satisfy >=2.0"
+        );
+        // Not shipping and nothing deployed: the plugin version is unknown
+        let mut theme_only = versions("2.1.0");
+        theme_only.remove("plugin");
+        let err = check_shipping_constraints(
+            std::slice::from_ref(&theme),
+            &theme_only,
+            &all,
+            &base_path,
+            &transport,
+        )
+        .unwrap_err();
+        assert!(error_text(&err).ends_with("version of plugin is unknown"));
+    }
+
+    #[test]
+    fn trim_entries_keeps_newest_entries() {
+        let home = tempfile::tempdir().unwrap();
+        for name in [
+            "20260101000000 copy",
+            "20260102000000123-abc123",
+            "20260103000000",
+        ] {
+            std::fs::create_dir_all(home.path().join("releases").join(name)).unwrap();
+        }
+        let transport = Transport::Local {
+            home: home.path().to_path_buf(),
+        };
+
+        let output = transport.execute(&trim_entries_command("releases", 1));
+
+        assert!(output.success, "{}", output.stderr);
+        let left: Vec<String> = std::fs::read_dir(home.path().join("releases"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(left, vec!["20260103000000"]);
+    }
+}
@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+use crate::component;
+use crate::deploy::{DeployOrchestrationResult, DeploySummary};
+use crate::error::{Error, Result};
+use crate::git;
+use crate::paths;
+
+/// One deploy (or rollback) invocation recorded in the project's ledger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployHistoryRecord {
+    pub timestamp: String,
+    pub action: String,
+    pub project_id: String,
+    pub operator: String,
+    pub components: Vec<DeployHistoryComponent>,
+    pub summary: DeploySummary,
+}
+
+/// Per-component details of a recorded deploy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployHistoryComponent {
+    pub id: String,
+    pub status: String,
+    pub local_version: Option<String>,
+    pub remote_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_sha: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact_sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_dir: Option<String>,
+    pub build_exit_code: Option<i32>,
+    pub deploy_exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Append a deploy result to the project's history ledger.
+pub fn record(
+    project_id: &str,
+    action: &str,
+    result: &DeployOrchestrationResult,
+) -> Result<DeployHistoryRecord> {
+    let record = build_record(project_id, action, result);
+    append(&paths::deploy_history_file(project_id)?, &record)?;
+    Ok(record)
+}
+
+fn build_record(
+    project_id: &str,
+    action: &str,
+    result: &DeployOrchestrationResult,
+) -> DeployHistoryRecord {
+    let components = result
+        .results
+        .iter()
+        .map(|r| {
+            let git_sha = component::load(&r.id)
+                .ok()
+                .and_then(|c| git::head_sha(Path::new(&c.local_path)));
+            // Only an artifact that was actually deployed has a meaningful checksum
+            let artifact_sha256 = r.artifact_sha256.clone().filter(|_| r.status == "deployed");
+
+            DeployHistoryComponent {
+                id: r.id.clone(),
+                status: r.status.clone(),
+                local_version: r.local_version.clone(),
+                remote_version: r.remote_version.clone(),
+                git_sha,
+                artifact_sha256,
+                remote_path: r.remote_path.clone(),
+                release_dir: r.release_dir.clone(),
+                build_exit_code: r.build_exit_code,
+                deploy_exit_code: r.deploy_exit_code,
+                error: r.error.clone(),
+            }
+        })
+        .collect();
+
+    DeployHistoryRecord {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        action: action.to_string(),
+        project_id: project_id.to_string(),
+        operator: operator(),
+        components,
+        summary: result.summary.clone(),
+    }
+}
+
+/// List recorded deploys for a project, newest first.
+///
+/// When `component_id` is set, only records touching that component are returned
+/// and their component lists are narrowed to it.
+pub fn list(
+    project_id: &str,
+    component_id: Option<&str>,
+    limit: Option<usize>,
+) -> Result<Vec<DeployHistoryRecord>> {
+    read(
+        &paths::deploy_history_file(project_id)?,
+        component_id,
+        limit,
+    )
+}
+
+fn read(
+    path: &Path,
+    component_id: Option<&str>,
+    limit: Option<usize>,
+) -> Result<Vec<DeployHistoryRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("read {}", path.display()))))?;
+
+    let mut records: Vec<DeployHistoryRecord> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    records.reverse();
+
+    if let Some(id) = component_id {
+        records = records
+            .into_iter()
+            .filter_map(|mut record| {
+                record.components.retain(|c| c.id == id);
+                (!record.components.is_empty()).then_some(record)
+            })
+            .collect();
+    }
+
+    if let Some(limit) = limit {
+        records.truncate(limit);
+    }
+
+    Ok(records)
+}
+
+fn append(path: &Path, record: &DeployHistoryRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            Error::internal_io(e.to_string(), Some(format!("create {}", parent.display())))
+        })?;
+    }
+
+    let line = serde_json::to_string(record)
+        .map_err(|e| Error::internal_json(e.to_string(), Some("serialize deploy record".into())))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("open {}", path.display()))))?;
+
+    writeln!(file, "{}", line)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("write {}", path.display()))))
+}
+
+/// Operator identity recorded with each deploy (`user@host`).
+pub(crate) fn operator() -> String {
+    format!("{}@{}", current_user(), current_host())
+}
+
+/// Local user name running homeboy.
+pub(crate) fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Local host name running homeboy.
+pub(crate) fn current_host() -> String {
+    Command::new("hostname")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|h| !h.is_empty())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+    use crate::deploy::ComponentDeployResult;
+
+    fn deploy_result(component_ids: &[&str]) -> DeployOrchestrationResult {
+        let results: Vec<ComponentDeployResult> = component_ids
+            .iter()
+            .map(|id| {
+                let component =
+                    Component::new(id.to_string(), String::new(), id.to_string(), String::new());
+                ComponentDeployResult::new(&component, "/srv")
+                    .with_status("deployed")
+                    .with_versions(Some("1.1.0".to_string()), Some("1.0.0".to_string()))
+            })
+            .collect();
+        let total = results.len() as u32;
+        DeployOrchestrationResult {
+            results,
+            summary: DeploySummary {
+                total,
+                succeeded: total,
+                failed: 0,
+                skipped: 0,
+            },
+            hooks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn records_round_trip_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = dir.path().join("history/site.jsonl");
+
+        assert!(read(&ledger, None, None).unwrap().is_empty());
+
+        append(
+            &ledger,
+            &build_record("site", "deploy", &deploy_result(&["plugin"])),
+        )
+        .unwrap();
+        append(
+            &ledger,
+            &build_record("site", "rollback", &deploy_result(&["theme"])),
+        )
+        .unwrap();
+
+        let records = read(&ledger, None, None).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].action, "rollback");
+        assert_eq!(records[1].action, "deploy");
+        assert_eq!(records[1].project_id, "site");
+        assert_eq!(records[1].summary.succeeded, 1);
+
+        let component = &records[1].components[0];
+        assert_eq!(component.id, "plugin");
+        assert_eq!(component.status, "deployed");
+        assert_eq!(component.local_version.as_deref(), Some("1.1.0"));
+        assert_eq!(component.remote_version.as_deref(), Some("1.0.0"));
+    }
+
+    #[test]
+    fn list_filters_by_component_and_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = dir.path().join("site.jsonl");
+        for ids in [&["plugin", "theme"][..], &["theme"], &["plugin"]] {
+            append(
+                &ledger,
+                &build_record("site", "deploy", &deploy_result(ids)),
+            )
+            .unwrap();
+        }
+        // Unparseable lines are skipped
+        let mut file = OpenOptions::new().append(true).open(&ledger).unwrap();
+        writeln!(file, "not json").unwrap();
+
+        assert_eq!(read(&ledger, None, None).unwrap().len(), 3);
+
+        let plugin = read(&ledger, Some("plugin"), None).unwrap();
+        assert_eq!(plugin.len(), 2);
+        assert!(plugin
+            .iter()
+            .all(|r| r.components.len() == 1 && r.components[0].id == "plugin"));
+
+        let limited = read(&ledger, Some("theme"), Some(1)).unwrap();
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].components[0].id, "theme");
+        assert_eq!(read(&ledger, None, Some(0)).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn records_artifact_checksum_only_for_deployed_components() {
+        let mut result = deploy_result(&["plugin", "theme"]);
+        for r in &mut result.results {
+            r.artifact_sha256 = Some("abc".to_string());
+        }
+        result.results[1].status = "failed".to_string();
+
+        let record = build_record("site", "deploy", &result);
+
+        assert_eq!(record.components[0].artifact_sha256.as_deref(), Some("abc"));
+        assert_eq!(record.components[1].artifact_sha256, None);
+    }
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::deploy::deploy_log;
+use crate::shell;
+use crate::template::render_map;
+use crate::transport::Transport;
+
+/// When a deploy hook runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookStage {
+    PreDeploy,
+    PostDeploy,
+    OnDeployFailure,
+}
+
+impl HookStage {
+    fn label(self) -> &'static str {
+        match self {
+            HookStage::PreDeploy => "pre-deploy",
+            HookStage::PostDeploy => "post-deploy",
+            HookStage::OnDeployFailure => "on-failure",
+        }
+    }
+}
+
+/// Captured output of one hook command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub struct HookResult {
+    pub stage: HookStage,
+    pub command: String,
+    pub success: bool,
+    pub exit_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Run hook commands in order over the deploy's transport, from the first of
+/// `working_dirs` that exists (a component's directory is missing before its
+/// first deploy).
+///
+/// Pre- and post-deploy hooks stop at the first failure; failure hooks always all run.
+pub fn run(
+    transport: &Transport,
+    stage: HookStage,
+    commands: &[String],
+    working_dirs: &[&str],
+    vars: &HashMap<String, String>,
+) -> Vec<HookResult> {
+    let mut results = Vec::with_capacity(commands.len());
+    let cd = change_dir_command(working_dirs);
+
+    for template in commands {
+        let command = render_map(template, vars);
+        deploy_log!("Running {} hook: {}", stage.label(), command);
+
+        let output = transport.execute(&format!("{} && {}", cd, command));
+        let result = HookResult {
+            stage,
+            command,
+            success: output.success,
+            exit_code: output.exit_code,
+            stdout: output.stdout,
+            stderr: output.stderr,
+        };
+
+        let failed = !result.success;
+        results.push(result);
+        if failed && stage != HookStage::OnDeployFailure {
+            break;
+        }
+    }
+
+    results
+}
+
+/// `cd` into the first existing directory; only the last attempt reports errors.
+fn change_dir_command(working_dirs: &[&str]) -> String {
+    let attempts: Vec<String> = working_dirs
+        .iter()
+        .enumerate()
+        .map(|(index, dir)| {
+            let quiet = if index + 1 < working_dirs.len() {
+                " 2>/dev/null"
+            } else {
+                ""
+            };
+            format!("cd {}{}", shell::quote_path(dir), quiet)
+        })
+        .collect();
+    format!("{{ {}; }}", attempts.join(" || "))
+}
+
+/// First failed hook, formatted for a deploy error message.
+pub fn failure_message(results: &[HookResult]) -> Option<String> {
+    results.iter().find(|r| !r.success).map(|r| {
+        let detail = if r.stderr.trim().is_empty() {
+            r.stdout.trim()
+        } else {
+            r.stderr.trim()
+        };
+        format!(
+            "{} hook failed (exit {}): {}{}",
+            r.stage.label(),
+            r.exit_code,
+            r.command,
+            if detail.is_empty() {
+                String::new()
+            } else {
+                format!("\n{}", detail)
+            }
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(dir: &tempfile::TempDir) -> Transport {
+        Transport::Local {
+            home: dir.path().to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn run_renders_vars_and_stops_at_first_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let vars = HashMap::from([("projectId".to_string(), "site".to_string())]);
+        let commands = vec![
+            "echo flush {{projectId}}".to_string(),
+            "exit 3".to_string(),
+            "echo unreachable".to_string(),
+        ];
+
+        let results = run(&local(&dir), HookStage::PreDeploy, &commands, &["."], &vars);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].stdout.trim(), "flush site");
+        assert_eq!(results[1].exit_code, 3);
+        assert!(failure_message(&results)
+            .unwrap()
+            .starts_with("pre-deploy hook failed (exit 3)"));
+    }
+
+    #[test]
+    fn failure_hooks_all_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let commands = vec!["exit 1".to_string(), "echo still runs".to_string()];
+
+        let results = run(
+            &local(&dir),
+            HookStage::OnDeployFailure,
+            &commands,
+            &["."],
+            &HashMap::new(),
+        );
+
+        assert_eq!(results.len(), 2);
+        assert!(results[1].success);
+    }
+
+    #[test]
+    fn run_falls_back_when_component_dir_is_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("site/plugins")).unwrap();
+        let commands = vec!["pwd".to_string()];
+
+        let first_deploy = run(
+            &local(&dir),
+            HookStage::PreDeploy,
+            &commands,
+            &["site/plugins/new-plugin", "site"],
+            &HashMap::new(),
+        );
+        assert!(first_deploy[0].success, "{}", first_deploy[0].stderr);
+        assert!(first_deploy[0].stdout.trim().ends_with("/site"));
+        assert!(first_deploy[0].stderr.is_empty());
+
+        let missing = run(
+            &local(&dir),
+            HookStage::PreDeploy,
+            &commands,
+            &["nowhere"],
+            &HashMap::new(),
+        );
+        assert!(!missing[0].success);
+    }
+}
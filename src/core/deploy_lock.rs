@@ -0,0 +1,295 @@
+use serde::{Deserialize, Serialize};
+
+use crate::defaults;
+use crate::deploy::deploy_log;
+use crate::deploy_history::{current_host, current_user};
+use crate::error::{DeployLockedDetails, Error, Result};
+use crate::shell;
+use crate::transport::Transport;
+
+/// Contents of the advisory lock file for a remote base path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub(crate) struct DeployLockInfo {
+    pub id: String,
+    pub owner: String,
+    pub host: String,
+    pub pid: u32,
+    pub started_at: String,
+    pub started_epoch: i64,
+}
+
+/// Held deploy lock. The lock file is removed when this is dropped.
+pub(crate) struct DeployLockGuard<'a> {
+    transport: &'a Transport,
+    path: String,
+    id: String,
+}
+
+impl Drop for DeployLockGuard<'_> {
+    fn drop(&mut self) {
+        // Only remove the lock if it is still ours (it may have been force-unlocked)
+        let cmd = format!(
+            "grep -q {id} {path} 2>/dev/null && rm -f {path}",
+            id = shell::quote_arg(&self.id),
+            path = shell::quote_path(&self.path),
+        );
+        let _ = self.transport.execute(&cmd);
+    }
+}
+
+/// Acquire the project's deploy lock, failing fast with `deploy.locked` when another
+/// deploy holds it. Locks older than `defaults.deploy.lock_ttl_seconds` are treated
+/// as stale and replaced; `force` replaces any existing lock.
+pub(crate) fn acquire<'a>(
+    transport: &'a Transport,
+    project_id: &str,
+    base_path: &str,
+    force: bool,
+) -> Result<DeployLockGuard<'a>> {
+    let deploy_defaults = defaults::load_defaults().deploy;
+    let path = lock_path(base_path, deploy_defaults.lock_dir.as_deref());
+    let now = chrono::Utc::now();
+    let info = DeployLockInfo {
+        id: uuid::Uuid::new_v4().to_string(),
+        owner: current_user(),
+        host: current_host(),
+        pid: std::process::id(),
+        started_at: now.to_rfc3339(),
+        started_epoch: now.timestamp(),
+    };
+    let content = serde_json::to_string(&info)
+        .map_err(|e| Error::internal_json(e.to_string(), Some("serialize deploy lock".into())))?;
+
+    let ttl = deploy_defaults.lock_ttl_seconds;
+    let mut replaced = false;
+
+    loop {
+        // noclobber makes the redirect fail if the file already exists
+        let cmd = format!(
+            "mkdir -p {dir} && (set -C; printf '%s' {content} > {path}) 2>/dev/null && echo acquired || cat {path}",
+            dir = shell::quote_path(lock_parent(&path)),
+            content = shell::quote_arg(&content),
+            path = shell::quote_path(&path),
+        );
+        let output = transport.execute(&cmd);
+        if output.stdout.trim() == "acquired" {
+            return Ok(DeployLockGuard {
+                transport,
+                path,
+                id: info.id,
+            });
+        }
+
+        if output.stdout.trim().is_empty() {
+            return Err(Error::other(format!(
+                "Failed to create deploy lock {}: {}",
+                path,
+                output.stderr.trim()
+            ))
+            .with_hint(
+                "Set defaults.deploy.lock_dir to a writable directory outside the web root",
+            ));
+        }
+
+        let holder: Option<DeployLockInfo> = serde_json::from_str(output.stdout.trim()).ok();
+        let stale = match &holder {
+            Some(holder) => ttl > 0 && now.timestamp() - holder.started_epoch > ttl as i64,
+            // Unreadable lock contents: only replace when forced
+            None => false,
+        };
+
+        if replaced || !(force || stale) {
+            let holder = holder.unwrap_or(DeployLockInfo {
+                id: String::new(),
+                owner: "unknown".to_string(),
+                host: "unknown".to_string(),
+                pid: 0,
+                started_at: "unknown".to_string(),
+                started_epoch: 0,
+            });
+            return Err(Error::deploy_locked(DeployLockedDetails {
+                project_id: project_id.to_string(),
+                lock_path: path,
+                owner: holder.owner,
+                host: holder.host,
+                pid: holder.pid,
+                started_at: holder.started_at,
+            }));
+        }
+
+        deploy_log!(
+            "Removing {} deploy lock: {}",
+            if force { "existing" } else { "stale" },
+            path
+        );
+        // Only remove the lock that was read: another deploy may have replaced it
+        // since, and its fresh lock must survive
+        let still_held = match &holder {
+            Some(holder) => format!(
+                "grep -qF {id} {path}",
+                id = shell::quote_arg(&holder.id),
+                path = shell::quote_path(&path),
+            ),
+            None => format!(
+                "[ \"$(cat {path})\" = {content} ]",
+                path = shell::quote_path(&path),
+                content = shell::quote_arg(output.stdout.trim_end_matches('\n')),
+            ),
+        };
+        transport.execute(&format!(
+            "{} && rm -f {}",
+            still_held,
+            shell::quote_path(&path)
+        ));
+        replaced = true;
+    }
+}
+
+/// Lock file for a base path: `<lock_dir>/<name>.deploy.lock`, where `lock_dir`
+/// defaults to `.homeboy/` next to the base path so the web server never
+/// serves it.
+fn lock_path(base_path: &str, lock_dir: Option<&str>) -> String {
+    let trimmed = base_path.trim_end_matches('/');
+    let (parent, name) = match trimmed.rsplit_once('/') {
+        Some((parent, name)) => (parent, name),
+        None => (".", trimmed),
+    };
+    // `.` or `..` has no usable directory name
+    let name = if matches!(name, "" | "." | "..") {
+        "base"
+    } else {
+        name
+    };
+    let dir = match lock_dir {
+        Some(dir) => dir.trim_end_matches('/').to_string(),
+        None => format!("{}/.homeboy", parent),
+    };
+    format!("{}/{}.deploy.lock", dir, name)
+}
+
+fn lock_parent(path: &str) -> &str {
+    match path.rsplit_once('/') {
+        Some(("", _)) => "/",
+        Some((parent, _)) => parent,
+        None => ".",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(dir: &tempfile::TempDir) -> Transport {
+        Transport::Local {
+            home: dir.path().to_path_buf(),
+        }
+    }
+
+    fn lock_file(dir: &tempfile::TempDir) -> std::path::PathBuf {
+        dir.path().join(".homeboy/site.deploy.lock")
+    }
+
+    fn write_lock(dir: &tempfile::TempDir, id: &str, started_epoch: i64) {
+        let info = DeployLockInfo {
+            id: id.to_string(),
+            owner: "other".to_string(),
+            host: "elsewhere".to_string(),
+            pid: 42,
+            started_at: "earlier".to_string(),
+            started_epoch,
+        };
+        std::fs::create_dir_all(dir.path().join(".homeboy")).unwrap();
+        std::fs::write(lock_file(dir), serde_json::to_string(&info).unwrap()).unwrap();
+    }
+
+    fn lock_id(dir: &tempfile::TempDir) -> Option<String> {
+        let content = std::fs::read_to_string(lock_file(dir)).ok()?;
+        serde_json::from_str::<DeployLockInfo>(&content)
+            .ok()
+            .map(|info| info.id)
+    }
+
+    #[test]
+    fn acquire_creates_and_releases_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let transport = local(&dir);
+
+        let guard = acquire(&transport, "site", "site", false).unwrap();
+        assert_eq!(lock_id(&dir).as_deref(), Some(guard.id.as_str()));
+
+        drop(guard);
+        assert!(!lock_file(&dir).exists());
+    }
+
+    #[test]
+    fn acquire_conflicts_with_a_held_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let transport = local(&dir);
+        let _held = acquire(&transport, "site", "site", false).unwrap();
+
+        let err = acquire(&transport, "site", "site", false).err().unwrap();
+
+        assert_eq!(err.code.as_str(), "deploy.locked");
+        assert_eq!(err.retryable, Some(true));
+    }
+
+    #[test]
+    fn acquire_replaces_stale_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let transport = local(&dir);
+        write_lock(&dir, "stale-holder", 0);
+
+        let guard = acquire(&transport, "site", "site", false).unwrap();
+
+        assert_eq!(lock_id(&dir).as_deref(), Some(guard.id.as_str()));
+    }
+
+    #[test]
+    fn force_replaces_live_and_unreadable_locks() {
+        let dir = tempfile::tempdir().unwrap();
+        let transport = local(&dir);
+        write_lock(&dir, "live-holder", chrono::Utc::now().timestamp());
+
+        assert!(acquire(&transport, "site", "site", false).is_err());
+        let guard = acquire(&transport, "site", "site", true).unwrap();
+        assert_eq!(lock_id(&dir).as_deref(), Some(guard.id.as_str()));
+        drop(guard);
+
+        std::fs::write(lock_file(&dir), "not json").unwrap();
+        assert!(acquire(&transport, "site", "site", false).is_err());
+        let guard = acquire(&transport, "site", "site", true).unwrap();
+        assert_eq!(lock_id(&dir).as_deref(), Some(guard.id.as_str()));
+    }
+
+    #[test]
+    fn guard_keeps_a_lock_taken_over_by_another_deploy() {
+        let dir = tempfile::tempdir().unwrap();
+        let transport = local(&dir);
+        let guard = acquire(&transport, "site", "site", false).unwrap();
+
+        // Another deploy replaced the lock in between
+        write_lock(&dir, "newer-holder", chrono::Utc::now().timestamp());
+        drop(guard);
+
+        assert_eq!(lock_id(&dir).as_deref(), Some("newer-holder"));
+    }
+
+    #[test]
+    fn lock_lives_outside_the_base_path() {
+        assert_eq!(
+            lock_path("/var/www/html/", None),
+            "/var/www/.homeboy/html.deploy.lock"
+        );
+        assert_eq!(lock_path("/srv", None), "/.homeboy/srv.deploy.lock");
+        assert_eq!(
+            lock_path("public_html", None),
+            "./.homeboy/public_html.deploy.lock"
+        );
+        assert_eq!(
+            lock_path("/var/www/html", Some("/home/deploy/locks/")),
+            "/home/deploy/locks/html.deploy.lock"
+        );
+        assert_eq!(lock_parent("/.homeboy/srv.deploy.lock"), "/.homeboy");
+    }
+}
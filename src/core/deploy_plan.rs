@@ -0,0 +1,293 @@
+//! Frozen deploy plans.
+//!
+//! `deploy --plan-out` builds the selected components and records exactly what
+//! would be deployed. `deploy apply` replays a saved plan after checking that
+//! nothing it depends on has changed since it was written.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::deploy::{AtomicDeployConfig, DeployReason, UploadConfig};
+use crate::error::{Error, Result};
+
+/// Plan file format version. Bump when fields change meaning.
+pub const PLAN_FORMAT_VERSION: u32 = 1;
+
+/// A reviewable, replayable deploy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub struct DeployPlan {
+    pub format_version: u32,
+    pub project_id: String,
+    /// Where the plan deploys to: `user@host` or `local`
+    pub target: String,
+    pub base_path: String,
+    pub created_at: String,
+    pub created_by: String,
+    /// Project deploy hooks, rendered
+    #[serde(default)]
+    pub project_hooks: PlannedHooks,
+    pub components: Vec<PlannedComponent>,
+}
+
+/// One component in a deploy plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub struct PlannedComponent {
+    pub id: String,
+    pub deploy_reason: DeployReason,
+    pub local_version: Option<String>,
+    pub remote_version: Option<String>,
+    pub artifact_path: String,
+    pub artifact_sha256: String,
+    pub remote_path: String,
+    #[serde(flatten)]
+    pub strategy: PlannedStrategy,
+    #[serde(flatten)]
+    pub commands: PlannedCommands,
+}
+
+/// How a component is uploaded and installed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+
+pub struct PlannedStrategy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub atomic_deploy: Option<AtomicDeployConfig>,
+    /// Upload settings with the strategy resolved from defaults
+    pub upload: UploadConfig,
+}
+
+/// Commands the deploy will run for a component, fully rendered.
+///
+/// Atomic deploys use `<release>` in place of the release directory name,
+/// which is only chosen when the deploy runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+
+pub struct PlannedCommands {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extract_command: Option<String>,
+    /// Module whose deploy override installs the component
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_module: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cleanup_command: Option<String>,
+    #[serde(flatten)]
+    pub hooks: PlannedHooks,
+}
+
+/// Deploy hook commands, rendered with the deploy's template variables.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+
+pub struct PlannedHooks {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_deploy_commands: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_deploy_commands: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_deploy_failure: Vec<String>,
+}
+
+impl DeployPlan {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            Error::internal_io(e.to_string(), Some(format!("read {}", path.display())))
+        })?;
+        let plan: DeployPlan = serde_json::from_str(&content).map_err(|e| {
+            Error::validation_invalid_argument(
+                "plan",
+                format!("Invalid deploy plan: {}", e),
+                Some(path.display().to_string()),
+                None,
+            )
+        })?;
+
+        if plan.format_version != PLAN_FORMAT_VERSION {
+            return Err(Error::validation_invalid_argument(
+                "plan",
+                format!(
+                    "Unsupported plan format version {} (expected {})",
+                    plan.format_version, PLAN_FORMAT_VERSION
+                ),
+                Some(path.display().to_string()),
+                None,
+            ));
+        }
+
+        Ok(plan)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            Error::internal_json(e.to_string(), Some("serialize deploy plan".into()))
+        })?;
+        fs::write(path, content).map_err(|e| {
+            Error::internal_io(e.to_string(), Some(format!("write {}", path.display())))
+        })
+    }
+}
+
+/// Differences between a planned component and its current state, one line each.
+pub fn stale_reasons(
+    planned: &PlannedComponent,
+    artifact_sha256: Option<&str>,
+    remote_version: Option<&str>,
+    strategy: &PlannedStrategy,
+    commands: &PlannedCommands,
+) -> Vec<String> {
+    let mut reasons = Vec::new();
+    let id = &planned.id;
+
+    match artifact_sha256 {
+        None => reasons.push(format!(
+            "{}: artifact {} is missing",
+            id, planned.artifact_path
+        )),
+        Some(hash) if hash != planned.artifact_sha256 => reasons.push(format!(
+            "{}: artifact {} changed since planning",
+            id, planned.artifact_path
+        )),
+        Some(_) => {}
+    }
+
+    if remote_version != planned.remote_version.as_deref() {
+        reasons.push(format!(
+            "{}: remote version is {} (planned against {})",
+            id,
+            remote_version.unwrap_or("unknown"),
+            planned.remote_version.as_deref().unwrap_or("unknown")
+        ));
+    }
+
+    if strategy.atomic_deploy != planned.strategy.atomic_deploy {
+        reasons.push(format!("{}: atomic_deploy changed since planning", id));
+    }
+
+    if strategy.upload != planned.strategy.upload {
+        reasons.push(format!("{}: upload settings changed since planning", id));
+    }
+
+    if commands.hooks != planned.commands.hooks {
+        reasons.push(format!("{}: deploy hooks changed since planning", id));
+    }
+
+    let without_hooks = |c: &PlannedCommands| PlannedCommands {
+        hooks: PlannedHooks::default(),
+        ..c.clone()
+    };
+    if without_hooks(commands) != without_hooks(&planned.commands) {
+        reasons.push(format!("{}: deploy commands changed since planning", id));
+    }
+
+    reasons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planned() -> PlannedComponent {
+        PlannedComponent {
+            id: "plugin".to_string(),
+            deploy_reason: DeployReason::VersionMismatch,
+            local_version: Some("1.2.0".to_string()),
+            remote_version: Some("1.1.0".to_string()),
+            artifact_path: "/build/plugin.zip".to_string(),
+            artifact_sha256: "abc".to_string(),
+            remote_path: "/var/www/wp-content/plugins/plugin".to_string(),
+            strategy: PlannedStrategy::default(),
+            commands: PlannedCommands {
+                extract_command: Some("unzip -o plugin.zip".to_string()),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn unchanged_component_is_not_stale() {
+        let p = planned();
+        assert!(stale_reasons(&p, Some("abc"), Some("1.1.0"), &p.strategy, &p.commands).is_empty());
+    }
+
+    #[test]
+    fn reports_artifact_remote_and_command_changes() {
+        let p = planned();
+        let reasons = stale_reasons(
+            &p,
+            Some("def"),
+            Some("1.2.0"),
+            &p.strategy,
+            &PlannedCommands::default(),
+        );
+
+        assert_eq!(reasons.len(), 3);
+        assert!(reasons[0].contains("artifact /build/plugin.zip changed"));
+        assert!(reasons[1].contains("remote version is 1.2.0 (planned against 1.1.0)"));
+        assert!(reasons[2].contains("commands changed"));
+    }
+
+    #[test]
+    fn reports_atomic_and_upload_changes() {
+        let p = planned();
+        let strategy = PlannedStrategy {
+            atomic_deploy: Some(AtomicDeployConfig { keep_releases: 5 }),
+            upload: UploadConfig {
+                delete: true,
+                ..Default::default()
+            },
+        };
+
+        let reasons = stale_reasons(&p, Some("abc"), Some("1.1.0"), &strategy, &p.commands);
+
+        assert_eq!(
+            reasons,
+            vec![
+                "plugin: atomic_deploy changed since planning",
+                "plugin: upload settings changed since planning",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_hook_changes() {
+        let mut p = planned();
+        p.commands.hooks.post_deploy_commands = vec!["wp cache flush".to_string()];
+        let mut commands = p.commands.clone();
+        commands.hooks.post_deploy_commands = vec!["wp cache flush --all".to_string()];
+
+        let reasons = stale_reasons(&p, Some("abc"), Some("1.1.0"), &p.strategy, &commands);
+
+        assert_eq!(reasons, vec!["plugin: deploy hooks changed since planning"]);
+    }
+
+    #[test]
+    fn plan_round_trips_through_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        let plan = DeployPlan {
+            format_version: PLAN_FORMAT_VERSION,
+            project_id: "site".to_string(),
+            target: "local".to_string(),
+            base_path: "/var/www".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            created_by: "agent".to_string(),
+            project_hooks: PlannedHooks {
+                pre_deploy_commands: vec!["wp maintenance-mode activate".to_string()],
+                ..Default::default()
+            },
+            components: vec![planned()],
+        };
+
+        plan.save(&path).unwrap();
+        let loaded = DeployPlan::load(&path).unwrap();
+
+        assert_eq!(loaded.components[0].commands, plan.components[0].commands);
+        assert_eq!(loaded.project_hooks, plan.project_hooks);
+        assert_eq!(
+            loaded.components[0].deploy_reason,
+            DeployReason::VersionMismatch
+        );
+    }
+}
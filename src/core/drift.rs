@@ -0,0 +1,346 @@
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use crate::checksum;
+use crate::error::{Error, Result};
+use crate::shell;
+use crate::transport::Transport;
+
+const SECTION_MARKER: &str = "@@homeboy-drift ";
+const MISSING_MARKER: &str = "@@missing";
+
+/// File-level differences between a local build artifact and a deployed directory.
+///
+/// Paths are relative to the deployed directory. `added` files exist only in the
+/// artifact, `removed` files exist only on the server.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+
+pub struct DriftReport {
+    pub added: Vec<String>,
+    pub modified: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl DriftReport {
+    pub fn is_clean(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Relative path -> SHA-256 for every file in an artifact or deployed directory.
+pub type Manifest = BTreeMap<String, String>;
+
+/// Build the manifest of a local artifact as it would appear once deployed.
+///
+/// Directory artifacts are hashed as-is. Zip artifacts are hashed entry by entry;
+/// a single top-level folder named like the remote directory is stripped, matching
+/// archives that wrap their contents in the plugin/theme folder.
+pub fn local_manifest(artifact: &Path, remote_dir: &str) -> Result<Manifest> {
+    if artifact.is_dir() {
+        return Ok(checksum::file_hashes(artifact)?.into_iter().collect());
+    }
+
+    let is_zip = artifact
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+
+    if !is_zip {
+        return Err(Error::validation_invalid_argument(
+            "buildArtifact",
+            "Drift detection supports directory and .zip artifacts",
+            Some(artifact.display().to_string()),
+            None,
+        ));
+    }
+
+    let manifest = zip_manifest(artifact)?;
+    let folder = remote_dir
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or("");
+    Ok(strip_wrapper_folder(manifest, folder))
+}
+
+fn zip_manifest(artifact: &Path) -> Result<Manifest> {
+    let display = artifact.display().to_string();
+    let file = fs::File::open(artifact)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("open {}", display))))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("read zip {}", display))))?;
+
+    let mut manifest = Manifest::new();
+    let mut buf = [0u8; 64 * 1024];
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(|e| {
+            Error::internal_io(e.to_string(), Some(format!("read zip {}", display)))
+        })?;
+        if entry.is_dir() {
+            continue;
+        }
+
+        let name = entry.name().to_string();
+        let mut hasher = Sha256::new();
+        loop {
+            let read = entry.read(&mut buf).map_err(|e| {
+                Error::internal_io(e.to_string(), Some(format!("read {} in {}", name, display)))
+            })?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buf[..read]);
+        }
+
+        let hash: String = hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        manifest.insert(name, hash);
+    }
+
+    Ok(manifest)
+}
+
+fn strip_wrapper_folder(manifest: Manifest, folder: &str) -> Manifest {
+    let prefix = format!("{}/", folder);
+    if folder.is_empty() || !manifest.keys().all(|path| path.starts_with(&prefix)) {
+        return manifest;
+    }
+
+    manifest
+        .into_iter()
+        .map(|(path, hash)| (path[prefix.len()..].to_string(), hash))
+        .collect()
+}
+
+/// Hash every file under each deployed directory with a single command.
+///
+/// Returns a manifest per key; directories that do not exist map to `None`.
+pub fn remote_manifests(
+    transport: &Transport,
+    dirs: &[(String, String)],
+) -> Result<HashMap<String, Option<Manifest>>> {
+    if dirs.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut script = String::from(
+        "if command -v sha256sum >/dev/null 2>&1; then H=sha256sum; else H='shasum -a 256'; fi; ",
+    );
+    for (key, dir) in dirs {
+        script.push_str(&format!(
+            "echo {marker}; if [ -d {dir} ]; then (cd {dir} && find -L . -type f -exec $H {{}} +); else echo {missing}; fi; ",
+            marker = shell::quote_arg(&format!("{}{}", SECTION_MARKER, key)),
+            dir = shell::quote_path(dir),
+            missing = MISSING_MARKER,
+        ));
+    }
+
+    let output = transport
+        .execute(&script)
+        .into_remote_result("drift hash listing", transport.target_details())?;
+
+    Ok(parse_remote_listing(&output.stdout))
+}
+
+/// Parse `sha256sum` output split into sections by component marker lines.
+fn parse_remote_listing(output: &str) -> HashMap<String, Option<Manifest>> {
+    let mut manifests: HashMap<String, Option<Manifest>> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in output.lines() {
+        if let Some(key) = line.strip_prefix(SECTION_MARKER) {
+            manifests.insert(key.to_string(), Some(Manifest::new()));
+            current = Some(key.to_string());
+            continue;
+        }
+
+        let Some(key) = current.as_ref() else {
+            continue;
+        };
+
+        if line == MISSING_MARKER {
+            manifests.insert(key.clone(), None);
+            continue;
+        }
+
+        // "<hash>  ./relative/path" (binary mode uses " *" as the separator)
+        let Some((hash, path)) = line.split_once(' ') else {
+            continue;
+        };
+        let path = path.trim_start_matches([' ', '*']);
+        let path = path.strip_prefix("./").unwrap_or(path);
+        if let Some(Some(manifest)) = manifests.get_mut(key) {
+            manifest.insert(path.to_string(), hash.to_string());
+        }
+    }
+
+    manifests
+}
+
+/// Drop paths matched by upload `exclude` patterns. Excluded files are never
+/// uploaded, so they are runtime files (caches, uploads) rather than drift.
+///
+/// Patterns follow rsync: a leading `/` anchors to the deployed directory, a
+/// trailing `/` matches directories only, `*` and `?` stay within one path
+/// segment and `**` crosses segments. Matching a directory excludes
+/// everything below it.
+pub fn without_excluded(manifest: Manifest, patterns: &[String]) -> Manifest {
+    if patterns.is_empty() {
+        return manifest;
+    }
+
+    manifest
+        .into_iter()
+        .filter(|(path, _)| !patterns.iter().any(|p| is_excluded(path, p)))
+        .collect()
+}
+
+fn is_excluded(path: &str, pattern: &str) -> bool {
+    let anchored = pattern.starts_with('/');
+    let dir_only = pattern.ends_with('/');
+    let pattern = pattern.trim_matches('/');
+    if pattern.is_empty() {
+        return false;
+    }
+
+    let segments: Vec<&str> = path.split('/').collect();
+    let starts = if anchored { 0..1 } else { 0..segments.len() };
+    for start in starts {
+        for end in start + 1..=segments.len() {
+            // The last segment is the file itself, never a directory
+            if dir_only && end == segments.len() {
+                continue;
+            }
+            if glob_match(
+                pattern.as_bytes(),
+                segments[start..end].join("/").as_bytes(),
+            ) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+        [b'*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != b'/')
+            .any(|i| glob_match(rest, &text[i..])),
+        [b'?', rest @ ..] => {
+            matches!(text, [c, tail @ ..] if *c != b'/' && glob_match(rest, tail))
+        }
+        [c, rest @ ..] => matches!(text, [t, tail @ ..] if t == c && glob_match(rest, tail)),
+    }
+}
+
+/// Compare a local manifest against what is deployed.
+pub fn compare(local: &Manifest, remote: &Manifest) -> DriftReport {
+    let mut report = DriftReport::default();
+
+    for (path, hash) in local {
+        match remote.get(path) {
+            None => report.added.push(path.clone()),
+            Some(remote_hash) if remote_hash != hash => report.modified.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+
+    report.removed = remote
+        .keys()
+        .filter(|path| !local.contains_key(*path))
+        .cloned()
+        .collect();
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(entries: &[(&str, &str)]) -> Manifest {
+        entries
+            .iter()
+            .map(|(p, h)| (p.to_string(), h.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parse_remote_listing_splits_sections_and_missing_dirs() {
+        let output = "\
+@@homeboy-drift plugin
+aaa  ./plugin.php
+bbb  ./inc/api.php
+@@homeboy-drift theme
+@@missing
+";
+        let parsed = parse_remote_listing(output);
+
+        assert_eq!(
+            parsed.get("plugin").cloned().flatten(),
+            Some(manifest(&[("plugin.php", "aaa"), ("inc/api.php", "bbb")]))
+        );
+        assert_eq!(parsed.get("theme"), Some(&None));
+    }
+
+    #[test]
+    fn compare_reports_added_modified_and_removed() {
+        let local = manifest(&[("a.php", "1"), ("b.php", "2"), ("new.php", "3")]);
+        let remote = manifest(&[("a.php", "1"), ("b.php", "hotfix"), ("stale.php", "4")]);
+
+        let report = compare(&local, &remote);
+
+        assert_eq!(report.added, vec!["new.php"]);
+        assert_eq!(report.modified, vec!["b.php"]);
+        assert_eq!(report.removed, vec!["stale.php"]);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn strip_wrapper_folder_only_when_every_entry_is_wrapped() {
+        let wrapped = manifest(&[("my-plugin/a.php", "1"), ("my-plugin/inc/b.php", "2")]);
+        assert_eq!(
+            strip_wrapper_folder(wrapped, "my-plugin"),
+            manifest(&[("a.php", "1"), ("inc/b.php", "2")])
+        );
+
+        let mixed = manifest(&[("my-plugin/a.php", "1"), ("readme.txt", "2")]);
+        assert_eq!(strip_wrapper_folder(mixed.clone(), "my-plugin"), mixed);
+    }
+
+    #[test]
+    fn without_excluded_follows_rsync_patterns() {
+        let deployed = manifest(&[
+            ("plugin.php", "1"),
+            ("cache/page.html", "2"),
+            ("inc/cache/keep.php", "3"),
+            ("uploads/2026/photo.jpg", "4"),
+            ("debug.log", "5"),
+            ("logs", "6"),
+            ("inc/logs/error.txt", "7"),
+        ]);
+        let patterns: Vec<String> = ["/cache/", "uploads", "*.log", "logs/"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+
+        assert_eq!(
+            without_excluded(deployed, &patterns),
+            manifest(&[
+                ("plugin.php", "1"),
+                ("inc/cache/keep.php", "3"),
+                ("logs", "6"),
+            ])
+        );
+    }
+}
@@ -37,6 +37,7 @@ pub enum ErrorCode {
     DeployNoComponentsConfigured,
     DeployBuildFailed,
     DeployUploadFailed,
+    DeployLocked,
 
     GitCommandFailed,
 
@@ -74,6 +75,7 @@ impl ErrorCode {
             ErrorCode::DeployNoComponentsConfigured => "deploy.no_components_configured",
             ErrorCode::DeployBuildFailed => "deploy.build_failed",
             ErrorCode::DeployUploadFailed => "deploy.upload_failed",
+            ErrorCode::DeployLocked => "deploy.locked",
 
             ErrorCode::GitCommandFailed => "git.command_failed",
 
@@ -210,6 +212,13 @@ pub struct RemoteCommandFailedDetails {
 
 #[derive(Debug, Serialize)]
 
+pub struct RemoteCommandTimeoutDetails {
+    pub command: String,
+    pub timeout_seconds: u64,
+}
+
+#[derive(Debug, Serialize)]
+
 pub struct SshServerInvalidDetails {
     pub server_id: String,
     pub missing_fields: Vec<String>,
@@ -222,6 +231,17 @@ pub struct SshIdentityFileNotFoundDetails {
     pub identity_file: String,
 }
 
+#[derive(Debug, Serialize)]
+
+pub struct DeployLockedDetails {
+    pub project_id: String,
+    pub lock_path: String,
+    pub owner: String,
+    pub host: String,
+    pub pid: u32,
+    pub started_at: String,
+}
+
 impl Error {
     pub fn new(code: ErrorCode, message: impl Into<String>, details: Value) -> Self {
         Self {
@@ -360,6 +380,20 @@ impl Error {
         )
     }
 
+    pub fn deploy_locked(details: DeployLockedDetails) -> Self {
+        let message = format!(
+            "Deploy already in progress for project '{}' (started {} by {}@{}, pid {})",
+            details.project_id, details.started_at, details.owner, details.host, details.pid
+        );
+        let details =
+            serde_json::to_value(details).unwrap_or_else(|_| Value::Object(serde_json::Map::new()));
+
+        let mut err = Self::new(ErrorCode::DeployLocked, message, details)
+            .with_hint("Wait for the other deploy to finish, or rerun with --force-unlock if it is no longer running");
+        err.retryable = Some(true);
+        err
+    }
+
     pub fn remote_command_failed(details: RemoteCommandFailedDetails) -> Self {
         let details =
             serde_json::to_value(details).unwrap_or_else(|_| Value::Object(serde_json::Map::new()));
@@ -371,6 +405,19 @@ impl Error {
         )
     }
 
+    pub fn remote_command_timeout(details: RemoteCommandTimeoutDetails) -> Self {
+        let message = format!(
+            "Command timed out after {}s: {}",
+            details.timeout_seconds, details.command
+        );
+        let details =
+            serde_json::to_value(details).unwrap_or_else(|_| Value::Object(serde_json::Map::new()));
+
+        let mut err = Self::new(ErrorCode::RemoteCommandTimeout, message, details);
+        err.retryable = Some(true);
+        err
+    }
+
     pub fn git_command_failed(message: impl Into<String>) -> Self {
         Self::new(
             ErrorCode::GitCommandFailed,
@@ -35,6 +35,7 @@
 // execute_for_project_interactive() -> routes local/SSH with inherited stdio
 // execute_for_project_direct() -> tries direct first, falls back to shell
 
+use crate::cancel;
 use crate::context::resolve_project_ssh;
 use crate::error::{Error, Result};
 use crate::module::CliConfig;
@@ -131,7 +132,7 @@ fn try_execute_direct(
         cmd.args(&parsed.args);
     }
 
-    match cmd.output() {
+    match cancel::output(&mut cmd) {
         Ok(out) => Ok(CommandOutput {
             stdout: String::from_utf8_lossy(&out.stdout).to_string(),
             stderr: String::from_utf8_lossy(&out.stderr).to_string(),
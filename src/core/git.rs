@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::Command;
 
+use crate::cancel;
 use crate::component;
 use crate::config::read_json_spec_to_string;
 use crate::error::{Error, Result};
@@ -15,10 +16,9 @@ use crate::project;
 
 /// Clone a git repository to a target directory.
 pub fn clone_repo(url: &str, target_dir: &Path) -> Result<()> {
-    let output = Command::new("git")
-        .args(["clone", url, &target_dir.to_string_lossy()])
-        .output()
-        .map_err(|e| Error::git_command_failed(format!("Failed to run git clone: {}", e)))?;
+    let output =
+        cancel::output(Command::new("git").args(["clone", url, &target_dir.to_string_lossy()]))
+            .map_err(|e| Error::git_command_failed(format!("Failed to run git clone: {}", e)))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -33,10 +33,7 @@ pub fn clone_repo(url: &str, target_dir: &Path) -> Result<()> {
 
 /// Pull latest changes in a git repository.
 pub fn pull_repo(repo_dir: &Path) -> Result<()> {
-    let output = Command::new("git")
-        .args(["pull"])
-        .current_dir(repo_dir)
-        .output()
+    let output = cancel::output(Command::new("git").args(["pull"]).current_dir(repo_dir))
         .map_err(|e| Error::git_command_failed(format!("Failed to run git pull: {}", e)))?;
 
     if !output.status.success() {
@@ -52,10 +49,11 @@ pub fn pull_repo(repo_dir: &Path) -> Result<()> {
 
 /// Check if a git working directory has no uncommitted changes.
 pub fn is_workdir_clean(path: &Path) -> bool {
-    let output = Command::new("git")
-        .args(["status", "--porcelain"])
-        .current_dir(path)
-        .output();
+    let output = cancel::output(
+        Command::new("git")
+            .args(["status", "--porcelain"])
+            .current_dir(path),
+    );
 
     match output {
         Ok(output) => output.status.success() && output.stdout.is_empty(),
@@ -63,6 +61,23 @@ pub fn is_workdir_clean(path: &Path) -> bool {
     }
 }
 
+/// Get the full commit SHA of HEAD, or None if not a git repository.
+pub fn head_sha(path: &Path) -> Option<String> {
+    let output = cancel::output(
+        Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(path),
+    )
+    .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!sha.is_empty()).then_some(sha)
+}
+
 /// Pull with fast-forward only, inheriting stdio for interactive output.
 pub fn pull_ff_only_interactive(path: &Path) -> Result<()> {
     use std::process::Stdio;
@@ -139,11 +154,12 @@ pub fn parse_conventional_commit(subject: &str) -> CommitCategory {
 /// Get the latest git tag in the repository.
 /// Returns None if no tags exist.
 pub fn get_latest_tag(path: &str) -> Result<Option<String>> {
-    let output = Command::new("git")
-        .args(["describe", "--tags", "--abbrev=0"])
-        .current_dir(path)
-        .output()
-        .map_err(|e| Error::other(format!("Failed to run git describe: {}", e)))?;
+    let output = cancel::output(
+        Command::new("git")
+            .args(["describe", "--tags", "--abbrev=0"])
+            .current_dir(path),
+    )
+    .map_err(|e| Error::other(format!("Failed to run git describe: {}", e)))?;
 
     if !output.status.success() {
         // No tags exist - this is fine, not an error
@@ -179,11 +195,12 @@ const NOISY_UNTRACKED_DIRS: [&str; 8] = [
 /// Matches strict patterns: v1.0.0, bump to X, release X, version X
 /// Returns the commit hash if found, None otherwise.
 pub fn find_version_commit(path: &str) -> Result<Option<String>> {
-    let output = Command::new("git")
-        .args(["log", "-200", "--format=%h|%s"])
-        .current_dir(path)
-        .output()
-        .map_err(|e| Error::other(format!("Failed to run git log: {}", e)))?;
+    let output = cancel::output(
+        Command::new("git")
+            .args(["log", "-200", "--format=%h|%s"])
+            .current_dir(path),
+    )
+    .map_err(|e| Error::other(format!("Failed to run git log: {}", e)))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -214,11 +231,12 @@ pub fn find_version_commit(path: &str) -> Result<Option<String>> {
 
 /// Get the last N commits from the repository.
 pub fn get_last_n_commits(path: &str, n: usize) -> Result<Vec<CommitInfo>> {
-    let output = Command::new("git")
-        .args(["log", &format!("-{}", n), "--format=%h|%s"])
-        .current_dir(path)
-        .output()
-        .map_err(|e| Error::other(format!("Failed to run git log: {}", e)))?;
+    let output = cancel::output(
+        Command::new("git")
+            .args(["log", &format!("-{}", n), "--format=%h|%s"])
+            .current_dir(path),
+    )
+    .map_err(|e| Error::other(format!("Failed to run git log: {}", e)))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -251,17 +269,21 @@ pub fn get_last_n_commits(path: &str, n: usize) -> Result<Vec<CommitInfo>> {
 
 /// Get commits since a given tag (or all commits if tag is None).
 /// Returns commits in reverse chronological order (newest first).
+///
+/// Commit bodies are checked for `BREAKING CHANGE:` footers, so a breaking
+/// change described only in the body is still categorized as Breaking.
 pub fn get_commits_since_tag(path: &str, tag: Option<&str>) -> Result<Vec<CommitInfo>> {
     let range = match tag {
         Some(t) => format!("{}..HEAD", t),
         None => "HEAD".to_string(),
     };
 
-    let output = Command::new("git")
-        .args(["log", &range, "--format=%h|%s"])
-        .current_dir(path)
-        .output()
-        .map_err(|e| Error::other(format!("Failed to run git log: {}", e)))?;
+    let output = cancel::output(
+        Command::new("git")
+            .args(["log", &range, "--format=%h%x1f%s%x1f%b%x1e"])
+            .current_dir(path),
+    )
+    .map_err(|e| Error::other(format!("Failed to run git log: {}", e)))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -269,27 +291,40 @@ pub fn get_commits_since_tag(path: &str, tag: Option<&str>) -> Result<Vec<Commit
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let commits = stdout
-        .lines()
-        .filter(|line| !line.is_empty())
-        .filter_map(|line| {
-            let parts: Vec<&str> = line.splitn(2, '|').collect();
-            if parts.len() == 2 {
-                let hash = parts[0].to_string();
-                let subject = parts[1].to_string();
-                let category = parse_conventional_commit(&subject);
-                Some(CommitInfo {
-                    hash,
-                    subject,
-                    category,
-                })
-            } else {
-                None
+    Ok(parse_commit_records(&stdout))
+}
+
+/// Parse `%h%x1f%s%x1f%b%x1e` git log output.
+fn parse_commit_records(output: &str) -> Vec<CommitInfo> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(3, '\x1f');
+            let hash = fields.next()?.trim().to_string();
+            let subject = fields.next()?.to_string();
+            let body = fields.next().unwrap_or("");
+            if hash.is_empty() {
+                return None;
             }
+            let category = if has_breaking_footer(body) {
+                CommitCategory::Breaking
+            } else {
+                parse_conventional_commit(&subject)
+            };
+            Some(CommitInfo {
+                hash,
+                subject,
+                category,
+            })
         })
-        .collect();
+        .collect()
+}
 
-    Ok(commits)
+fn has_breaking_footer(body: &str) -> bool {
+    body.lines().any(|line| {
+        let line = line.trim_start();
+        line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+    })
 }
 
 /// Convert commits to changelog entries.
@@ -308,7 +343,7 @@ pub fn commits_to_changelog_entries(commits: &[CommitInfo]) -> Vec<String> {
 /// Strip conventional commit prefix from a subject line.
 /// "feat: Add new feature" -> "Add new feature"
 /// "fix(scope): Fix bug" -> "Fix bug"
-fn strip_conventional_prefix(subject: &str) -> &str {
+pub(crate) fn strip_conventional_prefix(subject: &str) -> &str {
     // Pattern: type(scope)?: message or type!: message
     if let Some(pos) = subject.find(": ") {
         let prefix = &subject[..pos];
@@ -501,7 +536,7 @@ fn get_component_path(component_id: &str) -> Result<String> {
 }
 
 fn execute_git(path: &str, args: &[&str]) -> std::io::Result<std::process::Output> {
-    Command::new("git").args(args).current_dir(path).output()
+    cancel::output(Command::new("git").args(args).current_dir(path))
 }
 
 pub fn execute_git_for_release(path: &str, args: &[&str]) -> std::io::Result<std::process::Output> {
@@ -1231,10 +1266,8 @@ pub fn changes_project_filtered(
     Ok(build_bulk_changes_output(&filtered, include_diff))
 }
 
-fn is_git_repo(path: &str) -> bool {
-    std::process::Command::new("git")
-        .args(["-C", path, "rev-parse", "--git-dir"])
-        .output()
+pub(crate) fn is_git_repo(path: &str) -> bool {
+    cancel::output(Command::new("git").args(["-C", path, "rev-parse", "--git-dir"]))
         .map(|o| o.status.success())
         .unwrap_or(false)
 }
@@ -1260,6 +1293,25 @@ pub fn tag_exists_on_remote(path: &str, tag_name: &str) -> Result<bool> {
     Ok(!stdout.trim().is_empty())
 }
 
+/// List local tags.
+pub fn list_tags(path: &str) -> Result<Vec<String>> {
+    let output = execute_git(path, &["tag", "-l"]).map_err(|e| Error::other(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::git_command_failed(format!(
+            "git tag failed: {}",
+            stderr.trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
 /// Check if a tag exists locally.
 pub fn tag_exists_locally(path: &str, tag_name: &str) -> Result<bool> {
     let output =
@@ -1273,6 +1325,18 @@ pub fn tag_exists_locally(path: &str, tag_name: &str) -> Result<bool> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_commit_records_reads_breaking_footers() {
+        let output = "abc1234\x1ffeat: Add export\x1f\x1e\n\
+                      def5678\x1frefactor: Rework config\x1fDetails.\n\nBREAKING CHANGE: keys renamed\n\x1e\n";
+        let commits = parse_commit_records(output);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].hash, "abc1234");
+        assert_eq!(commits[0].category, CommitCategory::Feature);
+        assert_eq!(commits[1].subject, "refactor: Rework config");
+        assert_eq!(commits[1].category, CommitCategory::Breaking);
+    }
+
     #[test]
     fn parse_conventional_commit_feat() {
         assert_eq!(
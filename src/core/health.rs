@@ -0,0 +1,283 @@
+//! Post-deploy HTTP health checks.
+//!
+//! Checks request a path on the project domain (or a sub-target domain) and
+//! compare the response status and, optionally, the body against a regex.
+
+use regex::Regex;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use std::thread;
+use std::time::Duration;
+
+use crate::project::Project;
+
+/// Health checks declared on a project or component.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+
+pub struct HealthCheckConfig {
+    #[serde(default)]
+    pub checks: Vec<HealthCheck>,
+    /// Restore the previous release or backup when a check fails
+    #[serde(default)]
+    pub rollback_on_failure: bool,
+}
+
+/// A single HTTP health check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub struct HealthCheck {
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// Sub-target name whose domain is checked (default: project domain)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_target: Option<String>,
+    #[serde(default = "default_expected_status")]
+    pub expected_status: u16,
+    /// Regex the response body must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_pattern: Option<String>,
+    /// Additional attempts after the first failure
+    #[serde(default)]
+    pub retries: u32,
+    #[serde(default = "default_retry_delay_seconds")]
+    pub retry_delay_seconds: u64,
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u64,
+}
+
+fn default_path() -> String {
+    "/".to_string()
+}
+
+fn default_expected_status() -> u16 {
+    200
+}
+
+fn default_retry_delay_seconds() -> u64 {
+    2
+}
+
+fn default_timeout_seconds() -> u64 {
+    10
+}
+
+/// Outcome of one health check.
+#[derive(Debug, Clone, Serialize)]
+
+pub struct HealthCheckResult {
+    pub url: String,
+    pub passed: bool,
+    pub attempts: u32,
+    pub status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Run checks in order against the project's domains. Never fails; problems are
+/// reported per check.
+pub fn run_checks(checks: &[HealthCheck], project: &Project) -> Vec<HealthCheckResult> {
+    checks
+        .iter()
+        .map(|check| run_check(check, project))
+        .collect()
+}
+
+fn run_check(check: &HealthCheck, project: &Project) -> HealthCheckResult {
+    let url = match check_url(check, project) {
+        Ok(url) => url,
+        Err(error) => {
+            return HealthCheckResult {
+                url: check.path.clone(),
+                passed: false,
+                attempts: 0,
+                status: None,
+                error: Some(error),
+            }
+        }
+    };
+
+    let body_pattern = match check.body_pattern.as_deref().map(Regex::new).transpose() {
+        Ok(pattern) => pattern,
+        Err(err) => {
+            return HealthCheckResult {
+                url,
+                passed: false,
+                attempts: 0,
+                status: None,
+                error: Some(format!("Invalid body_pattern: {}", err)),
+            }
+        }
+    };
+
+    let client = match Client::builder()
+        .timeout(Duration::from_secs(check.timeout_seconds))
+        .build()
+    {
+        Ok(client) => client,
+        Err(err) => {
+            return HealthCheckResult {
+                url,
+                passed: false,
+                attempts: 0,
+                status: None,
+                error: Some(err.to_string()),
+            }
+        }
+    };
+
+    let mut result = HealthCheckResult {
+        url: url.clone(),
+        passed: false,
+        attempts: 0,
+        status: None,
+        error: None,
+    };
+
+    for attempt in 1..=check.retries + 1 {
+        if attempt > 1 {
+            thread::sleep(Duration::from_secs(check.retry_delay_seconds));
+        }
+        result.attempts = attempt;
+
+        let (status, error) = match client.get(&url).send() {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let error = if status != check.expected_status {
+                    Some(format!(
+                        "Expected HTTP {}, got {}",
+                        check.expected_status, status
+                    ))
+                } else {
+                    match (&body_pattern, response.text()) {
+                        (None, _) => None,
+                        (Some(pattern), Ok(body)) if pattern.is_match(&body) => None,
+                        (Some(pattern), Ok(_)) => {
+                            Some(format!("Response body does not match /{}/", pattern))
+                        }
+                        (Some(_), Err(err)) => Some(format!("Failed to read body: {}", err)),
+                    }
+                };
+                (Some(status), error)
+            }
+            Err(err) => (None, Some(err.to_string())),
+        };
+
+        result.status = status;
+        result.error = error;
+        if result.error.is_none() {
+            result.passed = true;
+            break;
+        }
+    }
+
+    result
+}
+
+/// Resolve the URL for a check. Domains without a scheme use https.
+fn check_url(check: &HealthCheck, project: &Project) -> std::result::Result<String, String> {
+    let domain = match check.sub_target.as_deref() {
+        Some(name) => project
+            .sub_targets
+            .iter()
+            .find(|t| t.name == name)
+            .or_else(|| project.find_sub_target(name))
+            .map(|t| t.domain.clone())
+            .ok_or_else(|| format!("Unknown sub-target '{}'", name))?,
+        None => project
+            .domain
+            .clone()
+            .filter(|d| !d.is_empty())
+            .ok_or_else(|| "Project has no domain configured".to_string())?,
+    };
+
+    let base = if domain.contains("://") {
+        domain
+    } else {
+        format!("https://{}", domain)
+    };
+
+    Ok(format!(
+        "{}/{}",
+        base.trim_end_matches('/'),
+        check.path.trim_start_matches('/')
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve canned HTTP responses, one per connection, from a local port.
+    fn serve(responses: Vec<(u16, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    fn check(body_pattern: Option<&str>, retries: u32) -> HealthCheck {
+        HealthCheck {
+            path: "/health".to_string(),
+            sub_target: None,
+            expected_status: 200,
+            body_pattern: body_pattern.map(str::to_string),
+            retries,
+            retry_delay_seconds: 0,
+            timeout_seconds: 5,
+        }
+    }
+
+    fn project(domain: String) -> Project {
+        Project {
+            domain: Some(domain),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn passes_on_expected_status_and_body() {
+        let project = project(serve(vec![(200, "{\"ok\":true}")]));
+        let results = run_checks(&[check(Some("\"ok\":\\s*true"), 0)], &project);
+
+        assert!(results[0].passed, "{:?}", results[0]);
+        assert_eq!(results[0].status, Some(200));
+        assert!(results[0].url.ends_with("/health"));
+    }
+
+    #[test]
+    fn retries_until_success() {
+        let project = project(serve(vec![(503, "down"), (200, "up")]));
+        let results = run_checks(&[check(None, 2)], &project);
+
+        assert!(results[0].passed);
+        assert_eq!(results[0].attempts, 2);
+    }
+
+    #[test]
+    fn fails_when_body_does_not_match() {
+        let project = project(serve(vec![(200, "fatal error")]));
+        let results = run_checks(&[check(Some("^ok$"), 0)], &project);
+
+        assert!(!results[0].passed);
+        assert!(results[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("does not match"));
+    }
+}
@@ -3,32 +3,46 @@ pub mod api;
 pub mod auth;
 pub mod build;
 pub mod changelog;
+pub mod changelog_format;
 pub mod cli_tool;
 pub mod component;
 pub mod config;
 pub mod context;
 pub mod db;
 pub mod deploy;
+pub mod deploy_history;
+pub mod deploy_hooks;
+pub mod deploy_plan;
+pub mod drift;
 pub mod error;
 pub mod executor;
 pub mod files;
 pub mod git;
+pub mod health;
 pub mod logs;
 pub mod module;
 pub mod output;
 pub mod pipeline;
 pub mod project;
 pub mod release;
+pub mod release_runs;
 
 pub mod server;
 pub mod shell;
 pub mod ssh;
 pub mod token;
+pub mod transport;
 pub mod upgrade;
 pub mod version;
+pub mod version_constraints;
+pub mod version_locator;
 
 // Internal modules - not part of public API
 pub(crate) mod base_path;
+pub(crate) mod cancel;
+pub(crate) mod checksum;
+pub(crate) mod condition;
+pub(crate) mod deploy_lock;
 pub(crate) mod http;
 pub(crate) mod keychain;
 pub(crate) mod local_files;
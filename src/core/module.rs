@@ -50,6 +50,8 @@ pub struct ModuleManifest {
     pub deploy_override: Vec<DeployOverride>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub version_patterns: Vec<VersionPatternConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changelog_formats: Vec<ChangelogFormatConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub build: Option<BuildConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -211,6 +213,14 @@ pub struct VersionPatternConfig {
     pub pattern: String,
 }
 
+/// Changelog format for files with this name (e.g. `readme.txt`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub struct ChangelogFormatConfig {
+    pub file: String,
+    pub format: crate::changelog_format::ChangelogFormat,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 
 pub struct BuildConfig {
@@ -1317,6 +1327,7 @@ impl ModuleScope {
 // Module Lifecycle Operations
 // ============================================================================
 
+use crate::cancel;
 use crate::git;
 use std::process::Command;
 
@@ -1361,10 +1372,11 @@ pub fn is_git_url(source: &str) -> bool {
 
 /// Check if a git working directory is clean (no uncommitted changes).
 fn is_workdir_clean(path: &Path) -> bool {
-    let output = Command::new("git")
-        .args(["status", "--porcelain"])
-        .current_dir(path)
-        .output();
+    let output = cancel::output(
+        Command::new("git")
+            .args(["status", "--porcelain"])
+            .current_dir(path),
+    );
 
     match output {
         Ok(output) => output.status.success() && output.stdout.is_empty(),
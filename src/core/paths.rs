@@ -47,6 +47,16 @@ pub fn backups() -> Result<PathBuf> {
     Ok(homeboy()?.join("backups"))
 }
 
+/// Deploy history directory
+pub fn deploy_history() -> Result<PathBuf> {
+    Ok(homeboy()?.join("deploy-history"))
+}
+
+/// Release run state directory
+pub fn release_runs() -> Result<PathBuf> {
+    Ok(homeboy()?.join("release-runs"))
+}
+
 /// Project file path
 pub fn project(id: &str) -> Result<PathBuf> {
     Ok(projects()?.join(format!("{}.json", id)))
@@ -76,3 +86,13 @@ pub fn module_manifest(id: &str) -> Result<PathBuf> {
 pub fn key(server_id: &str) -> Result<PathBuf> {
     Ok(keys()?.join(format!("{}_id_rsa", server_id)))
 }
+
+/// Deploy history ledger file path (JSON lines)
+pub fn deploy_history_file(project_id: &str) -> Result<PathBuf> {
+    Ok(deploy_history()?.join(format!("{}.jsonl", project_id)))
+}
+
+/// Persisted release run state file path
+pub fn release_run_file(run_id: &str) -> Result<PathBuf> {
+    Ok(release_runs()?.join(format!("{}.json", run_id)))
+}
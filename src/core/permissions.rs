@@ -3,14 +3,18 @@ use std::process::Command;
 use crate::defaults;
 use crate::error::{Error, Result};
 use crate::shell;
-use crate::ssh::{CommandOutput, SshClient};
+use crate::ssh::CommandOutput;
+use crate::transport::Transport;
 
 /// Fix local file permissions before build.
 ///
 /// Ensures files have group read/write so the zip archive contains correct permissions.
 /// This addresses the issue where Claude Code sometimes creates files with 600 permissions.
 pub fn fix_local_permissions(local_path: &str) {
-    eprintln!("[build] Fixing local file permissions");
+    eprintln!(
+        "{} Fixing local file permissions",
+        crate::deploy::log_prefix("build")
+    );
 
     let quoted_path = shell::quote_path(local_path);
     let perms = defaults::load_defaults().permissions.local;
@@ -31,7 +35,7 @@ pub fn fix_local_permissions(local_path: &str) {
 }
 
 /// Fix file permissions after deployment.
-pub fn fix_deployed_permissions(ssh_client: &SshClient, remote_path: &str) -> Result<()> {
+pub fn fix_deployed_permissions(transport: &Transport, remote_path: &str) -> Result<()> {
     let quoted_path = shell::quote_path(remote_path);
     let perms = defaults::load_defaults().permissions.remote;
 
@@ -39,14 +43,14 @@ pub fn fix_deployed_permissions(ssh_client: &SshClient, remote_path: &str) -> Re
         "find {} -type d -exec chmod {} {{}} + 2>/dev/null",
         quoted_path, perms.dir_mode
     );
-    let dir_output = ssh_client.execute(&dir_cmd);
+    let dir_output = transport.execute(&dir_cmd);
     ensure_remote_success(dir_output, "chmod directories", remote_path)?;
 
     let file_cmd = format!(
         "find {} -type f -exec chmod {} {{}} + 2>/dev/null",
         quoted_path, perms.file_mode
     );
-    let file_output = ssh_client.execute(&file_cmd);
+    let file_output = transport.execute(&file_cmd);
     ensure_remote_success(file_output, "chmod files", remote_path)?;
 
     Ok(())
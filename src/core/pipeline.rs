@@ -3,7 +3,12 @@ use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
-use crate::error::{Error, Result};
+use crate::cancel::CancelHandle;
+use crate::condition::{self, Condition};
+use crate::error::{Error, RemoteCommandTimeoutDetails, Result};
+
+/// How long a timed-out step's processes get to exit before they are killed.
+const CANCEL_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 
@@ -15,10 +20,50 @@ pub struct PipelineStep {
     pub label: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub needs: Vec<String>,
+    /// Condition that must hold for the step to run (see `condition`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    /// Fail the attempt if the step runs longer than this
+    #[serde(
+        default,
+        rename = "timeoutSeconds",
+        alias = "timeout_seconds",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timeout_seconds: Option<u64>,
+    /// Treat a failure as non-fatal: dependents still run and the run does not fail
+    #[serde(
+        default,
+        rename = "continueOnError",
+        alias = "continue_on_error",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub continue_on_error: bool,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub config: HashMap<String, serde_json::Value>,
 }
 
+/// How often a failed step is retried.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub count: u32,
+    /// Delay before the first retry; doubles after each further attempt
+    #[serde(
+        default = "default_backoff_seconds",
+        rename = "backoffSeconds",
+        alias = "backoff_seconds"
+    )]
+    pub backoff_seconds: u64,
+}
+
+fn default_backoff_seconds() -> u64 {
+    1
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 
 pub struct PipelinePlan {
@@ -37,6 +82,8 @@ pub struct PipelinePlanStep {
     pub label: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub needs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub config: HashMap<String, serde_json::Value>,
     pub status: PipelineStepStatus,
@@ -60,6 +107,11 @@ pub trait PipelineCapabilityResolver: Send + Sync {
 
 pub trait PipelineStepExecutor: Send + Sync {
     fn execute_step(&self, step: &PipelineStep) -> Result<PipelineStepResult>;
+
+    /// Values available to `when` conditions, in addition to `steps` and `env`.
+    fn condition_context(&self) -> serde_json::Map<String, serde_json::Value> {
+        serde_json::Map::new()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,6 +139,26 @@ pub struct PipelineStepResult {
     pub data: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Error code of the failure, e.g. `remote.command_timeout`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<PipelineStepAttempt>,
+    /// The step failed but was allowed to (`continueOnError`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub continue_on_error: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub struct PipelineStepAttempt {
+    pub attempt: u32,
+    pub status: PipelineRunStatus,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +179,8 @@ pub struct PipelineRunSummary {
     pub succeeded: usize,
     pub failed: usize,
     pub skipped: usize,
+    #[serde(default)]
+    pub condition_not_met: usize,
     pub missing: usize,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub next_actions: Vec<String>,
@@ -120,6 +194,8 @@ pub enum PipelineRunStatus {
     Failed,
     Skipped,
     Missing,
+    /// The step's `when` condition was false
+    ConditionNotMet,
 }
 
 pub fn plan(
@@ -149,6 +225,9 @@ pub fn plan_run(steps: &[PipelineStep], field: &str) -> Result<PipelineRunPlan>
 }
 
 fn order_steps(steps: &[PipelineStep], field: &str) -> Result<(Vec<PipelineStep>, Vec<String>)> {
+    validate_conditions(steps, field)?;
+    validate_step_references(steps, field)?;
+
     if steps.len() <= 1 {
         return Ok((steps.to_vec(), Vec::new()));
     }
@@ -228,6 +307,189 @@ fn order_steps(steps: &[PipelineStep], field: &str) -> Result<(Vec<PipelineStep>
     Ok((ordered, warnings))
 }
 
+fn validate_conditions(steps: &[PipelineStep], field: &str) -> Result<()> {
+    for step in steps {
+        if let Some(when) = &step.when {
+            Condition::parse(when).map_err(|problem| {
+                Error::validation_invalid_argument(
+                    field,
+                    format!(
+                        "Step '{}' has an invalid when condition: {}",
+                        step.id, problem
+                    ),
+                    Some(when.clone()),
+                    None,
+                )
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn step_reference_pattern() -> &'static regex::Regex {
+    static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    PATTERN.get_or_init(|| {
+        regex::Regex::new(r"\{\{\s*steps\.([A-Za-z0-9_.\-]+)\s*\}\}").expect("valid regex")
+    })
+}
+
+/// Paths (after `steps.`) referenced by `{{steps.<id>.<path>}}` in a config value.
+fn step_references(value: &serde_json::Value, found: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(text) => found.extend(
+            step_reference_pattern()
+                .captures_iter(text)
+                .map(|caps| caps[1].to_string()),
+        ),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| step_references(v, found)),
+        serde_json::Value::Object(map) => map.values().for_each(|v| step_references(v, found)),
+        _ => {}
+    }
+}
+
+/// Config may only reference steps that are guaranteed to have finished, i.e.
+/// the step's direct or transitive dependencies.
+fn validate_step_references(steps: &[PipelineStep], field: &str) -> Result<()> {
+    let by_id: HashMap<&str, &PipelineStep> =
+        steps.iter().map(|step| (step.id.as_str(), step)).collect();
+
+    for step in steps {
+        let mut references = Vec::new();
+        step.config
+            .values()
+            .for_each(|value| step_references(value, &mut references));
+        if references.is_empty() {
+            continue;
+        }
+
+        let mut ancestors: Vec<&str> = Vec::new();
+        let mut stack: Vec<&str> = step.needs.iter().map(String::as_str).collect();
+        while let Some(id) = stack.pop() {
+            if ancestors.contains(&id) {
+                continue;
+            }
+            ancestors.push(id);
+            if let Some(parent) = by_id.get(id) {
+                stack.extend(parent.needs.iter().map(String::as_str));
+            }
+        }
+
+        for reference in references {
+            let target = by_id
+                .keys()
+                .filter(|id| reference == **id || reference.starts_with(&format!("{}.", id)))
+                .max_by_key(|id| id.len());
+            match target {
+                Some(id) if ancestors.contains(id) => {}
+                Some(id) => {
+                    return Err(Error::validation_invalid_argument(
+                        field,
+                        format!(
+                            "Step '{}' references steps.{} but does not depend on '{}'",
+                            step.id, reference, id
+                        ),
+                        Some(step.id.clone()),
+                        None,
+                    )
+                    .with_hint(format!("Add '{}' to the step's needs", id)));
+                }
+                None => {
+                    return Err(Error::validation_invalid_argument(
+                        field,
+                        format!(
+                            "Step '{}' references steps.{}, which is not a known step",
+                            step.id, reference
+                        ),
+                        Some(step.id.clone()),
+                        Some(by_id.keys().map(|id| id.to_string()).collect()),
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace `{{steps.<id>.<path>}}` in a step's config with values from earlier
+/// results. A string that is exactly one reference takes the referenced value
+/// as-is (object, number, ...); references inside longer strings are
+/// interpolated as text.
+fn render_step_config(step: &PipelineStep, results: &[PipelineStepResult]) -> Result<PipelineStep> {
+    let mut references = Vec::new();
+    step.config
+        .values()
+        .for_each(|value| step_references(value, &mut references));
+    if references.is_empty() {
+        return Ok(step.clone());
+    }
+
+    let steps_context = steps_context(results);
+    let mut rendered = step.clone();
+    for value in rendered.config.values_mut() {
+        *value = render_value(value, &steps_context).map_err(|reference| {
+            Error::validation_invalid_argument(
+                "config",
+                format!(
+                    "Step '{}' references steps.{}, which has no value",
+                    step.id, reference
+                ),
+                Some(step.id.clone()),
+                None,
+            )
+        })?;
+    }
+    Ok(rendered)
+}
+
+fn render_value(
+    value: &serde_json::Value,
+    steps: &serde_json::Value,
+) -> std::result::Result<serde_json::Value, String> {
+    let resolve = |reference: &str| {
+        condition::lookup_path(steps, reference)
+            .filter(|value| !value.is_null())
+            .ok_or_else(|| reference.to_string())
+    };
+
+    match value {
+        serde_json::Value::String(text) => {
+            let pattern = step_reference_pattern();
+            if let Some(caps) = pattern.captures(text) {
+                if caps[0].len() == text.trim().len() {
+                    return resolve(&caps[1]);
+                }
+            }
+
+            let mut missing = None;
+            let replaced =
+                pattern.replace_all(text, |caps: &regex::Captures| match resolve(&caps[1]) {
+                    Ok(serde_json::Value::String(s)) => s,
+                    Ok(other) => other.to_string(),
+                    Err(reference) => {
+                        missing.get_or_insert(reference);
+                        String::new()
+                    }
+                });
+            match missing {
+                Some(reference) => Err(reference),
+                None => Ok(serde_json::Value::String(replaced.into_owned())),
+            }
+        }
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| render_value(item, steps))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map(serde_json::Value::Array),
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(key, item)| Ok((key.clone(), render_value(item, steps)?)))
+            .collect::<std::result::Result<serde_json::Map<_, _>, String>>()
+            .map(serde_json::Value::Object),
+        other => Ok(other.clone()),
+    }
+}
+
 fn to_plan_step(
     step: PipelineStep,
     resolver: &dyn PipelineCapabilityResolver,
@@ -249,6 +511,7 @@ fn to_plan_step(
         step_type: step.step_type,
         label: step.label,
         needs: step.needs,
+        when: step.when,
         config: step.config,
         status,
         missing,
@@ -261,6 +524,44 @@ pub fn run(
     resolver: Arc<dyn PipelineCapabilityResolver>,
     enabled: bool,
     field: &str,
+) -> Result<PipelineRunResult> {
+    resume(steps, &[], executor, resolver, enabled, field)
+}
+
+/// Run steps, reusing the results of steps that succeeded in a previous run.
+///
+/// Completed steps are not executed again. Their previous results are reported
+/// (with a warning) so dependent steps treat them as satisfied.
+pub fn resume(
+    steps: &[PipelineStep],
+    completed: &[PipelineStepResult],
+    executor: Arc<dyn PipelineStepExecutor>,
+    resolver: Arc<dyn PipelineCapabilityResolver>,
+    enabled: bool,
+    field: &str,
+) -> Result<PipelineRunResult> {
+    resume_with_progress(
+        steps,
+        completed,
+        executor,
+        resolver,
+        enabled,
+        field,
+        &mut |_| {},
+    )
+}
+
+/// Like [`resume`], calling `on_progress` with the results so far after each
+/// batch of steps finishes, so callers can persist a run that may be
+/// interrupted. Progress snapshots have no summary.
+pub fn resume_with_progress(
+    steps: &[PipelineStep],
+    completed: &[PipelineStepResult],
+    executor: Arc<dyn PipelineStepExecutor>,
+    resolver: Arc<dyn PipelineCapabilityResolver>,
+    enabled: bool,
+    field: &str,
+    on_progress: &mut dyn FnMut(&PipelineRunResult),
 ) -> Result<PipelineRunResult> {
     if !enabled {
         let results: Vec<PipelineStepResult> = steps
@@ -274,6 +575,9 @@ pub fn run(
                 hints: Vec::new(),
                 data: None,
                 error: None,
+                error_code: None,
+                attempts: Vec::new(),
+                continue_on_error: false,
             })
             .collect();
         let summary = build_summary(&results, &PipelineRunStatus::Skipped);
@@ -291,7 +595,16 @@ pub fn run(
     let mut pending_steps: Vec<PipelineStep> = Vec::new();
 
     for step in plan.steps {
-        if resolver.is_supported(&step.step_type) {
+        let previous = completed
+            .iter()
+            .find(|r| r.id == step.id && r.status == PipelineRunStatus::Success);
+        if let Some(previous) = previous {
+            let mut result = previous.clone();
+            result
+                .warnings
+                .push("Completed in a previous attempt; not re-run".to_string());
+            results.push(result);
+        } else if resolver.is_supported(&step.step_type) {
             pending_steps.push(step);
         } else {
             results.push(PipelineStepResult {
@@ -303,6 +616,9 @@ pub fn run(
                 hints: Vec::new(),
                 data: None,
                 error: None,
+                error_code: None,
+                attempts: Vec::new(),
+                continue_on_error: false,
             });
             overall_status = PipelineRunStatus::Missing;
         }
@@ -324,14 +640,35 @@ pub fn run(
             ));
         }
 
+        let (ready, not_met) = evaluate_conditions(ready, &results, executor.as_ref());
+        results.extend(not_met);
+
+        let mut rendered = Vec::with_capacity(ready.len());
+        for step in ready {
+            match render_step_config(&step, &results) {
+                Ok(step) => rendered.push(step),
+                Err(err) => {
+                    overall_status = PipelineRunStatus::Failed;
+                    results.push(attempt_result(&step, Err(err)));
+                }
+            }
+        }
+        let ready = rendered;
+
         let batch_results = execute_batch(&ready, Arc::clone(&executor), Arc::clone(&resolver))?;
         for result in batch_results {
-            if matches!(result.status, PipelineRunStatus::Failed) {
+            if is_fatal_failure(&result) {
                 overall_status = PipelineRunStatus::Failed;
             }
             results.push(result);
         }
 
+        on_progress(&PipelineRunResult {
+            steps: results.clone(),
+            status: derive_overall_status(&results, overall_status.clone()),
+            warnings: plan.warnings.clone(),
+            summary: None,
+        });
         pending_steps = blocked;
     }
 
@@ -346,6 +683,79 @@ pub fn run(
     })
 }
 
+/// Split ready steps into those whose `when` condition holds and results for
+/// those whose condition is false.
+fn evaluate_conditions(
+    ready: Vec<PipelineStep>,
+    results: &[PipelineStepResult],
+    executor: &dyn PipelineStepExecutor,
+) -> (Vec<PipelineStep>, Vec<PipelineStepResult>) {
+    if ready.iter().all(|step| step.when.is_none()) {
+        return (ready, Vec::new());
+    }
+
+    let context = condition_context(results, executor);
+    let mut run = Vec::new();
+    let mut not_met = Vec::new();
+
+    for step in ready {
+        let Some(when) = step.when.clone() else {
+            run.push(step);
+            continue;
+        };
+        // Conditions were validated when the run was planned.
+        let holds = Condition::parse(&when)
+            .map(|condition| condition.evaluate(&context))
+            .unwrap_or(false);
+        if holds {
+            run.push(step);
+        } else {
+            not_met.push(PipelineStepResult {
+                id: step.id,
+                step_type: step.step_type,
+                status: PipelineRunStatus::ConditionNotMet,
+                missing: Vec::new(),
+                warnings: vec![format!("Condition not met: {}", when)],
+                hints: Vec::new(),
+                data: None,
+                error: None,
+                error_code: None,
+                attempts: Vec::new(),
+                continue_on_error: false,
+            });
+        }
+    }
+
+    (run, not_met)
+}
+
+fn condition_context(
+    results: &[PipelineStepResult],
+    executor: &dyn PipelineStepExecutor,
+) -> serde_json::Value {
+    let mut context = executor.condition_context();
+    context.insert("steps".to_string(), steps_context(results));
+    serde_json::Value::Object(context)
+}
+
+/// `{ <id>: { status, data, error } }` for finished steps.
+fn steps_context(results: &[PipelineStepResult]) -> serde_json::Value {
+    let steps: serde_json::Map<String, serde_json::Value> = results
+        .iter()
+        .map(|result| {
+            (
+                result.id.clone(),
+                serde_json::json!({
+                    "status": result.status,
+                    "data": result.data,
+                    "error": result.error,
+                }),
+            )
+        })
+        .collect();
+    serde_json::Value::Object(steps)
+}
+
 fn split_ready_steps(
     pending: &[PipelineStep],
     results: &[PipelineStepResult],
@@ -362,17 +772,29 @@ fn split_ready_steps(
         .iter()
         .map(|result| (result.id.clone(), result.status.clone()))
         .collect();
+    let ignored_failures: std::collections::HashSet<&str> = results
+        .iter()
+        .filter(|result| result.continue_on_error)
+        .map(|result| result.id.as_str())
+        .collect();
 
     for step in pending {
         let mut unmet = false;
         let mut failed_dependency: Option<String> = None;
 
         for need in &step.needs {
+            if ignored_failures.contains(need.as_str()) {
+                continue;
+            }
             match status_map.get(need) {
                 Some(PipelineRunStatus::Success) | Some(PipelineRunStatus::PartialSuccess) => {}
+                // A condition that reads the dependency's status decides for
+                // itself, e.g. `steps.build.status == 'failed'`.
+                Some(_) if reacts_to_status(step, need) => {}
                 Some(PipelineRunStatus::Failed)
                 | Some(PipelineRunStatus::Missing)
-                | Some(PipelineRunStatus::Skipped) => {
+                | Some(PipelineRunStatus::Skipped)
+                | Some(PipelineRunStatus::ConditionNotMet) => {
                     failed_dependency = Some(need.clone());
                     break;
                 }
@@ -392,6 +814,9 @@ fn split_ready_steps(
                 hints: Vec::new(),
                 data: None,
                 error: None,
+                error_code: None,
+                attempts: Vec::new(),
+                continue_on_error: false,
             };
             status_map.insert(step.id.clone(), PipelineRunStatus::Skipped);
             skipped.push(result);
@@ -408,6 +833,14 @@ fn split_ready_steps(
     (ready, blocked, skipped)
 }
 
+/// Whether the step's `when` reads `steps.<need>.status`.
+fn reacts_to_status(step: &PipelineStep, need: &str) -> bool {
+    step.when
+        .as_deref()
+        .and_then(|when| Condition::parse(when).ok())
+        .is_some_and(|condition| condition.references(&format!("steps.{}.status", need)))
+}
+
 fn derive_overall_status(
     results: &[PipelineStepResult],
     current: PipelineRunStatus,
@@ -415,9 +848,7 @@ fn derive_overall_status(
     let has_success = results
         .iter()
         .any(|result| matches!(result.status, PipelineRunStatus::Success));
-    let has_failed = results
-        .iter()
-        .any(|result| matches!(result.status, PipelineRunStatus::Failed));
+    let has_failed = results.iter().any(is_fatal_failure);
     let has_missing = results
         .iter()
         .any(|result| matches!(result.status, PipelineRunStatus::Missing));
@@ -440,15 +871,17 @@ fn derive_overall_status(
     PipelineRunStatus::Success
 }
 
+/// Failed, and not allowed to fail by `continueOnError`.
+fn is_fatal_failure(result: &PipelineStepResult) -> bool {
+    result.status == PipelineRunStatus::Failed && !result.continue_on_error
+}
+
 fn build_summary(results: &[PipelineStepResult], status: &PipelineRunStatus) -> PipelineRunSummary {
     let succeeded = results
         .iter()
         .filter(|r| matches!(r.status, PipelineRunStatus::Success))
         .count();
-    let failed = results
-        .iter()
-        .filter(|r| matches!(r.status, PipelineRunStatus::Failed))
-        .count();
+    let failed = results.iter().filter(|r| is_fatal_failure(r)).count();
     let skipped = results
         .iter()
         .filter(|r| matches!(r.status, PipelineRunStatus::Skipped))
@@ -457,6 +890,10 @@ fn build_summary(results: &[PipelineStepResult], status: &PipelineRunStatus) ->
         .iter()
         .filter(|r| matches!(r.status, PipelineRunStatus::Missing))
         .count();
+    let condition_not_met = results
+        .iter()
+        .filter(|r| matches!(r.status, PipelineRunStatus::ConditionNotMet))
+        .count();
 
     let next_actions = match status {
         PipelineRunStatus::PartialSuccess | PipelineRunStatus::Failed => {
@@ -476,6 +913,7 @@ fn build_summary(results: &[PipelineStepResult], status: &PipelineRunStatus) ->
         succeeded,
         failed,
         skipped,
+        condition_not_met,
         missing,
         next_actions,
     }
@@ -490,7 +928,7 @@ fn execute_batch(
         if let Some(step) = steps.first() {
             return Ok(vec![execute_single_step(
                 step.clone(),
-                executor.as_ref(),
+                executor,
                 resolver.as_ref(),
             )?]);
         }
@@ -505,7 +943,7 @@ fn execute_batch(
         .map(|step| {
             let executor = Arc::clone(&executor);
             let resolver = Arc::clone(&resolver);
-            thread::spawn(move || execute_single_step(step, executor.as_ref(), resolver.as_ref()))
+            thread::spawn(move || execute_single_step(step, executor, resolver.as_ref()))
         })
         .collect();
 
@@ -524,9 +962,10 @@ fn execute_batch(
     Ok(results)
 }
 
+/// Run a step, retrying failed attempts according to its retry policy.
 fn execute_single_step(
     step: PipelineStep,
-    executor: &dyn PipelineStepExecutor,
+    executor: Arc<dyn PipelineStepExecutor>,
     resolver: &dyn PipelineCapabilityResolver,
 ) -> Result<PipelineStepResult> {
     if !resolver.is_supported(&step.step_type) {
@@ -541,26 +980,520 @@ fn execute_single_step(
             hints: Vec::new(),
             data: None,
             error: None,
+            error_code: None,
+            attempts: Vec::new(),
+            continue_on_error: false,
         });
     }
 
-    match executor.execute_step(&step) {
+    let max_attempts = 1 + step.retry.as_ref().map_or(0, |retry| retry.count);
+    let mut attempts = Vec::new();
+    let mut attempt = 1;
+
+    loop {
+        let started = std::time::Instant::now();
+        let mut result = execute_attempt(&step, Arc::clone(&executor));
+        attempts.push(PipelineStepAttempt {
+            attempt,
+            status: result.status.clone(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            error: result.error.clone(),
+            error_code: result.error_code.clone(),
+        });
+
+        if result.status != PipelineRunStatus::Failed || attempt >= max_attempts {
+            if attempt > 1 {
+                result
+                    .warnings
+                    .push(format!("Finished after {} attempts", attempt));
+            }
+            if result.status == PipelineRunStatus::Failed && step.continue_on_error {
+                result.continue_on_error = true;
+                result
+                    .warnings
+                    .push("Failure ignored (continueOnError)".to_string());
+            }
+            result.attempts = attempts;
+            return Ok(result);
+        }
+
+        if let Some(retry) = &step.retry {
+            let delay = retry
+                .backoff_seconds
+                .saturating_mul(1 << (attempt - 1).min(16));
+            std::thread::sleep(std::time::Duration::from_secs(delay));
+        }
+        attempt += 1;
+    }
+}
+
+/// Run one attempt of a step, enforcing its timeout.
+///
+/// The attempt runs on its own thread inside a cancel scope. On timeout, the
+/// child processes it started are terminated (killed after a grace period)
+/// and the pipeline waits for the attempt to return, so a retry never runs
+/// alongside an earlier attempt and nothing outlives the run.
+fn execute_attempt(
+    step: &PipelineStep,
+    executor: Arc<dyn PipelineStepExecutor>,
+) -> PipelineStepResult {
+    let Some(timeout_seconds) = step.timeout_seconds else {
+        return attempt_result(step, executor.execute_step(step));
+    };
+
+    let cancel = CancelHandle::default();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let thread_step = step.clone();
+    let thread_cancel = cancel.clone();
+    std::thread::spawn(move || {
+        let outcome = thread_cancel.scope(|| executor.execute_step(&thread_step));
+        let _ = sender.send(outcome);
+    });
+
+    match receiver.recv_timeout(std::time::Duration::from_secs(timeout_seconds)) {
+        Ok(outcome) => attempt_result(step, outcome),
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+            cancel.cancel(false);
+            if let Err(std::sync::mpsc::RecvTimeoutError::Timeout) =
+                receiver.recv_timeout(CANCEL_GRACE)
+            {
+                cancel.cancel(true);
+                let _ = receiver.recv();
+            }
+            attempt_result(
+                step,
+                Err(Error::remote_command_timeout(RemoteCommandTimeoutDetails {
+                    command: format!("pipeline step '{}'", step.id),
+                    timeout_seconds,
+                })),
+            )
+        }
+        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => attempt_result(
+            step,
+            Err(Error::internal_unexpected(format!(
+                "Step '{}' execution thread panicked",
+                step.id
+            ))),
+        ),
+    }
+}
+
+fn attempt_result(step: &PipelineStep, outcome: Result<PipelineStepResult>) -> PipelineStepResult {
+    match outcome {
         Ok(mut result) => {
             if result.status == PipelineRunStatus::Success {
                 result.missing = Vec::new();
                 result.error = None;
+                result.error_code = None;
             }
-            Ok(result)
+            result
         }
-        Err(err) => Ok(PipelineStepResult {
-            id: step.id,
-            step_type: step.step_type,
+        Err(err) => PipelineStepResult {
+            id: step.id.clone(),
+            step_type: step.step_type.clone(),
             status: PipelineRunStatus::Failed,
             missing: Vec::new(),
             warnings: Vec::new(),
             hints: err.hints.clone(),
             data: None,
             error: Some(err.message.clone()),
-        }),
+            error_code: Some(err.code.as_str().to_string()),
+            attempts: Vec::new(),
+            continue_on_error: false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct AllSupported;
+
+    impl PipelineCapabilityResolver for AllSupported {
+        fn is_supported(&self, _step_type: &str) -> bool {
+            true
+        }
+
+        fn missing(&self, _step_type: &str) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    /// Records executed step IDs and fails the ones listed in `failing`.
+    struct Recorder {
+        executed: Mutex<Vec<String>>,
+        failing: Vec<String>,
+    }
+
+    impl PipelineStepExecutor for Recorder {
+        fn execute_step(&self, step: &PipelineStep) -> Result<PipelineStepResult> {
+            self.executed.lock().unwrap().push(step.id.clone());
+            let status = if self.failing.contains(&step.id) {
+                PipelineRunStatus::Failed
+            } else {
+                PipelineRunStatus::Success
+            };
+            Ok(PipelineStepResult {
+                id: step.id.clone(),
+                step_type: step.step_type.clone(),
+                status,
+                missing: Vec::new(),
+                warnings: Vec::new(),
+                hints: Vec::new(),
+                data: None,
+                error: None,
+                error_code: None,
+                attempts: Vec::new(),
+                continue_on_error: false,
+            })
+        }
+    }
+
+    fn chain(ids: &[&str]) -> Vec<PipelineStep> {
+        ids.iter()
+            .enumerate()
+            .map(|(idx, id)| PipelineStep {
+                id: id.to_string(),
+                step_type: id.to_string(),
+                label: None,
+                needs: if idx == 0 {
+                    Vec::new()
+                } else {
+                    vec![ids[idx - 1].to_string()]
+                },
+                when: None,
+                retry: None,
+                timeout_seconds: None,
+                continue_on_error: false,
+                config: HashMap::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resume_skips_steps_that_already_succeeded() {
+        let steps = chain(&["version", "git.tag", "git.push"]);
+        let first = Arc::new(Recorder {
+            executed: Mutex::new(Vec::new()),
+            failing: vec!["git.push".to_string()],
+        });
+        let failed = run(&steps, first, Arc::new(AllSupported), true, "steps").unwrap();
+        assert_eq!(failed.status, PipelineRunStatus::PartialSuccess);
+
+        let second = Arc::new(Recorder {
+            executed: Mutex::new(Vec::new()),
+            failing: Vec::new(),
+        });
+        let resumed = resume(
+            &steps,
+            &failed.steps,
+            second.clone(),
+            Arc::new(AllSupported),
+            true,
+            "steps",
+        )
+        .unwrap();
+
+        assert_eq!(resumed.status, PipelineRunStatus::Success);
+        assert_eq!(*second.executed.lock().unwrap(), vec!["git.push"]);
+        assert!(!resumed.steps[0].warnings.is_empty());
+    }
+
+    #[test]
+    fn progress_reports_results_after_each_batch() {
+        let steps = chain(&["version", "git.tag", "git.push"]);
+        let executor = Arc::new(Recorder {
+            executed: Mutex::new(Vec::new()),
+            failing: vec!["git.tag".to_string()],
+        });
+        let mut snapshots = Vec::new();
+
+        let result = resume_with_progress(
+            &steps,
+            &[],
+            executor,
+            Arc::new(AllSupported),
+            true,
+            "steps",
+            &mut |progress| {
+                snapshots.push((progress.steps.len(), progress.status.clone()));
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            snapshots,
+            vec![
+                (1, PipelineRunStatus::Success),
+                (2, PipelineRunStatus::PartialSuccess),
+            ]
+        );
+        assert_eq!(result.steps.len(), 3);
+    }
+
+    #[test]
+    fn when_conditions_gate_steps_and_see_prior_results() {
+        let mut steps = chain(&["build", "publish"]);
+        let mut notify = chain(&["notify"]);
+        notify[0].when = Some("env.HOMEBOY_TEST_NEVER_SET == 'yes'".to_string());
+        steps.extend(notify);
+        let mut cleanup = chain(&["cleanup"]);
+        cleanup[0].needs = vec!["build".to_string()];
+        cleanup[0].when = Some("steps.build.status == 'failed'".to_string());
+        steps.extend(cleanup);
+
+        let executor = Arc::new(Recorder {
+            executed: Mutex::new(Vec::new()),
+            failing: vec!["build".to_string()],
+        });
+        let result = run(
+            &steps,
+            executor.clone(),
+            Arc::new(AllSupported),
+            true,
+            "steps",
+        )
+        .unwrap();
+
+        assert_eq!(*executor.executed.lock().unwrap(), vec!["build", "cleanup"]);
+        let notify = result.steps.iter().find(|s| s.id == "notify").unwrap();
+        assert_eq!(notify.status, PipelineRunStatus::ConditionNotMet);
+        assert_eq!(result.summary.unwrap().condition_not_met, 1);
+    }
+
+    #[test]
+    fn unrelated_conditions_still_skip_after_failed_dependency() {
+        let mut steps = chain(&["build", "publish"]);
+        steps[1].when = Some("!prerelease".to_string());
+
+        let executor = Arc::new(Recorder {
+            executed: Mutex::new(Vec::new()),
+            failing: vec!["build".to_string()],
+        });
+        let result = run(
+            &steps,
+            executor.clone(),
+            Arc::new(AllSupported),
+            true,
+            "steps",
+        )
+        .unwrap();
+
+        assert_eq!(*executor.executed.lock().unwrap(), vec!["build"]);
+        let publish = result.steps.iter().find(|s| s.id == "publish").unwrap();
+        assert_eq!(publish.status, PipelineRunStatus::Skipped);
+    }
+
+    #[test]
+    fn plan_rejects_invalid_when_condition() {
+        let mut steps = chain(&["build"]);
+        steps[0].when = Some("branch ==".to_string());
+        assert!(plan(&steps, &AllSupported, true, "steps").is_err());
+    }
+
+    /// Fails its first `failures` calls, sleeping `delay_ms` on each call.
+    struct Flaky {
+        calls: Mutex<u32>,
+        failures: u32,
+        delay_ms: u64,
+    }
+
+    impl PipelineStepExecutor for Flaky {
+        fn execute_step(&self, step: &PipelineStep) -> Result<PipelineStepResult> {
+            std::thread::sleep(std::time::Duration::from_millis(self.delay_ms));
+            let mut calls = self.calls.lock().unwrap();
+            *calls += 1;
+            if *calls <= self.failures {
+                return Err(Error::other("publish failed"));
+            }
+            Recorder {
+                executed: Mutex::new(Vec::new()),
+                failing: Vec::new(),
+            }
+            .execute_step(step)
+        }
+    }
+
+    #[test]
+    fn retries_failed_steps_and_records_attempts() {
+        let mut steps = chain(&["publish"]);
+        steps[0].retry = Some(RetryPolicy {
+            count: 2,
+            backoff_seconds: 0,
+        });
+        let executor = Arc::new(Flaky {
+            calls: Mutex::new(0),
+            failures: 2,
+            delay_ms: 0,
+        });
+
+        let result = run(&steps, executor, Arc::new(AllSupported), true, "steps").unwrap();
+
+        assert_eq!(result.status, PipelineRunStatus::Success);
+        let attempts = &result.steps[0].attempts;
+        assert_eq!(attempts.len(), 3);
+        assert_eq!(attempts[0].status, PipelineRunStatus::Failed);
+        assert_eq!(attempts[2].status, PipelineRunStatus::Success);
+    }
+
+    #[test]
+    fn timed_out_step_fails_with_timeout_code_and_can_continue() {
+        let mut steps = chain(&["publish", "announce"]);
+        steps[0].timeout_seconds = Some(0);
+        steps[0].continue_on_error = true;
+        let executor = Arc::new(Flaky {
+            calls: Mutex::new(0),
+            failures: 0,
+            delay_ms: 200,
+        });
+
+        let result = run(&steps, executor, Arc::new(AllSupported), true, "steps").unwrap();
+
+        let publish = &result.steps[0];
+        assert_eq!(publish.status, PipelineRunStatus::Failed);
+        assert_eq!(
+            publish.error_code.as_deref(),
+            Some("remote.command_timeout")
+        );
+        assert!(publish.continue_on_error);
+        assert_eq!(result.steps[1].status, PipelineRunStatus::Success);
+        assert_eq!(result.status, PipelineRunStatus::Success);
+    }
+
+    /// Runs a shell command per attempt, tracking how many attempts overlap.
+    struct SlowCommand {
+        command: String,
+        active: Mutex<u32>,
+        max_active: Mutex<u32>,
+    }
+
+    impl PipelineStepExecutor for SlowCommand {
+        fn execute_step(&self, step: &PipelineStep) -> Result<PipelineStepResult> {
+            {
+                let mut active = self.active.lock().unwrap();
+                *active += 1;
+                let mut max = self.max_active.lock().unwrap();
+                *max = (*max).max(*active);
+            }
+            crate::ssh::execute_local_command(&self.command);
+            *self.active.lock().unwrap() -= 1;
+            Recorder {
+                executed: Mutex::new(Vec::new()),
+                failing: Vec::new(),
+            }
+            .execute_step(step)
+        }
+    }
+
+    #[test]
+    fn timed_out_attempts_are_terminated_before_retrying() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("finished");
+        let mut steps = chain(&["publish"]);
+        steps[0].timeout_seconds = Some(1);
+        steps[0].retry = Some(RetryPolicy {
+            count: 1,
+            backoff_seconds: 0,
+        });
+        let executor = Arc::new(SlowCommand {
+            command: format!("sleep 3 && touch {}", marker.display()),
+            active: Mutex::new(0),
+            max_active: Mutex::new(0),
+        });
+        let started = std::time::Instant::now();
+
+        let result = run(
+            &steps,
+            executor.clone(),
+            Arc::new(AllSupported),
+            true,
+            "steps",
+        )
+        .unwrap();
+
+        assert_eq!(result.steps[0].attempts.len(), 2);
+        assert_eq!(*executor.max_active.lock().unwrap(), 1);
+        assert!(started.elapsed() < std::time::Duration::from_secs(3));
+        std::thread::sleep(std::time::Duration::from_millis(2500));
+        assert!(!marker.exists());
+    }
+
+    /// Returns `{"url": "https://example.test/<id>"}` as data and records the
+    /// config each step ran with.
+    struct Publisher {
+        configs: Mutex<HashMap<String, HashMap<String, serde_json::Value>>>,
+    }
+
+    impl PipelineStepExecutor for Publisher {
+        fn execute_step(&self, step: &PipelineStep) -> Result<PipelineStepResult> {
+            self.configs
+                .lock()
+                .unwrap()
+                .insert(step.id.clone(), step.config.clone());
+            let mut result = Recorder {
+                executed: Mutex::new(Vec::new()),
+                failing: Vec::new(),
+            }
+            .execute_step(step)?;
+            result.data = Some(serde_json::json!({
+                "url": format!("https://example.test/{}", step.id),
+                "build": { "number": 42 }
+            }));
+            Ok(result)
+        }
+    }
+
+    #[test]
+    fn step_config_references_earlier_step_data() {
+        let mut steps = chain(&["git.tag", "announce"]);
+        steps[1].config.insert(
+            "message".to_string(),
+            serde_json::json!(
+                "Released {{ steps.git.tag.data.url }} (build {{steps.git.tag.data.build.number}})"
+            ),
+        );
+        steps[1].config.insert(
+            "build".to_string(),
+            serde_json::json!("{{steps.git.tag.data.build}}"),
+        );
+        let executor = Arc::new(Publisher {
+            configs: Mutex::new(HashMap::new()),
+        });
+
+        let result = run(
+            &steps,
+            executor.clone(),
+            Arc::new(AllSupported),
+            true,
+            "steps",
+        )
+        .unwrap();
+
+        assert_eq!(result.status, PipelineRunStatus::Success);
+        let configs = executor.configs.lock().unwrap();
+        assert_eq!(
+            configs["announce"]["message"],
+            "Released https://example.test/git.tag (build 42)"
+        );
+        assert_eq!(
+            configs["announce"]["build"],
+            serde_json::json!({ "number": 42 })
+        );
+    }
+
+    #[test]
+    fn step_references_must_be_dependencies() {
+        let mut steps = chain(&["build"]);
+        steps.extend(chain(&["announce"]));
+        steps[1].config.insert(
+            "url".to_string(),
+            serde_json::json!("{{steps.build.data.url}}"),
+        );
+        assert!(plan_run(&steps, "steps").is_err());
+
+        steps[1].needs = vec!["build".to_string()];
+        assert!(plan_run(&steps, "steps").is_ok());
     }
 }
@@ -1,5 +1,6 @@
 use crate::config::{self, ConfigEntity};
 use crate::error::{Error, Result};
+use crate::health::HealthCheckConfig;
 use crate::output::{CreateOutput, MergeOutput, RemoveResult};
 use crate::paths;
 use crate::server;
@@ -50,6 +51,16 @@ pub struct Project {
     pub shared_tables: Vec<String>,
     #[serde(default)]
     pub component_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheckConfig>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_deploy_commands: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_deploy_commands: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_deploy_failure: Vec<String>,
 }
 
 impl Project {
@@ -3,13 +3,13 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::component::{self, Component};
-use crate::core::local_files::FileSystem;
 use crate::error::{Error, Result};
 use crate::module::{self, ModuleManifest};
 use crate::pipeline::{
     self, PipelineCapabilityResolver, PipelinePlanStep, PipelineRunResult, PipelineRunStatus,
-    PipelineStep, PipelineStepExecutor, PipelineStepResult,
+    PipelineStep, PipelineStepExecutor, PipelineStepResult, RetryPolicy,
 };
+use crate::release_runs::{self, ReleaseRunRecord};
 use crate::{changelog, version};
 
 fn parse_module_inputs(values: &[serde_json::Value]) -> Result<Vec<(String, String)>> {
@@ -82,6 +82,25 @@ pub struct ReleaseStep {
     pub label: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub needs: Vec<String>,
+    /// Run the step only when this condition holds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    #[serde(
+        default,
+        rename = "timeoutSeconds",
+        alias = "timeout_seconds",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub timeout_seconds: Option<u64>,
+    #[serde(
+        default,
+        rename = "continueOnError",
+        alias = "continue_on_error",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub continue_on_error: bool,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub config: HashMap<String, serde_json::Value>,
 }
@@ -93,6 +112,10 @@ impl From<ReleaseStep> for PipelineStep {
             step_type: step.step_type,
             label: step.label,
             needs: step.needs,
+            when: step.when,
+            retry: step.retry,
+            timeout_seconds: step.timeout_seconds,
+            continue_on_error: step.continue_on_error,
             config: step.config,
         }
     }
@@ -113,8 +136,11 @@ pub struct ReleasePlan {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 
 pub struct ReleaseRun {
+    pub run_id: String,
     pub component_id: String,
     pub enabled: bool,
+    /// Number of times this run has been executed (1 + resumes)
+    pub attempts: u32,
     pub result: PipelineRunResult,
 }
 
@@ -128,13 +154,15 @@ pub struct ReleaseArtifact {
     pub platform: Option<String>,
 }
 
+/// Values produced by release steps and passed to later ones.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 
-struct ReleaseContext {
-    version: Option<String>,
-    tag: Option<String>,
-    notes: Option<String>,
-    artifacts: Vec<ReleaseArtifact>,
+pub struct ReleaseContext {
+    pub version: Option<String>,
+    pub tag: Option<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub artifacts: Vec<ReleaseArtifact>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -147,6 +175,8 @@ pub struct ReleasePlanStep {
     pub label: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub needs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub config: HashMap<String, serde_json::Value>,
     pub status: ReleasePlanStatus,
@@ -167,6 +197,7 @@ impl From<PipelinePlanStep> for ReleasePlanStep {
             step_type: step.step_type,
             label: step.label,
             needs: step.needs,
+            when: step.when,
             config: step.config,
             status,
             missing: step.missing,
@@ -225,11 +256,11 @@ struct ReleaseStepExecutor {
 }
 
 impl ReleaseStepExecutor {
-    fn new(component_id: String, modules: Vec<ModuleManifest>) -> Self {
+    fn new(component_id: String, modules: Vec<ModuleManifest>, context: ReleaseContext) -> Self {
         Self {
             component_id,
             modules,
-            context: std::sync::Mutex::new(ReleaseContext::default()),
+            context: std::sync::Mutex::new(context),
         }
     }
 
@@ -250,6 +281,9 @@ impl ReleaseStepExecutor {
             hints,
             data,
             error,
+            error_code: None,
+            attempts: Vec::new(),
+            continue_on_error: false,
         }
     }
 
@@ -261,6 +295,8 @@ impl ReleaseStepExecutor {
             "git.commit" => self.run_git_commit(step),
             "git.tag" => self.run_git_tag(step),
             "git.push" => self.run_git_push(step),
+            "checksums" => self.run_checksums(step),
+            "changelog.lint" => self.run_changelog_lint(step),
             _ => Err(Error::validation_invalid_argument(
                 "release.steps",
                 format!("Unsupported core step '{}'", step.step_type),
@@ -319,7 +355,8 @@ impl ReleaseStepExecutor {
             .get("bump")
             .and_then(|v| v.as_str())
             .unwrap_or("patch");
-        let result = version::bump_version(Some(&self.component_id), bump_type)?;
+        let preid = step.config.get("preid").and_then(|v| v.as_str());
+        let result = version::bump_version(Some(&self.component_id), bump_type, preid)?;
         let data = serde_json::to_value(&result)
             .map_err(|e| Error::internal_json(e.to_string(), Some("version output".to_string())))?;
         self.store_version_context(&result.new_version)?;
@@ -441,6 +478,212 @@ impl ReleaseStepExecutor {
         ))
     }
 
+    /// Lint the component's changelog, failing at the configured severity.
+    fn run_changelog_lint(&self, step: &PipelineStep) -> Result<PipelineStepResult> {
+        let fail_on = lint_fail_on(&step.config)?;
+        let output = changelog::lint(&self.component_id)?;
+        let failed = output.fails(fail_on);
+        let error = failed.then(|| lint_failure_message(&output, fail_on));
+        let data = serde_json::to_value(&output)
+            .map_err(|e| Error::internal_json(e.to_string(), Some("lint output".to_string())))?;
+        let status = if failed {
+            PipelineRunStatus::Failed
+        } else {
+            PipelineRunStatus::Success
+        };
+        Ok(self.step_result(step, status, Some(data), error, Vec::new()))
+    }
+
+    /// Hash every release artifact into `SHA256SUMS` (and `SHA512SUMS`), sign
+    /// the manifest if a key is configured, and add the manifest files to the
+    /// release artifacts so publish steps upload them.
+    fn run_checksums(&self, step: &PipelineStep) -> Result<PipelineStepResult> {
+        let component = component::load(&self.component_id)?;
+        self.write_checksums(step, std::path::Path::new(&component.local_path))
+    }
+
+    /// Hash the context's artifacts, resolving relative paths against `local_path`.
+    fn write_checksums(
+        &self,
+        step: &PipelineStep,
+        local_path: &std::path::Path,
+    ) -> Result<PipelineStepResult> {
+        let resolve = |path: &str| {
+            let path = std::path::Path::new(path);
+            if path.is_absolute() {
+                path.to_path_buf()
+            } else {
+                local_path.join(path)
+            }
+        };
+
+        let sha512 = step
+            .config
+            .get("sha512")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let signing_key = match (
+            step.config.get("signingKey").and_then(|v| v.as_str()),
+            step.config.get("signingServer").and_then(|v| v.as_str()),
+        ) {
+            (Some(key), _) => Some(std::path::PathBuf::from(
+                shellexpand::tilde(key).to_string(),
+            )),
+            (None, Some(server_id)) => {
+                let server = crate::server::load(server_id)?;
+                let key = server.identity_file.ok_or_else(|| {
+                    Error::validation_invalid_argument(
+                        "signingServer",
+                        "Server has no SSH identity file to sign with",
+                        Some(server_id.to_string()),
+                        None,
+                    )
+                    .with_hint(format!(
+                        "Generate one with: homeboy server key generate {}",
+                        server_id
+                    ))
+                })?;
+                Some(std::path::PathBuf::from(
+                    shellexpand::tilde(&key).to_string(),
+                ))
+            }
+            (None, None) => None,
+        };
+        let namespace = step
+            .config
+            .get("namespace")
+            .and_then(|v| v.as_str())
+            .unwrap_or("file");
+
+        // Manifests from an earlier attempt are replaced, not hashed.
+        let artifacts: Vec<ReleaseArtifact> = {
+            let context = self.context.lock().map_err(|_| {
+                Error::internal_unexpected("Failed to lock release context".to_string())
+            })?;
+            context
+                .artifacts
+                .iter()
+                .filter(|a| !matches!(a.artifact_type.as_deref(), Some("checksums" | "signature")))
+                .cloned()
+                .collect()
+        };
+        if artifacts.is_empty() {
+            return Err(Error::validation_invalid_argument(
+                "release.steps",
+                "checksums step found no release artifacts",
+                Some(step.id.clone()),
+                None,
+            )
+            .with_hint("Add a package step before checksums and list it in needs"));
+        }
+
+        let output_dir = match step.config.get("dir").and_then(|v| v.as_str()) {
+            Some(dir) => resolve(dir),
+            None => resolve(&artifacts[0].path)
+                .parent()
+                .map(|p| p.to_path_buf())
+                .unwrap_or_else(|| local_path.to_path_buf()),
+        };
+        std::fs::create_dir_all(&output_dir).map_err(|e| {
+            Error::internal_io(
+                e.to_string(),
+                Some(format!("create {}", output_dir.display())),
+            )
+        })?;
+
+        let mut warnings = Vec::new();
+        let mut files = Vec::new();
+        let mut sha256_entries = Vec::new();
+        let mut sha512_entries = Vec::new();
+        for artifact in &artifacts {
+            let path = resolve(&artifact.path);
+            if path.is_dir() {
+                warnings.push(format!("Skipped directory artifact {}", artifact.path));
+                continue;
+            }
+            let name = path
+                .strip_prefix(&output_dir)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|_| {
+                    path.file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| artifact.path.clone())
+                });
+            let sha256 = crate::checksum::sha256_file(&path)?;
+            sha256_entries.push((name.clone(), sha256.clone()));
+            let sha512_hash = if sha512 {
+                let hash = crate::checksum::sha512_file(&path)?;
+                sha512_entries.push((name.clone(), hash.clone()));
+                Some(hash)
+            } else {
+                None
+            };
+            files.push(serde_json::json!({
+                "path": artifact.path,
+                "name": name,
+                "sha256": sha256,
+                "sha512": sha512_hash,
+            }));
+        }
+        if files.is_empty() {
+            return Err(Error::validation_invalid_argument(
+                "release.steps",
+                "checksums step found no files to hash",
+                Some(step.id.clone()),
+                Some(artifacts.iter().map(|a| a.path.clone()).collect()),
+            )
+            .with_hint("Package the release as archive files; directories are not hashed"));
+        }
+
+        let mut manifests = Vec::new();
+        let sha256_manifest = output_dir.join("SHA256SUMS");
+        crate::checksum::write_sums_file(&sha256_manifest, &sha256_entries)?;
+        manifests.push((sha256_manifest.clone(), "checksums"));
+        if sha512 {
+            let sha512_manifest = output_dir.join("SHA512SUMS");
+            crate::checksum::write_sums_file(&sha512_manifest, &sha512_entries)?;
+            manifests.push((sha512_manifest, "checksums"));
+        }
+        let signature = match &signing_key {
+            Some(key) => {
+                let signature = crate::checksum::ssh_sign(&sha256_manifest, key, namespace)?;
+                manifests.push((signature.clone(), "signature"));
+                Some(signature)
+            }
+            None => None,
+        };
+
+        {
+            let mut context = self.context.lock().map_err(|_| {
+                Error::internal_unexpected("Failed to lock release context".to_string())
+            })?;
+            context.artifacts = artifacts;
+            context
+                .artifacts
+                .extend(manifests.iter().map(|(path, kind)| ReleaseArtifact {
+                    path: path.to_string_lossy().to_string(),
+                    artifact_type: Some(kind.to_string()),
+                    platform: None,
+                }));
+        }
+
+        let data = serde_json::json!({
+            "manifest": sha256_manifest.to_string_lossy(),
+            "signature": signature.map(|p| p.to_string_lossy().to_string()),
+            "namespace": signing_key.as_ref().map(|_| namespace),
+            "files": files,
+        });
+        let mut result = self.step_result(
+            step,
+            PipelineRunStatus::Success,
+            Some(data),
+            None,
+            Vec::new(),
+        );
+        result.warnings = warnings;
+        Ok(result)
+    }
+
     fn run_git_commit(&self, step: &PipelineStep) -> Result<PipelineStepResult> {
         let status_output = crate::git::status(Some(&self.component_id))?;
         let is_clean = status_output.stdout.trim().is_empty();
@@ -603,9 +846,21 @@ impl ReleaseStepExecutor {
 
     fn load_release_notes(&self) -> Result<String> {
         let component = component::load(&self.component_id)?;
-        let changelog_path = changelog::resolve_changelog_path(&component)?;
-        let changelog_content = crate::core::local_files::local().read(&changelog_path)?;
-        let notes = extract_latest_notes(&changelog_content).ok_or_else(|| {
+        let (_, parsed) = changelog::read_parsed(&component)?;
+        let version = self
+            .context
+            .lock()
+            .map_err(|_| Error::internal_unexpected("Failed to lock release context".to_string()))?
+            .version
+            .clone();
+
+        // Prefer the section for the version being released; fall back to the
+        // latest finalized section when the version step didn't run.
+        let section = version
+            .as_deref()
+            .and_then(|v| parsed.release(v))
+            .or_else(|| parsed.latest_release());
+        let notes = section.and_then(|s| s.notes()).ok_or_else(|| {
             Error::validation_invalid_argument(
                 "changelog",
                 "No finalized changelog entries found for release notes",
@@ -755,6 +1010,57 @@ impl PipelineStepExecutor for ReleaseStepExecutor {
 
         self.run_module_action(step)
     }
+
+    fn condition_context(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut vars = serde_json::Map::new();
+        let component = component::load(&self.component_id).ok();
+
+        let (version, tag) = match self.context.lock() {
+            Ok(context) => (context.version.clone(), context.tag.clone()),
+            Err(_) => (None, None),
+        };
+        let version = version.or_else(|| {
+            version::read_version(Some(&self.component_id))
+                .ok()
+                .map(|info| info.version)
+        });
+        let prerelease = version.as_deref().map(|v| {
+            semver::Version::parse(v)
+                .map(|parsed| !parsed.pre.is_empty())
+                .unwrap_or_else(|_| v.contains('-'))
+        });
+
+        let branch = component.as_ref().and_then(|c| {
+            crate::git::execute_git_for_release(
+                &c.local_path,
+                &["rev-parse", "--abbrev-ref", "HEAD"],
+            )
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        });
+
+        let settings = component
+            .as_ref()
+            .and_then(|c| c.release.as_ref())
+            .map(|release| serde_json::json!(release.settings));
+
+        vars.insert("branch".to_string(), serde_json::json!(branch));
+        vars.insert("version".to_string(), serde_json::json!(version));
+        vars.insert("prerelease".to_string(), serde_json::json!(prerelease));
+        vars.insert("tag".to_string(), serde_json::json!(tag));
+        vars.insert(
+            "settings".to_string(),
+            settings.unwrap_or(serde_json::Value::Null),
+        );
+        vars.insert(
+            "component".to_string(),
+            component
+                .and_then(|c| serde_json::to_value(c).ok())
+                .unwrap_or(serde_json::Value::Null),
+        );
+        vars
+    }
 }
 
 fn resolve_modules(component: &Component, module_id: Option<&str>) -> Result<Vec<ModuleManifest>> {
@@ -805,43 +1111,6 @@ fn resolve_module_actions(
     Ok(matches)
 }
 
-fn extract_latest_notes(content: &str) -> Option<String> {
-    let mut in_section = false;
-    let mut buffer = Vec::new();
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with("## ") {
-            if in_section {
-                break;
-            }
-            if extract_version_from_heading(trimmed).is_some() {
-                in_section = true;
-                continue;
-            }
-        }
-
-        if in_section {
-            buffer.push(line);
-        }
-    }
-
-    let notes = buffer.join("\n").trim().to_string();
-    if notes.is_empty() {
-        None
-    } else {
-        Some(notes)
-    }
-}
-
-fn extract_version_from_heading(label: &str) -> Option<String> {
-    let semver_pattern = regex::Regex::new(r"\[?(\d+\.\d+\.\d+)\]?").ok()?;
-    semver_pattern
-        .captures(label)
-        .and_then(|caps| caps.get(1))
-        .map(|m| m.as_str().to_string())
-}
-
 fn parse_release_artifacts(value: &serde_json::Value) -> Result<Vec<ReleaseArtifact>> {
     let mut artifacts = Vec::new();
     let items = match value {
@@ -960,7 +1229,6 @@ pub fn plan(component_id: &str, module_id: Option<&str>) -> Result<ReleasePlan>
 pub fn run(component_id: &str, module_id: Option<&str>) -> Result<ReleaseRun> {
     let component = component::load(component_id)?;
     let modules = resolve_modules(&component, module_id)?;
-    let resolver = ReleaseCapabilityResolver::new(modules.clone());
     let release = resolve_component_release(&component).ok_or_else(|| {
         Error::validation_invalid_argument(
             "release",
@@ -981,23 +1249,142 @@ pub fn run(component_id: &str, module_id: Option<&str>) -> Result<ReleaseRun> {
 
     validate_preflight(&component, &release_steps)?;
 
-    let executor = ReleaseStepExecutor::new(component_id.to_string(), modules.clone());
+    let now = chrono::Utc::now().to_rfc3339();
+    let record = ReleaseRunRecord {
+        run_id: release_runs::new_run_id(component_id),
+        component_id: component_id.to_string(),
+        started_at: now.clone(),
+        updated_at: now,
+        attempts: 0,
+        steps: release_steps,
+        context: ReleaseContext::default(),
+        result: PipelineRunResult {
+            steps: Vec::new(),
+            status: PipelineRunStatus::Skipped,
+            warnings: Vec::new(),
+            summary: None,
+        },
+        in_progress: false,
+        error: None,
+    };
+
+    execute_run(record, modules, enabled)
+}
+
+/// Resume a saved release run, skipping steps that already succeeded and
+/// restoring the version, tag, notes and artifacts they produced.
+pub fn resume(run_id: &str) -> Result<ReleaseRun> {
+    let record = release_runs::load(run_id)?;
+    if record.result.status == PipelineRunStatus::Success {
+        return Err(Error::validation_invalid_argument(
+            "run_id",
+            "Release run already completed successfully",
+            Some(run_id.to_string()),
+            None,
+        ));
+    }
+
+    let component = component::load(&record.component_id)?;
+    let modules = resolve_modules(&component, None)?;
+    let enabled = resolve_component_release(&component)
+        .and_then(|release| release.enabled)
+        .unwrap_or(true);
+
+    validate_preflight(&component, &record.steps)?;
+
+    execute_run(record, modules, enabled)
+}
+
+/// Run (or resume) the pipeline for a run record.
+///
+/// The record is saved before the pipeline starts, after each batch of steps
+/// and when it finishes or errors, so an interrupted run can still be resumed.
+fn execute_run(
+    mut record: ReleaseRunRecord,
+    modules: Vec<ModuleManifest>,
+    enabled: bool,
+) -> Result<ReleaseRun> {
+    let resolver = ReleaseCapabilityResolver::new(modules.clone());
+    let executor = std::sync::Arc::new(ReleaseStepExecutor::new(
+        record.component_id.clone(),
+        modules,
+        record.context.clone(),
+    ));
+
+    let pipeline_steps: Vec<PipelineStep> = record
+        .steps
+        .iter()
+        .cloned()
+        .map(PipelineStep::from)
+        .collect();
 
-    let pipeline_steps: Vec<PipelineStep> =
-        release_steps.into_iter().map(PipelineStep::from).collect();
+    record.attempts += 1;
+    record.in_progress = true;
+    record.error = None;
+    record.updated_at = chrono::Utc::now().to_rfc3339();
+    release_runs::save(&record)?;
 
-    let run_result = pipeline::run(
+    let completed = record.result.steps.clone();
+    let outcome = pipeline::resume_with_progress(
         &pipeline_steps,
-        std::sync::Arc::new(executor),
+        &completed,
+        executor.clone(),
         std::sync::Arc::new(resolver),
         enabled,
         "release.steps",
-    )?;
+        &mut |progress| {
+            if let Ok(context) = executor.context.lock() {
+                record.context = context.clone();
+            }
+            record.result = progress.clone();
+            record.updated_at = chrono::Utc::now().to_rfc3339();
+            // Best effort: the final save below reports persistence errors
+            let _ = release_runs::save(&record);
+        },
+    );
+
+    if let Ok(context) = executor.context.lock() {
+        record.context = context.clone();
+    }
+    record.in_progress = false;
+    record.updated_at = chrono::Utc::now().to_rfc3339();
+
+    let mut run_result = match outcome {
+        Ok(result) => result,
+        Err(err) => {
+            record.result.status = PipelineRunStatus::Failed;
+            let problem = err.details.get("problem").and_then(|p| p.as_str());
+            record.error = Some(problem.unwrap_or(&err.message).to_string());
+            release_runs::save(&record)?;
+            return Err(err.with_hint(format!(
+                "Resume with: homeboy release resume {}",
+                record.run_id
+            )));
+        }
+    };
+
+    if matches!(
+        run_result.status,
+        PipelineRunStatus::Failed | PipelineRunStatus::PartialSuccess
+    ) {
+        if let Some(summary) = run_result.summary.as_mut() {
+            summary.next_actions = vec![format!(
+                "Fix the issue and resume with: homeboy release resume {} \
+                 (steps that succeeded are not re-run)",
+                record.run_id
+            )];
+        }
+    }
+
+    record.result = run_result;
+    release_runs::save(&record)?;
 
     Ok(ReleaseRun {
-        component_id: component_id.to_string(),
+        run_id: record.run_id,
+        component_id: record.component_id,
         enabled,
-        result: run_result,
+        attempts: record.attempts,
+        result: record.result,
     })
 }
 
@@ -1018,31 +1405,70 @@ fn validate_preflight(component: &Component, steps: &[ReleaseStep]) -> Result<()
         ));
     }
 
+    // Lint the changelog up front when the pipeline has a changelog.lint step
+    if let Some(step) = steps.iter().find(|s| s.step_type == "changelog.lint") {
+        let fail_on = lint_fail_on(&step.config)?;
+        let output = crate::changelog::lint(&component.id)?;
+        if output.fails(fail_on) {
+            let findings = output
+                .findings
+                .iter()
+                .filter(|f| {
+                    fail_on == crate::changelog::LintSeverity::Warning || f.severity == fail_on
+                })
+                .map(|f| match f.line {
+                    Some(line) => format!("line {}: {}", line, f.message),
+                    None => f.message.clone(),
+                })
+                .collect();
+            return Err(Error::validation_invalid_argument(
+                "changelog",
+                lint_failure_message(&output, fail_on),
+                Some(component.id.clone()),
+                Some(findings),
+            )
+            .with_hint(format!(
+                "Run `homeboy changelog lint {}` for details and fix hints",
+                component.id
+            )));
+        }
+    }
+
+    // Dependencies must be deployed at satisfying versions in every project
+    // that ships this component
+    if component
+        .depends_on
+        .as_ref()
+        .is_some_and(|deps| !deps.is_empty())
+    {
+        let projects = crate::project::list().map_err(|err| {
+            err.with_hint("Projects are listed to check this component's dependsOn ranges")
+        })?;
+        check_release_constraints(component, projects, crate::deploy::deployed_versions)?;
+    }
+
     // Validate changelog has no unreleased section with content
-    if let Ok(changelog_path) = crate::changelog::resolve_changelog_path(component) {
-        let changelog_content = crate::core::local_files::local().read(&changelog_path);
-        if let Ok(content) = changelog_content {
-            let settings = crate::changelog::resolve_effective_settings(Some(component));
-            if let Some(status) = crate::changelog::check_next_section_content(
-                &content,
-                &settings.next_section_aliases,
-            )? {
-                match status.as_str() {
-                    "empty" => {
-                        // Empty unreleased section is fine - no content to release
-                    }
-                    "subsection_headers_only" | _ => {
-                        // Has unreleased content - should be finalized before release
-                        return Err(Error::validation_invalid_argument(
-                            "changelog",
-                            "Changelog has unreleased section with content. Finalize changelog before releasing.",
-                            None,
-                            Some(vec![
-                                "Run `homeboy version bump <component>` to finalize and increment version".to_string(),
-                                "Or run `homeboy changelog add <component> -m \"...\"` to add more items".to_string(),
-                            ]),
-                        ));
-                    }
+    if let Ok(changelog_file) = crate::changelog::read_changelog(component) {
+        let content = changelog_file.markdown();
+        let settings = crate::changelog::resolve_effective_settings(Some(component));
+        if let Some(status) =
+            crate::changelog::check_next_section_content(&content, &settings.next_section_aliases)?
+        {
+            match status.as_str() {
+                "empty" => {
+                    // Empty unreleased section is fine - no content to release
+                }
+                "subsection_headers_only" | _ => {
+                    // Has unreleased content - should be finalized before release
+                    return Err(Error::validation_invalid_argument(
+                        "changelog",
+                        "Changelog has unreleased section with content. Finalize changelog before releasing.",
+                        None,
+                        Some(vec![
+                            "Run `homeboy version bump <component>` to finalize and increment version".to_string(),
+                            "Or run `homeboy changelog add <component> -m \"...\"` to add more items".to_string(),
+                        ]),
+                    ));
                 }
             }
         }
@@ -1054,10 +1480,99 @@ fn validate_preflight(component: &Component, steps: &[ReleaseStep]) -> Result<()
 fn is_core_step(step_type: &str) -> bool {
     matches!(
         step_type,
-        "build" | "changelog" | "version" | "git.commit" | "git.tag" | "git.push" | "changes"
+        "build"
+            | "changelog"
+            | "version"
+            | "git.commit"
+            | "git.tag"
+            | "git.push"
+            | "changes"
+            | "checksums"
+            | "changelog.lint"
     )
 }
 
+/// Check the component's `dependsOn` ranges against the versions deployed in
+/// every project that ships it. A project whose deployed versions cannot be
+/// read is reported by ID instead of aborting the check.
+fn check_release_constraints(
+    component: &Component,
+    projects: Vec<crate::project::Project>,
+    deployed_versions: impl Fn(&str, &[String]) -> Result<HashMap<String, String>>,
+) -> Result<()> {
+    use crate::version_constraints::{self, ProjectConstraintReport};
+
+    let required: Vec<String> = component
+        .depends_on
+        .iter()
+        .flatten()
+        .map(|dep| dep.component.clone())
+        .collect();
+    let reports: Vec<ProjectConstraintReport> = projects
+        .into_iter()
+        .filter(|p| p.component_ids.contains(&component.id))
+        .map(|project| {
+            let present: Vec<String> = required
+                .iter()
+                .filter(|id| project.component_ids.contains(id))
+                .cloned()
+                .collect();
+            match deployed_versions(&project.id, &present) {
+                Ok(versions) => {
+                    let checks = version_constraints::check_component(
+                        component,
+                        &project.component_ids,
+                        &versions,
+                    );
+                    ProjectConstraintReport {
+                        passed: checks.iter().all(|check| check.satisfied),
+                        project_id: project.id,
+                        checks,
+                        error: None,
+                    }
+                }
+                Err(err) => ProjectConstraintReport {
+                    project_id: project.id,
+                    checks: Vec::new(),
+                    passed: false,
+                    error: Some(crate::deploy::error_text(&err)),
+                },
+            }
+        })
+        .collect();
+
+    version_constraints::ensure_projects_satisfied(&reports, &format!("release {}", component.id))
+}
+
+/// `failOn` for `changelog.lint` steps: `error` (default) or `warning`.
+fn lint_fail_on(config: &HashMap<String, serde_json::Value>) -> Result<changelog::LintSeverity> {
+    match config.get("failOn") {
+        None => Ok(changelog::LintSeverity::Error),
+        Some(value) => serde_json::from_value(value.clone()).map_err(|_| {
+            Error::validation_invalid_argument(
+                "failOn",
+                format!("Invalid changelog.lint failOn value {}", value),
+                None,
+                Some(vec!["error".to_string(), "warning".to_string()]),
+            )
+        }),
+    }
+}
+
+fn lint_failure_message(
+    output: &changelog::LintOutput,
+    fail_on: changelog::LintSeverity,
+) -> String {
+    let counted = match fail_on {
+        changelog::LintSeverity::Error => format!("{} error(s)", output.errors),
+        changelog::LintSeverity::Warning => format!(
+            "{} error(s) and {} warning(s)",
+            output.errors, output.warnings
+        ),
+    };
+    format!("Changelog lint found {}", counted)
+}
+
 fn auto_insert_commit_step(steps: Vec<ReleaseStep>) -> (Vec<ReleaseStep>, bool) {
     let has_tag = steps.iter().any(|s| s.step_type == "git.tag");
     let has_commit = steps.iter().any(|s| s.step_type == "git.commit");
@@ -1076,6 +1591,10 @@ fn auto_insert_commit_step(steps: Vec<ReleaseStep>) -> (Vec<ReleaseStep>, bool)
                 step_type: "git.commit".to_string(),
                 label: Some("Commit release changes".to_string()),
                 needs: step.needs.clone(),
+                when: None,
+                retry: None,
+                timeout_seconds: None,
+                continue_on_error: false,
                 config: HashMap::new(),
             };
             result.push(commit_step);
@@ -1127,3 +1646,135 @@ fn build_plan_hints(
 
     hints
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checksums_step(config: serde_json::Value) -> PipelineStep {
+        serde_json::from_value(serde_json::json!({
+            "id": "checksums",
+            "type": "checksums",
+            "config": config,
+        }))
+        .unwrap()
+    }
+
+    fn executor(artifacts: &[&str]) -> ReleaseStepExecutor {
+        let context = ReleaseContext {
+            artifacts: artifacts
+                .iter()
+                .map(|path| ReleaseArtifact {
+                    path: path.to_string(),
+                    artifact_type: Some("zip".to_string()),
+                    platform: None,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        ReleaseStepExecutor::new("plugin".to_string(), Vec::new(), context)
+    }
+
+    #[test]
+    fn release_constraints_report_each_consuming_project() {
+        let mut theme = Component::new(
+            "theme".to_string(),
+            String::new(),
+            "theme".to_string(),
+            String::new(),
+        );
+        theme.depends_on = Some(vec![crate::version_constraints::ComponentDependency {
+            component: "plugin".to_string(),
+            range: ">=2.0".to_string(),
+        }]);
+        let project = |id: &str, components: &[&str]| crate::project::Project {
+            id: id.to_string(),
+            component_ids: components.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        };
+        let projects = vec![
+            project("staging", &["theme", "plugin"]),
+            project("prod", &["theme", "plugin"]),
+            project("broken", &["theme", "plugin"]),
+            project("other", &["plugin"]),
+        ];
+
+        let err = check_release_constraints(&theme, projects, |project_id, present| {
+            assert_eq!(present, ["plugin".to_string()]);
+            match project_id {
+                "staging" => Ok(HashMap::from([("plugin".to_string(), "2.1.0".to_string())])),
+                "prod" => Ok(HashMap::from([("plugin".to_string(), "1.9.0".to_string())])),
+                "broken" => Err(Error::other("ssh: connect to host failed".to_string())),
+                _ => panic!("{} does not ship theme", project_id),
+            }
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            err.details["problem"],
+            "Refusing to release theme: 2 dependency constraint(s) not satisfied"
+        );
+        assert_eq!(
+            err.details["tried"],
+            serde_json::json!([
+                "project prod: theme requires plugin >=2.0: plugin 1.9.0 does not satisfy >=2.0",
+                "project broken: could not be checked: ssh: connect to host failed",
+            ])
+        );
+    }
+
+    #[test]
+    fn checksums_step_writes_manifest_and_records_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("dist/plugin")).unwrap();
+        std::fs::write(dir.path().join("dist/plugin.zip"), "abc").unwrap();
+        let executor = executor(&["dist/plugin.zip", "dist/plugin"]);
+
+        let result = executor
+            .write_checksums(&checksums_step(serde_json::json!({})), dir.path())
+            .unwrap();
+
+        assert_eq!(result.status, PipelineRunStatus::Success);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("dist/SHA256SUMS")).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  plugin.zip\n"
+        );
+        assert_eq!(
+            result.warnings,
+            vec!["Skipped directory artifact dist/plugin"]
+        );
+
+        let context = executor.context.lock().unwrap();
+        let artifacts: Vec<(&str, Option<&str>)> = context
+            .artifacts
+            .iter()
+            .map(|a| (a.path.as_str(), a.artifact_type.as_deref()))
+            .collect();
+        let manifest = dir.path().join("dist/SHA256SUMS");
+        assert_eq!(
+            artifacts,
+            vec![
+                ("dist/plugin.zip", Some("zip")),
+                ("dist/plugin", Some("zip")),
+                (manifest.to_str().unwrap(), Some("checksums")),
+            ]
+        );
+    }
+
+    #[test]
+    fn checksums_step_fails_when_no_file_is_hashed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("dist/plugin")).unwrap();
+        let executor = executor(&["dist/plugin"]);
+
+        let err = executor
+            .write_checksums(&checksums_step(serde_json::json!({})), dir.path())
+            .unwrap_err();
+
+        assert_eq!(
+            err.details["problem"],
+            "checksums step found no files to hash"
+        );
+        assert!(!dir.path().join("dist/SHA256SUMS").exists());
+    }
+}
@@ -0,0 +1,152 @@
+//! Persisted release run state.
+//!
+//! Every `release run` is saved under a run ID with its step results and the
+//! release context (version, tag, notes, artifacts), so a failed run can be
+//! resumed without repeating steps that already succeeded.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::error::{Error, Result};
+use crate::paths;
+use crate::pipeline::{PipelineRunResult, PipelineRunStatus};
+use crate::release::{ReleaseContext, ReleaseStep};
+
+/// Saved state of one release run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub struct ReleaseRunRecord {
+    pub run_id: String,
+    pub component_id: String,
+    pub started_at: String,
+    pub updated_at: String,
+    /// Number of times the run has been executed (1 + resumes)
+    pub attempts: u32,
+    /// Steps as configured when the run started; resumes replay these
+    pub steps: Vec<ReleaseStep>,
+    pub context: ReleaseContext,
+    pub result: PipelineRunResult,
+    /// Set while the pipeline is executing; still set if the run was interrupted
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub in_progress: bool,
+    /// Error that stopped the pipeline before it produced a result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// One line of `release runs` output.
+#[derive(Debug, Clone, Serialize)]
+
+pub struct ReleaseRunSummary {
+    pub run_id: String,
+    pub started_at: String,
+    pub updated_at: String,
+    pub attempts: u32,
+    pub status: PipelineRunStatus,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub in_progress: bool,
+    pub version: Option<String>,
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed_steps: Vec<String>,
+}
+
+impl From<&ReleaseRunRecord> for ReleaseRunSummary {
+    fn from(record: &ReleaseRunRecord) -> Self {
+        Self {
+            run_id: record.run_id.clone(),
+            started_at: record.started_at.clone(),
+            updated_at: record.updated_at.clone(),
+            attempts: record.attempts,
+            status: record.result.status.clone(),
+            in_progress: record.in_progress,
+            version: record.context.version.clone(),
+            tag: record.context.tag.clone(),
+            failed_steps: record
+                .result
+                .steps
+                .iter()
+                .filter(|s| s.status == PipelineRunStatus::Failed)
+                .map(|s| s.id.clone())
+                .collect(),
+        }
+    }
+}
+
+/// New sortable run ID: `<component>-<utc timestamp>-<random suffix>`.
+pub fn new_run_id(component_id: &str) -> String {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    format!(
+        "{}-{}-{}",
+        component_id,
+        chrono::Utc::now().format("%Y%m%d%H%M%S"),
+        &suffix[..6]
+    )
+}
+
+pub fn save(record: &ReleaseRunRecord) -> Result<()> {
+    let path = paths::release_run_file(&record.run_id)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            Error::internal_io(e.to_string(), Some(format!("create {}", parent.display())))
+        })?;
+    }
+
+    let content = serde_json::to_string_pretty(record)
+        .map_err(|e| Error::internal_json(e.to_string(), Some("serialize release run".into())))?;
+    fs::write(&path, content)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("write {}", path.display()))))
+}
+
+pub fn load(run_id: &str) -> Result<ReleaseRunRecord> {
+    let not_found = || {
+        Error::validation_invalid_argument(
+            "run_id",
+            "Release run not found",
+            Some(run_id.to_string()),
+            None,
+        )
+        .with_hint("List runs with: homeboy release runs <component>")
+    };
+
+    if run_id.is_empty() || run_id.contains(['/', '\\']) || run_id.starts_with('.') {
+        return Err(not_found());
+    }
+
+    let path = paths::release_run_file(run_id)?;
+    if !path.exists() {
+        return Err(not_found());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("read {}", path.display()))))?;
+    serde_json::from_str(&content)
+        .map_err(|e| Error::internal_json(e.to_string(), Some(format!("parse {}", path.display()))))
+}
+
+/// Saved runs for a component, newest first.
+pub fn list(component_id: &str, limit: Option<usize>) -> Result<Vec<ReleaseRunRecord>> {
+    let dir = paths::release_runs()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| Error::internal_io(e.to_string(), Some(format!("read {}", dir.display()))))?;
+
+    let prefix = format!("{}-", component_id);
+    let mut records: Vec<ReleaseRunRecord> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .filter_map(|content| serde_json::from_str::<ReleaseRunRecord>(&content).ok())
+        .filter(|record| record.component_id == component_id)
+        .collect();
+
+    records.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    if let Some(limit) = limit {
+        records.truncate(limit);
+    }
+
+    Ok(records)
+}
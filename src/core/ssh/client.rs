@@ -1,3 +1,4 @@
+use crate::cancel;
 use crate::error::{Error, RemoteCommandFailedDetails, Result, TargetDetails};
 use crate::server::Server;
 use crate::shell;
@@ -110,7 +111,11 @@ impl SshClient {
             }
         }
 
-        let output = cmd.output();
+        let output = if stdin_file.is_some() {
+            cancel::output_with_stdin(&mut cmd)
+        } else {
+            cancel::output(&mut cmd)
+        };
 
         match output {
             Ok(out) => CommandOutput {
@@ -131,12 +136,13 @@ impl SshClient {
     pub fn execute_interactive(&self, command: Option<&str>) -> i32 {
         let args = self.build_ssh_args(command);
 
-        let status = Command::new("ssh")
-            .args(&args)
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status();
+        let status = cancel::status(
+            Command::new("ssh")
+                .args(&args)
+                .stdin(Stdio::inherit())
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit()),
+        );
 
         match status {
             Ok(s) => s.code().unwrap_or(-1),
@@ -176,7 +182,7 @@ pub fn execute_local_command_in_dir(
         cmd.envs(env_pairs.iter().copied());
     }
 
-    match cmd.output() {
+    match cancel::output(&mut cmd) {
         Ok(out) => CommandOutput {
             stdout: String::from_utf8_lossy(&out.stdout).to_string(),
             stderr: String::from_utf8_lossy(&out.stderr).to_string(),
@@ -219,11 +225,11 @@ pub fn execute_local_command_interactive(
         cmd.envs(env_pairs.iter().copied());
     }
 
-    let status = cmd
-        .stdin(Stdio::inherit())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .status();
+    let status = cancel::status(
+        cmd.stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit()),
+    );
 
     match status {
         Ok(s) => s.code().unwrap_or(-1),
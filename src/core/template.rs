@@ -12,6 +12,8 @@ impl TemplateVars {
     pub const QUERY: &'static str = "query";
     pub const FORMAT: &'static str = "format";
     pub const TARGET_DIR: &'static str = "targetDir";
+    pub const COMPONENT_ID: &'static str = "componentId";
+    pub const VERSION: &'static str = "version";
     pub const DB_HOST: &'static str = "db_host";
     pub const DB_PORT: &'static str = "db_port";
     pub const DB_NAME: &'static str = "db_name";
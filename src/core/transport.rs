@@ -0,0 +1,111 @@
+//! Where deploy commands run.
+//!
+//! Projects with a `server_id` deploy over SSH. Projects without one deploy to
+//! the local filesystem using the same commands, run from the home directory so
+//! relative paths resolve the way they do in an SSH session.
+
+use std::path::{Path, PathBuf};
+
+use crate::context::resolve_project_ssh;
+use crate::error::{Error, Result, TargetDetails};
+use crate::project::Project;
+use crate::ssh::{execute_local_command_in_dir, CommandOutput, SshClient};
+
+/// Command execution target for a deploy.
+pub enum Transport {
+    Ssh(SshClient),
+    Local { home: PathBuf },
+}
+
+impl Transport {
+    /// SSH when the project has a server configured, local otherwise.
+    pub fn for_project(project: &Project) -> Result<Self> {
+        if project.server_id.as_ref().is_none_or(|s| s.is_empty()) {
+            return Self::local();
+        }
+        Ok(Self::Ssh(resolve_project_ssh(&project.id)?.client))
+    }
+
+    pub fn local() -> Result<Self> {
+        let home = dirs::home_dir().ok_or_else(|| {
+            Error::internal_io(
+                "Could not determine home directory".to_string(),
+                Some("resolve local deploy target".to_string()),
+            )
+        })?;
+        Ok(Self::Local { home })
+    }
+
+    /// Run a shell command on the target.
+    pub fn execute(&self, command: &str) -> CommandOutput {
+        match self {
+            Self::Ssh(client) => client.execute(command),
+            Self::Local { home } => execute_local_command_in_dir(command, home.to_str(), None),
+        }
+    }
+
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Local { .. })
+    }
+
+    /// `user@host` for SSH targets, `local` otherwise.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Ssh(client) => format!("{}@{}", client.user, client.host),
+            Self::Local { .. } => "local".to_string(),
+        }
+    }
+
+    /// Path on the target as seen from this machine: `user@host:path` for SSH,
+    /// or the path resolved against the home directory for local targets.
+    pub fn destination(&self, path: &str) -> String {
+        match self {
+            Self::Ssh(client) => format!("{}@{}:{}", client.user, client.host, path),
+            Self::Local { home } => resolve_local(home, path),
+        }
+    }
+
+    /// Error target details for failed commands.
+    pub fn target_details(&self) -> TargetDetails {
+        TargetDetails {
+            project_id: None,
+            server_id: None,
+            host: match self {
+                Self::Ssh(client) => Some(client.host.clone()),
+                Self::Local { .. } => None,
+            },
+        }
+    }
+}
+
+fn resolve_local(home: &Path, path: &str) -> String {
+    let expanded = shellexpand::tilde(path).to_string();
+    if Path::new(&expanded).is_absolute() {
+        expanded
+    } else {
+        home.join(expanded).to_string_lossy().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_commands_run_from_home() {
+        let home = tempfile::tempdir().unwrap();
+        let transport = Transport::Local {
+            home: home.path().to_path_buf(),
+        };
+
+        let output = transport.execute("mkdir -p site && pwd");
+
+        assert!(output.success);
+        assert!(home.path().join("site").is_dir());
+        assert_eq!(
+            transport.destination("site"),
+            home.path().join("site").to_string_lossy()
+        );
+        assert_eq!(transport.destination("/srv/site"), "/srv/site");
+    }
+}
@@ -195,22 +195,9 @@ pub fn check_for_updates() -> Result<VersionCheck> {
 }
 
 fn version_is_newer(latest: &str, current: &str) -> bool {
-    let parse = |v: &str| -> Option<(u32, u32, u32)> {
-        let parts: Vec<&str> = v.split('.').collect();
-        if parts.len() >= 3 {
-            Some((
-                parts[0].parse().ok()?,
-                parts[1].parse().ok()?,
-                parts[2].parse().ok()?,
-            ))
-        } else {
-            None
-        }
-    };
-
-    match (parse(latest), parse(current)) {
-        (Some(l), Some(c)) => l > c,
-        _ => latest != current,
+    match crate::version::compare_versions(latest, current) {
+        Some(ordering) => ordering == std::cmp::Ordering::Greater,
+        None => latest != current,
     }
 }
 
@@ -370,6 +357,10 @@ mod tests {
         assert!(version_is_newer("0.11.1", "0.11.0"));
         assert!(!version_is_newer("0.11.0", "0.11.0"));
         assert!(!version_is_newer("0.10.0", "0.11.0"));
+        assert!(version_is_newer("1.0.0", "1.0.0-rc.1"));
+        assert!(version_is_newer("1.0.0-rc.2", "1.0.0-rc.1"));
+        assert!(!version_is_newer("1.0.0-beta.1", "1.0.0"));
+        assert!(version_is_newer("v0.12.0", "0.11.0"));
     }
 
     #[test]
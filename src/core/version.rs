@@ -1,14 +1,14 @@
 use crate::changelog;
 use crate::component::{self, Component, VersionTarget};
-use crate::config::{from_str, set_json_pointer, to_string_pretty};
 use crate::defaults;
 use crate::error::{Error, Result};
+use crate::git::{CommitCategory, CommitInfo};
 use crate::local_files::{self, FileSystem};
 use crate::module::{load_all_modules, ModuleManifest};
 use crate::ssh::execute_local_command_in_dir;
+use crate::version_locator::VersionLocator;
 use regex::Regex;
-use serde::Serialize;
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 use std::fs;
 use std::path::Path;
@@ -98,76 +98,347 @@ fn find_version_pattern_in_module(module: &ModuleManifest, filename: &str) -> Op
     None
 }
 
+/// Regex (without capture group) matching a semantic version with optional
+/// prerelease and build metadata: `1.2.3`, `1.2.3-beta.1`, `1.2.3+build.5`.
+pub const VERSION_REGEX: &str = r"\d+\.\d+\.\d+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?";
+
+/// How a component's version is incremented.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionScheme {
+    /// Semantic versioning: `MAJOR.MINOR.PATCH[-PRERELEASE]`
+    #[default]
+    Semver,
+    /// Calendar versioning: `YYYY.MM.MICRO` (month not zero-padded)
+    Calver,
+}
+
 /// Increment semver version.
-/// bump_type: "patch", "minor", or "major"
+/// bump_type: "patch", "minor", "major", "premajor", "preminor", "prepatch",
+/// "prerelease" or "release"
 pub fn increment_version(version: &str, bump_type: &str) -> Option<String> {
-    let parts: Vec<&str> = version.split('.').collect();
-    if parts.len() != 3 {
-        return None;
-    }
+    increment_semver(version, bump_type, None).ok()
+}
 
-    let major: u32 = parts[0].parse().ok()?;
-    let minor: u32 = parts[1].parse().ok()?;
-    let patch: u32 = parts[2].parse().ok()?;
+/// Next version for a bump under the given scheme.
+///
+/// `preid` names the prerelease for `pre*` bumps (`beta` gives `1.3.0-beta.0`).
+/// CalVer ignores the bump type: the next version is `<year>.<month>.0` in a
+/// new month, otherwise MICRO is incremented.
+pub fn next_version(
+    version: &str,
+    bump_type: &str,
+    preid: Option<&str>,
+    scheme: VersionScheme,
+) -> Result<String> {
+    match scheme {
+        VersionScheme::Semver => increment_semver(version, bump_type, preid),
+        VersionScheme::Calver => {
+            if bump_type.starts_with("pre") || bump_type == "release" {
+                return Err(Error::validation_invalid_argument(
+                    "bump_type",
+                    format!("'{}' bumps are not supported with calver", bump_type),
+                    Some(version.to_string()),
+                    None,
+                )
+                .with_hint("Use 'homeboy version set' for calver prereleases"));
+            }
+            increment_calver(version, chrono::Local::now().date_naive())
+        }
+    }
+}
 
-    let (new_major, new_minor, new_patch) = match bump_type {
-        "patch" => (major, minor, patch + 1),
-        "minor" => (major, minor + 1, 0),
-        "major" => (major + 1, 0, 0),
-        _ => return None,
+fn increment_semver(version: &str, bump_type: &str, preid: Option<&str>) -> Result<String> {
+    let invalid_version = || {
+        Error::validation_invalid_argument(
+            "version",
+            format!("Invalid version format: {}", version),
+            None,
+            Some(vec![version.to_string()]),
+        )
     };
+    let mut v = semver::Version::parse(version.trim()).map_err(|_| invalid_version())?;
+    let is_prerelease = !v.pre.is_empty();
+    let first_pre = || prerelease(preid.map(|id| format!("{}.0", id)).unwrap_or("0".into()));
+
+    v.build = semver::BuildMetadata::EMPTY;
+    match bump_type {
+        // Bumping a prerelease to its own release line finishes it:
+        // 2.0.0-rc.1 -> 2.0.0 (major), 1.3.0-beta.2 -> 1.3.0 (minor).
+        "major" => {
+            if !(is_prerelease && v.minor == 0 && v.patch == 0) {
+                v = semver::Version::new(v.major + 1, 0, 0);
+            }
+            v.pre = semver::Prerelease::EMPTY;
+        }
+        "minor" => {
+            if !(is_prerelease && v.patch == 0) {
+                v = semver::Version::new(v.major, v.minor + 1, 0);
+            }
+            v.pre = semver::Prerelease::EMPTY;
+        }
+        "patch" => {
+            if !is_prerelease {
+                v.patch += 1;
+            }
+            v.pre = semver::Prerelease::EMPTY;
+        }
+        "premajor" => {
+            v = semver::Version::new(v.major + 1, 0, 0);
+            v.pre = first_pre()?;
+        }
+        "preminor" => {
+            v = semver::Version::new(v.major, v.minor + 1, 0);
+            v.pre = first_pre()?;
+        }
+        "prepatch" => {
+            v = semver::Version::new(v.major, v.minor, v.patch + 1);
+            v.pre = first_pre()?;
+        }
+        "prerelease" => {
+            if is_prerelease {
+                v.pre = prerelease(next_prerelease(v.pre.as_str(), preid))?;
+            } else {
+                v.patch += 1;
+                v.pre = first_pre()?;
+            }
+        }
+        "release" => {
+            if !is_prerelease {
+                return Err(Error::validation_invalid_argument(
+                    "bump_type",
+                    format!("{} is not a prerelease", version),
+                    Some(version.to_string()),
+                    None,
+                ));
+            }
+            v.pre = semver::Prerelease::EMPTY;
+        }
+        _ => {
+            return Err(Error::validation_invalid_argument(
+                "bump_type",
+                format!("Unknown bump type '{}'", bump_type),
+                None,
+                Some(
+                    [
+                        "patch",
+                        "minor",
+                        "major",
+                        "prepatch",
+                        "preminor",
+                        "premajor",
+                        "prerelease",
+                        "release",
+                    ]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+                ),
+            ))
+        }
+    }
 
-    Some(format!("{}.{}.{}", new_major, new_minor, new_patch))
+    Ok(v.to_string())
 }
 
-/// Update version in a file, handling both JSON and text-based version files.
-/// Returns the number of replacements made.
-pub fn update_version_in_file(
-    path: &str,
-    pattern: &str,
-    old_version: &str,
-    new_version: &str,
-) -> Result<usize> {
-    // JSON files with default pattern use structured update
-    if Path::new(path).extension().is_some_and(|ext| ext == "json")
-        && default_pattern_for_file(path).as_deref() == Some(pattern)
-    {
-        let content = local_files::local().read(Path::new(path))?;
-        let mut json: Value = from_str(&content)?;
-        let Some(current) = json.get("version").and_then(|v: &Value| v.as_str()) else {
-            return Err(Error::config_missing_key("version", Some(path.to_string())));
-        };
+fn prerelease(value: String) -> Result<semver::Prerelease> {
+    semver::Prerelease::new(&value).map_err(|e| {
+        Error::validation_invalid_argument(
+            "preid",
+            format!("Invalid prerelease identifier '{}': {}", value, e),
+            None,
+            None,
+        )
+    })
+}
 
-        if current != old_version {
-            return Err(Error::internal_unexpected(format!(
-                "Version mismatch in {}: found {}, expected {}",
-                path, current, old_version
-            )));
+/// `beta.1` -> `beta.2`; `beta` -> `beta.0`; switching identifier
+/// (`alpha.3` with preid `beta`) restarts at `beta.0`.
+fn next_prerelease(current: &str, preid: Option<&str>) -> String {
+    if let Some(id) = preid {
+        if current != id && !current.starts_with(&format!("{}.", id)) {
+            return format!("{}.0", id);
         }
+    }
 
-        set_json_pointer(
-            &mut json,
-            "/version",
-            serde_json::Value::String(new_version.to_string()),
-        )?;
-        let output = to_string_pretty(&json)?;
-        local_files::local().write(Path::new(path), &output)?;
-        return Ok(1);
+    let mut parts: Vec<String> = current.split('.').map(String::from).collect();
+    match parts.last().and_then(|last| last.parse::<u64>().ok()) {
+        Some(n) => {
+            let last = parts.len() - 1;
+            parts[last] = (n + 1).to_string();
+        }
+        None => parts.push("0".to_string()),
     }
+    parts.join(".")
+}
 
-    // Text files use regex replacement
-    let content = fs::read_to_string(path)
-        .map_err(|e| Error::internal_io(e.to_string(), Some("read version file".to_string())))?;
+fn increment_calver(version: &str, today: chrono::NaiveDate) -> Result<String> {
+    use chrono::Datelike;
+
+    let (year, month) = (today.year() as u64, today.month() as u64);
+    let current = parse_calver(version);
+    // Keep a zero-padded month (`2026.03.4`) padded.
+    let padded = current.is_some_and(|c| c.padded_month);
+    let next = match current {
+        // Same (or a later) month: next release in the month. Never go backwards.
+        Some(c) if (c.year, c.month) >= (year, month) => {
+            format_calver(c.year, c.month, c.micro + 1, padded)
+        }
+        _ => format_calver(year, month, 0, padded),
+    };
+    Ok(next)
+}
+
+#[derive(Clone, Copy)]
+struct Calver {
+    year: u64,
+    month: u64,
+    micro: u64,
+    padded_month: bool,
+}
+
+/// Parse `YYYY.MM.MICRO`, accepting a zero-padded month or micro.
+fn parse_calver(version: &str) -> Option<Calver> {
+    let parts: Vec<&str> = version.trim().split('.').collect();
+    let [year, month, micro] = parts.as_slice() else {
+        return None;
+    };
+    let number = |part: &str| {
+        (!part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| part.parse::<u64>().ok())
+            .flatten()
+    };
+    let calver = Calver {
+        year: number(year)?,
+        month: number(month)?,
+        micro: number(micro)?,
+        padded_month: month.len() == 2 && month.starts_with('0'),
+    };
+    (calver.year >= 1000 && (1..=12).contains(&calver.month)).then_some(calver)
+}
+
+fn format_calver(year: u64, month: u64, micro: u64, padded_month: bool) -> String {
+    if padded_month {
+        format!("{}.{:02}.{}", year, month, micro)
+    } else {
+        format!("{}.{}.{}", year, month, micro)
+    }
+}
+
+/// Resolve `auto` to a concrete bump type from the conventional commits since
+/// the latest tag. Other bump types are returned unchanged.
+///
+/// With a `preid`, the inferred bump becomes a prerelease bump
+/// (`minor` -> `preminor`), or `prerelease` if the version already is one.
+pub fn resolve_bump_type(
+    component: &Component,
+    current_version: &str,
+    bump_type: &str,
+    preid: Option<&str>,
+) -> Result<(String, Option<AutoBump>)> {
+    if bump_type != "auto" {
+        return Ok((bump_type.to_string(), None));
+    }
 
-    let versions = parse_versions(&content, pattern).ok_or_else(|| {
+    let since_tag = crate::git::get_latest_tag(&component.local_path)?;
+    let commits = crate::git::get_commits_since_tag(&component.local_path, since_tag.as_deref())?;
+    let breaking_as_minor = component.pre_1_0_breaking_minor.unwrap_or(false)
+        && parse_semver(current_version).is_some_and(|v| v.major == 0);
+
+    let (inferred, deciding) = infer_bump(&commits, breaking_as_minor).ok_or_else(|| {
         Error::validation_invalid_argument(
-            "versionPattern",
-            format!("Invalid version regex pattern '{}'", pattern),
+            "bump_type",
+            format!(
+                "No commits since {}; nothing to release",
+                since_tag.as_deref().unwrap_or("the first commit")
+            ),
+            Some(component.id.clone()),
             None,
-            Some(vec![pattern.to_string()]),
         )
     })?;
 
+    let bump = match preid {
+        Some(_) if parse_semver(current_version).is_some_and(|v| !v.pre.is_empty()) => {
+            "prerelease".to_string()
+        }
+        Some(_) => format!("pre{}", inferred),
+        None => inferred.to_string(),
+    };
+
+    Ok((
+        bump.clone(),
+        Some(AutoBump {
+            bump_type: bump,
+            since_tag,
+            commits: deciding,
+        }),
+    ))
+}
+
+/// Bump implied by conventional commits: `major` for breaking changes
+/// (`minor` when `breaking_as_minor`), `minor` for features, `patch` otherwise.
+/// Returns the bump and the commits that decided it; `None` without commits.
+pub fn infer_bump(
+    commits: &[CommitInfo],
+    breaking_as_minor: bool,
+) -> Option<(&'static str, Vec<CommitInfo>)> {
+    if commits.is_empty() {
+        return None;
+    }
+
+    let of = |category: CommitCategory| -> Vec<CommitInfo> {
+        commits
+            .iter()
+            .filter(|c| c.category == category)
+            .cloned()
+            .collect()
+    };
+
+    let breaking = of(CommitCategory::Breaking);
+    if !breaking.is_empty() {
+        return Some((if breaking_as_minor { "minor" } else { "major" }, breaking));
+    }
+    let features = of(CommitCategory::Feature);
+    if !features.is_empty() {
+        return Some(("minor", features));
+    }
+    Some(("patch", commits.to_vec()))
+}
+
+/// Lenient semver parse: accepts a leading `v` and pads `1.2` to `1.2.0`.
+pub fn parse_semver(version: &str) -> Option<semver::Version> {
+    let trimmed = version.trim().trim_start_matches(['v', 'V']);
+    if let Ok(parsed) = semver::Version::parse(trimmed) {
+        return Some(parsed);
+    }
+    let parts = trimmed.split('.').count();
+    match parts {
+        1 => semver::Version::parse(&format!("{}.0.0", trimmed)).ok(),
+        2 => semver::Version::parse(&format!("{}.0", trimmed)).ok(),
+        _ => None,
+    }
+}
+
+/// Compare two versions by semver precedence (build metadata ignored).
+/// `None` if either is not a recognizable version.
+pub fn compare_versions(a: &str, b: &str) -> Option<std::cmp::Ordering> {
+    Some(parse_semver(a)?.cmp_precedence(&parse_semver(b)?))
+}
+
+/// Update version in a file, rewriting only the located version strings so
+/// the rest of the file keeps its formatting. Returns the number of
+/// replacements made.
+pub fn update_version_in_file(
+    path: &str,
+    locator: &VersionLocator,
+    old_version: &str,
+    new_version: &str,
+) -> Result<usize> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| Error::internal_io(e.to_string(), Some("read version file".to_string())))?;
+
+    let versions = locator.read(&content)?;
+
     if versions.is_empty() {
         return Err(Error::internal_unexpected(format!(
             "Could not find version in {}",
@@ -185,15 +456,7 @@ pub fn update_version_in_file(
         }
     }
 
-    let (new_content, replaced_count) = replace_versions(&content, pattern, new_version)
-        .ok_or_else(|| {
-            Error::validation_invalid_argument(
-                "versionPattern",
-                format!("Invalid version regex pattern '{}'", pattern),
-                None,
-                Some(vec![pattern.to_string()]),
-            )
-        })?;
+    let (new_content, replaced_count) = locator.replace(&content, new_version)?;
 
     fs::write(path, &new_content)
         .map_err(|e| Error::internal_io(e.to_string(), Some("write version file".to_string())))?;
@@ -214,13 +477,13 @@ pub fn get_component_version(component: &Component) -> Option<String> {
 pub fn read_local_version(local_path: &str, version_target: &VersionTarget) -> Option<String> {
     let path = resolve_version_file_path(local_path, &version_target.file);
     let content = local_files::local().read(Path::new(&path)).ok()?;
+    parse_target_version(&content, version_target)
+}
 
-    let pattern: String = version_target
-        .pattern
-        .clone()
-        .or_else(|| default_pattern_for_file(&version_target.file))?;
-
-    parse_version(&content, &pattern)
+/// Parse the first version a target locates in already-read file content.
+pub fn parse_target_version(content: &str, version_target: &VersionTarget) -> Option<String> {
+    let locator = resolve_target_locator(version_target).ok()?;
+    locator.read(content).ok()?.into_iter().next()
 }
 
 /// Resolve version file path (absolute or relative to local_path)
@@ -260,25 +523,70 @@ pub struct BumpResult {
     pub changelog_path: String,
     pub changelog_finalized: bool,
     pub changelog_changed: bool,
+    /// `changelog_mirror_target` that received the finalized section
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog_mirror_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_bump: Option<AutoBump>,
 }
 
-/// Resolve pattern for a version target, using explicit pattern or module default.
-fn resolve_target_pattern(target: &VersionTarget) -> Result<String> {
-    target
-        .pattern
-        .clone()
-        .or_else(|| default_pattern_for_file(&target.file))
-        .ok_or_else(|| {
-            Error::validation_invalid_argument(
-                "versionTargets[].pattern",
-                format!(
-                    "No version pattern configured for '{}' and no module provides one",
-                    target.file
-                ),
-                None,
-                None,
-            )
-        })
+/// Bump type chosen by `auto` and the commits that decided it.
+#[derive(Debug, Clone, Serialize)]
+
+pub struct AutoBump {
+    pub bump_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since_tag: Option<String>,
+    /// Commits whose category decided the bump (all breaking commits for a
+    /// major bump, all features for minor, and so on)
+    pub commits: Vec<CommitInfo>,
+}
+
+/// Resolve how to locate a target's version: its `pattern`, `jsonPointer`,
+/// `tomlKey` or `yamlKey` (at most one may be set), else the module default
+/// pattern for the file.
+pub fn resolve_target_locator(target: &VersionTarget) -> Result<VersionLocator> {
+    let mut configured: Vec<VersionLocator> = [
+        target.pattern.clone().map(VersionLocator::Pattern),
+        target.json_pointer.clone().map(VersionLocator::JsonPointer),
+        target.toml_key.clone().map(VersionLocator::TomlKey),
+        target.yaml_key.clone().map(VersionLocator::YamlKey),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if configured.len() > 1 {
+        return Err(Error::validation_invalid_argument(
+            "versionTargets[]",
+            format!(
+                "Version target '{}' sets more than one of pattern, jsonPointer, tomlKey and yamlKey",
+                target.file
+            ),
+            None,
+            None,
+        ));
+    }
+
+    match configured.pop() {
+        Some(locator) => Ok(locator),
+        None => default_pattern_for_file(&target.file)
+            .map(VersionLocator::Pattern)
+            .ok_or_else(|| {
+                Error::validation_invalid_argument(
+                    "versionTargets[].pattern",
+                    format!(
+                        "No version pattern configured for '{}' and no module provides one",
+                        target.file
+                    ),
+                    None,
+                    Some(vec![
+                        "Set a pattern, jsonPointer, tomlKey or yamlKey on the version target"
+                            .to_string(),
+                    ]),
+                )
+            }),
+    }
 }
 
 /// Result of validating and finalizing changelog for a version operation.
@@ -287,6 +595,7 @@ pub struct ChangelogValidationResult {
     pub changelog_path: String,
     pub changelog_finalized: bool,
     pub changelog_changed: bool,
+    pub changelog_mirror_path: Option<String>,
 }
 
 /// Validate and finalize changelog for a version operation.
@@ -298,9 +607,8 @@ pub fn validate_and_finalize_changelog(
     new_version: &str,
 ) -> Result<ChangelogValidationResult> {
     let settings = changelog::resolve_effective_settings(Some(component));
-    let changelog_path = changelog::resolve_changelog_path(component)?;
-
-    let changelog_content = local_files::local().read(&changelog_path)?;
+    let changelog_file = changelog::read_changelog(component)?;
+    let changelog_content = changelog_file.markdown();
 
     let latest_changelog_version = changelog::get_latest_finalized_version(&changelog_content)
         .ok_or_else(|| {
@@ -343,20 +651,32 @@ pub fn validate_and_finalize_changelog(
     )?;
 
     if changelog_changed {
-        local_files::local().write(&changelog_path, &finalized_changelog)?;
+        changelog_file.write(&finalized_changelog)?;
     }
 
+    let mirror = changelog::mirror_finalized_section(component, &finalized_changelog, new_version)?;
+
     Ok(ChangelogValidationResult {
-        changelog_path: changelog_path.to_string_lossy().to_string(),
+        changelog_path: changelog_file.path.to_string_lossy().to_string(),
         changelog_finalized: true,
         changelog_changed,
+        changelog_mirror_path: mirror.map(|(path, _)| path.to_string_lossy().to_string()),
     })
 }
 
 /// Build a detailed error for version parsing failures
-fn build_version_parse_error(file: &str, pattern: &str, content: &str) -> Error {
+fn build_version_parse_error(file: &str, locator: &VersionLocator, content: &str) -> Error {
     let preview: String = content.chars().take(500).collect();
 
+    let VersionLocator::Pattern(pattern) = locator else {
+        return Error::internal_unexpected(format!(
+            "Could not find a version string at {} in {}\n\nFile preview (first 500 chars):\n{}",
+            locator.describe(),
+            file,
+            preview
+        ));
+    };
+
     let mut hints = Vec::new();
 
     if pattern.contains("\\\\s") || pattern.contains("\\\\d") {
@@ -399,23 +719,16 @@ pub fn read_component_version(component: &Component) -> Result<ComponentVersionI
     }
 
     let primary = &targets[0];
-    let primary_pattern = resolve_target_pattern(primary)?;
+    let primary_locator = resolve_target_locator(primary)?;
     let primary_full_path = resolve_version_file_path(&component.local_path, &primary.file);
 
     let content = local_files::local().read(Path::new(&primary_full_path))?;
-    let versions = parse_versions(&content, &primary_pattern).ok_or_else(|| {
-        Error::validation_invalid_argument(
-            "versionPattern",
-            format!("Invalid version regex pattern '{}'", primary_pattern),
-            None,
-            Some(vec![primary_pattern.clone()]),
-        )
-    })?;
+    let versions = primary_locator.read(&content)?;
 
     if versions.is_empty() {
         return Err(build_version_parse_error(
             &primary.file,
-            &primary_pattern,
+            &primary_locator,
             &content,
         ));
     }
@@ -435,7 +748,7 @@ pub fn read_component_version(component: &Component) -> Result<ComponentVersionI
         version,
         targets: vec![VersionTargetInfo {
             file: primary.file.clone(),
-            pattern: primary_pattern,
+            pattern: primary_locator.describe(),
             full_path: primary_full_path,
             match_count: versions.len(),
         }],
@@ -471,6 +784,8 @@ pub struct SetResult {
     pub changelog_path: String,
     pub changelog_finalized: bool,
     pub changelog_changed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog_mirror_path: Option<String>,
 }
 
 /// Set a component's version directly (without incrementing).
@@ -490,23 +805,16 @@ pub fn set_component_version(component: &Component, new_version: &str) -> Result
 
     // Read current version from primary target
     let primary = &targets[0];
-    let primary_pattern = resolve_target_pattern(primary)?;
+    let primary_locator = resolve_target_locator(primary)?;
     let primary_full_path = resolve_version_file_path(&component.local_path, &primary.file);
 
     let primary_content = local_files::local().read(Path::new(&primary_full_path))?;
-    let primary_versions = parse_versions(&primary_content, &primary_pattern).ok_or_else(|| {
-        Error::validation_invalid_argument(
-            "versionPattern",
-            format!("Invalid version regex pattern '{}'", primary_pattern),
-            None,
-            Some(vec![primary_pattern.clone()]),
-        )
-    })?;
+    let primary_versions = primary_locator.read(&primary_content)?;
 
     if primary_versions.is_empty() {
         return Err(build_version_parse_error(
             &primary.file,
-            &primary_pattern,
+            &primary_locator,
             &primary_content,
         ));
     }
@@ -530,18 +838,11 @@ pub fn set_component_version(component: &Component, new_version: &str) -> Result
     let mut target_infos = Vec::new();
 
     for target in targets {
-        let version_pattern = resolve_target_pattern(target)?;
+        let locator = resolve_target_locator(target)?;
         let full_path = resolve_version_file_path(&component.local_path, &target.file);
         let content = local_files::local().read(Path::new(&full_path))?;
 
-        let versions = parse_versions(&content, &version_pattern).ok_or_else(|| {
-            Error::validation_invalid_argument(
-                "versionPattern",
-                format!("Invalid version regex pattern '{}'", version_pattern),
-                None,
-                Some(vec![version_pattern.clone()]),
-            )
-        })?;
+        let versions = locator.read(&content)?;
 
         if versions.is_empty() {
             return Err(Error::internal_unexpected(format!(
@@ -571,7 +872,7 @@ pub fn set_component_version(component: &Component, new_version: &str) -> Result
         let match_count = versions.len();
 
         let replaced_count =
-            update_version_in_file(&full_path, &version_pattern, &old_version, new_version)?;
+            update_version_in_file(&full_path, &locator, &old_version, new_version)?;
 
         if replaced_count != match_count {
             return Err(Error::internal_unexpected(format!(
@@ -582,7 +883,7 @@ pub fn set_component_version(component: &Component, new_version: &str) -> Result
 
         target_infos.push(VersionTargetInfo {
             file: target.file.clone(),
-            pattern: version_pattern,
+            pattern: locator.describe(),
             full_path,
             match_count,
         });
@@ -595,6 +896,7 @@ pub fn set_component_version(component: &Component, new_version: &str) -> Result
         changelog_path: changelog_validation.changelog_path,
         changelog_finalized: changelog_validation.changelog_finalized,
         changelog_changed: changelog_validation.changelog_changed,
+        changelog_mirror_path: changelog_validation.changelog_mirror_path,
     })
 }
 
@@ -608,8 +910,12 @@ pub fn set_version(component_id: Option<&str>, new_version: &str) -> Result<SetR
 }
 
 /// Bump a component's version and finalize changelog.
-/// bump_type: "patch", "minor", or "major"
-pub fn bump_component_version(component: &Component, bump_type: &str) -> Result<BumpResult> {
+/// bump_type: see [`next_version`]; `preid` names prerelease bumps.
+pub fn bump_component_version(
+    component: &Component,
+    bump_type: &str,
+    preid: Option<&str>,
+) -> Result<BumpResult> {
     let targets = component
         .version_targets
         .as_ref()
@@ -625,23 +931,16 @@ pub fn bump_component_version(component: &Component, bump_type: &str) -> Result<
 
     // Read current version from primary target
     let primary = &targets[0];
-    let primary_pattern = resolve_target_pattern(primary)?;
+    let primary_locator = resolve_target_locator(primary)?;
     let primary_full_path = resolve_version_file_path(&component.local_path, &primary.file);
 
     let primary_content = local_files::local().read(Path::new(&primary_full_path))?;
-    let primary_versions = parse_versions(&primary_content, &primary_pattern).ok_or_else(|| {
-        Error::validation_invalid_argument(
-            "versionPattern",
-            format!("Invalid version regex pattern '{}'", primary_pattern),
-            None,
-            Some(vec![primary_pattern.clone()]),
-        )
-    })?;
+    let primary_versions = primary_locator.read(&primary_content)?;
 
     if primary_versions.is_empty() {
         return Err(build_version_parse_error(
             &primary.file,
-            &primary_pattern,
+            &primary_locator,
             &primary_content,
         ));
     }
@@ -656,14 +955,13 @@ pub fn bump_component_version(component: &Component, bump_type: &str) -> Result<
     }
 
     let old_version = primary_versions[0].clone();
-    let new_version = increment_version(&old_version, bump_type).ok_or_else(|| {
-        Error::validation_invalid_argument(
-            "version",
-            format!("Invalid version format: {}", old_version),
-            None,
-            Some(vec![old_version.clone()]),
-        )
-    })?;
+    let (bump_type, auto_bump) = resolve_bump_type(component, &old_version, bump_type, preid)?;
+    let new_version = next_version(
+        &old_version,
+        &bump_type,
+        preid,
+        component.version_scheme.unwrap_or_default(),
+    )?;
 
     let changelog_validation =
         validate_and_finalize_changelog(component, &old_version, &new_version)?;
@@ -672,18 +970,11 @@ pub fn bump_component_version(component: &Component, bump_type: &str) -> Result<
     let mut target_infos = Vec::new();
 
     for target in targets {
-        let version_pattern = resolve_target_pattern(target)?;
+        let locator = resolve_target_locator(target)?;
         let full_path = resolve_version_file_path(&component.local_path, &target.file);
         let content = local_files::local().read(Path::new(&full_path))?;
 
-        let versions = parse_versions(&content, &version_pattern).ok_or_else(|| {
-            Error::validation_invalid_argument(
-                "versionPattern",
-                format!("Invalid version regex pattern '{}'", version_pattern),
-                None,
-                Some(vec![version_pattern.clone()]),
-            )
-        })?;
+        let versions = locator.read(&content)?;
 
         if versions.is_empty() {
             return Err(Error::internal_unexpected(format!(
@@ -713,7 +1004,7 @@ pub fn bump_component_version(component: &Component, bump_type: &str) -> Result<
         let match_count = versions.len();
 
         let replaced_count =
-            update_version_in_file(&full_path, &version_pattern, &old_version, &new_version)?;
+            update_version_in_file(&full_path, &locator, &old_version, &new_version)?;
 
         if replaced_count != match_count {
             return Err(Error::internal_unexpected(format!(
@@ -724,7 +1015,7 @@ pub fn bump_component_version(component: &Component, bump_type: &str) -> Result<
 
         target_infos.push(VersionTargetInfo {
             file: target.file.clone(),
-            pattern: version_pattern,
+            pattern: locator.describe(),
             full_path,
             match_count,
         });
@@ -739,11 +1030,17 @@ pub fn bump_component_version(component: &Component, bump_type: &str) -> Result<
         changelog_path: changelog_validation.changelog_path,
         changelog_finalized: changelog_validation.changelog_finalized,
         changelog_changed: changelog_validation.changelog_changed,
+        changelog_mirror_path: changelog_validation.changelog_mirror_path,
+        auto_bump,
     })
 }
 
 /// Bump version by component ID.
-pub fn bump_version(component_id: Option<&str>, bump_type: &str) -> Result<BumpResult> {
+pub fn bump_version(
+    component_id: Option<&str>,
+    bump_type: &str,
+    preid: Option<&str>,
+) -> Result<BumpResult> {
     let id = component_id.ok_or_else(|| {
         Error::validation_invalid_argument(
             "componentId",
@@ -757,24 +1054,32 @@ pub fn bump_version(component_id: Option<&str>, bump_type: &str) -> Result<BumpR
         )
     })?;
     let component = component::load(id)?;
-    bump_component_version(&component, bump_type)
+    bump_component_version(&component, bump_type, preid)
 }
 
 /// Detect version targets in a directory by checking for well-known version files.
-pub fn detect_version_targets(base_path: &str) -> Result<Vec<(String, String, String)>> {
-    let mut found = Vec::new();
+/// Returns each proposed target with the full path of its file.
+pub fn detect_version_targets(base_path: &str) -> Result<Vec<(VersionTarget, String)>> {
+    let mut found: Vec<(VersionTarget, String)> = Vec::new();
 
     // Load version candidates from configurable defaults
     let version_candidates = defaults::load_defaults().version_candidates;
 
-    // Check well-known files first
+    // Check well-known files first; the first candidate that reads a version
+    // wins for each file
     for candidate in &version_candidates {
+        if found
+            .iter()
+            .any(|(target, _)| target.file == candidate.file)
+        {
+            continue;
+        }
         let full_path = format!("{}/{}", base_path, candidate.file);
         if Path::new(&full_path).exists() {
             let content = fs::read_to_string(&full_path).ok();
             if let Some(content) = content {
-                if parse_version(&content, &candidate.pattern).is_some() {
-                    found.push((candidate.file.clone(), candidate.pattern.clone(), full_path));
+                if parse_target_version(&content, candidate).is_some() {
+                    found.push((candidate.clone(), full_path));
                 }
             }
         }
@@ -782,7 +1087,8 @@ pub fn detect_version_targets(base_path: &str) -> Result<Vec<(String, String, St
 
     // Check for PHP plugin files (*.php with Version: header)
     if let Ok(entries) = fs::read_dir(base_path) {
-        let php_pattern = r"Version:\s*(\d+\.\d+\.\d+)";
+        let php_pattern = format!(r"Version:\s*({})", VERSION_REGEX);
+        let php_pattern = php_pattern.as_str();
         for entry in entries.filter_map(|e| e.ok()) {
             let path = entry.path();
             if path.extension().is_some_and(|ext| ext == "php") {
@@ -796,8 +1102,11 @@ pub fn detect_version_targets(base_path: &str) -> Result<Vec<(String, String, St
                             .and_then(|n| n.to_str())
                             .unwrap_or("unknown.php");
                         found.push((
-                            filename.to_string(),
-                            php_pattern.to_string(),
+                            VersionTarget {
+                                file: filename.to_string(),
+                                pattern: Some(php_pattern.to_string()),
+                                ..Default::default()
+                            },
                             path.to_string_lossy().to_string(),
                         ));
                     }
@@ -808,3 +1117,105 @@ pub fn detect_version_targets(base_path: &str) -> Result<Vec<(String, String, St
 
     Ok(found)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bump(version: &str, bump_type: &str, preid: Option<&str>) -> String {
+        next_version(version, bump_type, preid, VersionScheme::Semver).unwrap()
+    }
+
+    fn commit(subject: &str, category: CommitCategory) -> CommitInfo {
+        CommitInfo {
+            hash: "abc1234".to_string(),
+            subject: subject.to_string(),
+            category,
+        }
+    }
+
+    #[test]
+    fn infers_bump_from_commit_categories() {
+        let fix = commit("fix: Handle empty tag", CommitCategory::Fix);
+        let feat = commit("feat: Add export", CommitCategory::Feature);
+        let breaking = commit("feat!: Drop v1 config", CommitCategory::Breaking);
+
+        assert!(infer_bump(&[], false).is_none());
+        assert_eq!(
+            infer_bump(std::slice::from_ref(&fix), false).unwrap().0,
+            "patch"
+        );
+
+        let (bump, deciding) = infer_bump(&[fix.clone(), feat.clone()], false).unwrap();
+        assert_eq!(bump, "minor");
+        assert_eq!(deciding.len(), 1);
+        assert_eq!(deciding[0].subject, feat.subject);
+
+        let all = [fix, feat, breaking];
+        assert_eq!(infer_bump(&all, false).unwrap().0, "major");
+        assert_eq!(infer_bump(&all, true).unwrap().0, "minor");
+    }
+
+    #[test]
+    fn bumps_prereleases() {
+        assert_eq!(bump("1.2.3", "preminor", Some("beta")), "1.3.0-beta.0");
+        assert_eq!(bump("1.3.0-beta.0", "prerelease", None), "1.3.0-beta.1");
+        assert_eq!(bump("1.3.0-beta.1", "prerelease", Some("rc")), "1.3.0-rc.0");
+        assert_eq!(bump("1.2.3", "prerelease", None), "1.2.4-0");
+        assert_eq!(bump("1.2.3", "premajor", Some("alpha")), "2.0.0-alpha.0");
+        assert_eq!(bump("1.2.3+build.7", "prepatch", Some("rc")), "1.2.4-rc.0");
+    }
+
+    #[test]
+    fn release_bumps_finish_prereleases() {
+        assert_eq!(bump("1.3.0-rc.2", "release", None), "1.3.0");
+        assert_eq!(bump("1.3.0-rc.2", "minor", None), "1.3.0");
+        assert_eq!(bump("2.0.0-rc.1", "major", None), "2.0.0");
+        assert_eq!(bump("1.3.1-rc.1", "minor", None), "1.4.0");
+        assert_eq!(bump("1.2.3", "patch", None), "1.2.4");
+        assert!(next_version("1.2.3", "release", None, VersionScheme::Semver).is_err());
+        assert!(next_version("1.2", "patch", None, VersionScheme::Semver).is_err());
+    }
+
+    #[test]
+    fn calver_increments_micro_within_a_month() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        assert_eq!(increment_calver("2026.3.4", today).unwrap(), "2026.3.5");
+        assert_eq!(increment_calver("2026.2.9", today).unwrap(), "2026.3.0");
+        assert_eq!(increment_calver("1.4.2", today).unwrap(), "2026.3.0");
+    }
+
+    #[test]
+    fn calver_accepts_zero_padded_versions() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        assert_eq!(increment_calver("2026.03.4", today).unwrap(), "2026.03.5");
+        assert_eq!(increment_calver("2026.03.09", today).unwrap(), "2026.03.10");
+        assert_eq!(increment_calver("2026.02.9", today).unwrap(), "2026.03.0");
+        assert_eq!(increment_calver("2026.04.1", today).unwrap(), "2026.04.2");
+    }
+
+    #[test]
+    fn compares_by_semver_precedence() {
+        use std::cmp::Ordering;
+        assert_eq!(compare_versions("1.10.0", "1.9.0"), Some(Ordering::Greater));
+        assert_eq!(
+            compare_versions("1.0.0-rc.1", "1.0.0"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("v1.2", "1.2.0+build"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(compare_versions("latest", "1.0.0"), None);
+    }
+
+    #[test]
+    fn version_regex_captures_prerelease_and_build() {
+        let pattern = format!(r#""version"\s*:\s*"({})""#, VERSION_REGEX);
+        let content = r#"{ "version": "1.3.0-beta.2+sha.5" }"#;
+        assert_eq!(
+            parse_version(content, &pattern).as_deref(),
+            Some("1.3.0-beta.2+sha.5")
+        );
+    }
+}
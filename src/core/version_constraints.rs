@@ -0,0 +1,259 @@
+//! Cross-component version constraints.
+//!
+//! A component lists the components it needs in `dependsOn`, each with a
+//! semver range (`>=2.3`, `^1.4.0`, `~0.9`). Dependencies are resolved within
+//! a project's component set: `version check` compares local versions, while
+//! deploy and release preflight compare what will be deployed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::component::{self, Component};
+use crate::error::{Error, Result};
+use crate::project;
+use crate::version;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+
+pub struct ComponentDependency {
+    /// ID of the required component
+    pub component: String,
+    /// Semver range the required component's version must satisfy
+    pub range: String,
+}
+
+/// Outcome of checking one `dependsOn` entry.
+#[derive(Debug, Clone, Serialize)]
+
+pub struct DependencyCheck {
+    pub component_id: String,
+    pub dependency: String,
+    pub range: String,
+    /// Version the dependency resolved to, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub satisfied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub problem: Option<String>,
+}
+
+/// Check a component's `dependsOn` entries against the versions of the other
+/// components in its project. `members` are the project's component IDs and
+/// `versions` maps component IDs to the versions to check against.
+pub fn check_component(
+    component: &Component,
+    members: &[String],
+    versions: &HashMap<String, String>,
+) -> Vec<DependencyCheck> {
+    component
+        .depends_on
+        .iter()
+        .flatten()
+        .map(|dep| {
+            let version = versions.get(&dep.component).cloned();
+            let problem = dependency_problem(dep, members, version.as_deref());
+            DependencyCheck {
+                component_id: component.id.clone(),
+                dependency: dep.component.clone(),
+                range: dep.range.clone(),
+                version,
+                satisfied: problem.is_none(),
+                problem,
+            }
+        })
+        .collect()
+}
+
+fn dependency_problem(
+    dep: &ComponentDependency,
+    members: &[String],
+    version: Option<&str>,
+) -> Option<String> {
+    let req = match semver::VersionReq::parse(&dep.range) {
+        Ok(req) => req,
+        Err(e) => return Some(format!("invalid range '{}': {}", dep.range, e)),
+    };
+    if !members.contains(&dep.component) {
+        return Some(format!(
+            "{} is not a component of the project",
+            dep.component
+        ));
+    }
+    let Some(version) = version else {
+        return Some(format!("version of {} is unknown", dep.component));
+    };
+    match version::parse_semver(version) {
+        Some(parsed) if req.matches(&parsed) => None,
+        Some(_) => Some(format!(
+            "{} {} does not satisfy {}",
+            dep.component, version, dep.range
+        )),
+        None => Some(format!(
+            "{} version '{}' is not a semantic version",
+            dep.component, version
+        )),
+    }
+}
+
+/// Error refusing to ship when any check is unsatisfied.
+pub fn ensure_satisfied(checks: &[DependencyCheck], action: &str) -> Result<()> {
+    refuse_unless_empty(unsatisfied(checks), action)
+}
+
+/// Error refusing to ship when any project has an unsatisfied check or could
+/// not be checked. Each failure names its project.
+pub fn ensure_projects_satisfied(reports: &[ProjectConstraintReport], action: &str) -> Result<()> {
+    let failures = reports
+        .iter()
+        .flat_map(|report| {
+            let lines = match &report.error {
+                Some(error) => vec![format!("could not be checked: {}", error)],
+                None => unsatisfied(&report.checks),
+            };
+            lines
+                .into_iter()
+                .map(move |line| format!("project {}: {}", report.project_id, line))
+        })
+        .collect();
+    refuse_unless_empty(failures, action)
+}
+
+fn unsatisfied(checks: &[DependencyCheck]) -> Vec<String> {
+    checks
+        .iter()
+        .filter(|check| !check.satisfied)
+        .map(|check| {
+            format!(
+                "{} requires {} {}: {}",
+                check.component_id,
+                check.dependency,
+                check.range,
+                check.problem.as_deref().unwrap_or("unsatisfied")
+            )
+        })
+        .collect()
+}
+
+fn refuse_unless_empty(failures: Vec<String>, action: &str) -> Result<()> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    Err(Error::validation_invalid_argument(
+        "dependsOn",
+        format!(
+            "Refusing to {}: {} dependency constraint(s) not satisfied",
+            action,
+            failures.len()
+        ),
+        None,
+        Some(failures),
+    )
+    .with_hint("Deploy the required component versions first, or adjust `dependsOn` ranges"))
+}
+
+/// Constraint checks for one project's component set.
+#[derive(Debug, Clone, Serialize)]
+
+pub struct ProjectConstraintReport {
+    pub project_id: String,
+    pub checks: Vec<DependencyCheck>,
+    pub passed: bool,
+    /// Why the project could not be checked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Check every component's `dependsOn` in the given project (or all projects)
+/// against the local versions of the project's components.
+pub fn check_projects(project_id: Option<&str>) -> Result<Vec<ProjectConstraintReport>> {
+    let projects = match project_id {
+        Some(id) => vec![project::load(id)?],
+        None => project::list()?,
+    };
+
+    let mut reports = Vec::new();
+    for project in projects {
+        let mut components = Vec::new();
+        let mut versions = HashMap::new();
+        for id in &project.component_ids {
+            let component = component::load(id)?;
+            if let Ok(info) = version::read_component_version(&component) {
+                versions.insert(component.id.clone(), info.version);
+            }
+            components.push(component);
+        }
+
+        let checks: Vec<DependencyCheck> = components
+            .iter()
+            .flat_map(|component| check_component(component, &project.component_ids, &versions))
+            .collect();
+        let passed = checks.iter().all(|check| check.satisfied);
+        reports.push(ProjectConstraintReport {
+            project_id: project.id,
+            checks,
+            passed,
+            error: None,
+        });
+    }
+
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(id: &str, depends_on: &[(&str, &str)]) -> Component {
+        let mut component =
+            Component::new(id.to_string(), String::new(), String::new(), String::new());
+        component.depends_on = Some(
+            depends_on
+                .iter()
+                .map(|(dep, range)| ComponentDependency {
+                    component: dep.to_string(),
+                    range: range.to_string(),
+                })
+                .collect(),
+        );
+        component
+    }
+
+    #[test]
+    fn checks_dependency_ranges() {
+        let theme = component(
+            "theme",
+            &[
+                ("plugin", ">=2.3"),
+                ("api", "~1.4"),
+                ("cache", "^1"),
+                ("other", "*"),
+                ("plugin", "not a range"),
+            ],
+        );
+        let members: Vec<String> = ["theme", "plugin", "api", "cache"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        let versions: HashMap<String, String> = [("plugin", "2.3.1"), ("api", "1.5.0")]
+            .iter()
+            .map(|(id, v)| (id.to_string(), v.to_string()))
+            .collect();
+
+        let checks = check_component(&theme, &members, &versions);
+        let problems: Vec<Option<&str>> = checks.iter().map(|c| c.problem.as_deref()).collect();
+
+        assert!(checks[0].satisfied);
+        assert_eq!(problems[1], Some("api 1.5.0 does not satisfy ~1.4"));
+        assert_eq!(problems[2], Some("version of cache is unknown"));
+        assert_eq!(problems[3], Some("other is not a component of the project"));
+        assert!(problems[4].unwrap().starts_with("invalid range"));
+
+        let err = ensure_satisfied(&checks, "deploy").unwrap_err();
+        assert!(err.details["problem"]
+            .as_str()
+            .unwrap()
+            .contains("4 dependency constraint(s)"));
+        assert!(ensure_satisfied(&checks[..1], "deploy").is_ok());
+    }
+}
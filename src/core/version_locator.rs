@@ -0,0 +1,432 @@
+//! Locating version strings inside version target files.
+//!
+//! A version target either names a regex `pattern` whose first capture group
+//! is the version, or a structured key: a `jsonPointer` (`/version`), a TOML
+//! key path (`package.version`) or a YAML key path (`version`). Every locator
+//! resolves to byte ranges of the version strings in the file, and updates
+//! only rewrite those ranges, so formatting, comments and key order survive.
+//!
+//! The TOML and YAML scanners are line based: they follow `[table]` headers
+//! and indentation-nested `key: value` mappings, which covers manifests like
+//! `Cargo.toml` and `pubspec.yaml`. Inline tables, flow mappings and keys
+//! containing dots are not addressable.
+
+use regex::Regex;
+use serde_json::Value;
+use std::ops::Range;
+
+use crate::config::from_str;
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionLocator {
+    /// Regex whose first capture group is the version
+    Pattern(String),
+    /// RFC 6901 JSON pointer to a string value
+    JsonPointer(String),
+    /// Dotted TOML key path to a string value
+    TomlKey(String),
+    /// Dotted YAML key path to a scalar value
+    YamlKey(String),
+}
+
+impl VersionLocator {
+    /// Human-readable form used in output and errors. Patterns are shown as
+    /// is; structured locators are prefixed with their config key.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Pattern(pattern) => pattern.clone(),
+            Self::JsonPointer(pointer) => format!("jsonPointer:{}", pointer),
+            Self::TomlKey(key) => format!("tomlKey:{}", key),
+            Self::YamlKey(key) => format!("yamlKey:{}", key),
+        }
+    }
+
+    /// Byte ranges of every version string the locator matches.
+    pub fn find(&self, content: &str) -> Result<Vec<Range<usize>>> {
+        match self {
+            Self::Pattern(pattern) => {
+                let re = Regex::new(pattern).map_err(|_| {
+                    Error::validation_invalid_argument(
+                        "versionPattern",
+                        format!("Invalid version regex pattern '{}'", pattern),
+                        None,
+                        Some(vec![pattern.clone()]),
+                    )
+                })?;
+                Ok(re
+                    .captures_iter(content)
+                    .filter_map(|caps| caps.get(1).map(|m| m.range()))
+                    .collect())
+            }
+            Self::JsonPointer(pointer) => {
+                let tokens = pointer_tokens(pointer)?;
+                let json: Value = from_str(content)?;
+                if !json.pointer(pointer).is_some_and(Value::is_string) {
+                    return Ok(Vec::new());
+                }
+                Ok(JsonScanner::new(content)
+                    .find(&tokens)
+                    .into_iter()
+                    .collect())
+            }
+            Self::TomlKey(key) => Ok(find_toml_value(content, &key_path(key))
+                .into_iter()
+                .collect()),
+            Self::YamlKey(key) => Ok(find_yaml_value(content, &key_path(key))
+                .into_iter()
+                .collect()),
+        }
+    }
+
+    /// Every version string the locator matches, in file order.
+    pub fn read(&self, content: &str) -> Result<Vec<String>> {
+        Ok(self
+            .find(content)?
+            .into_iter()
+            .map(|range| content[range].to_string())
+            .collect())
+    }
+
+    /// Replace every matched version string. Returns the new content and the
+    /// number of replacements.
+    pub fn replace(&self, content: &str, new_version: &str) -> Result<(String, usize)> {
+        let ranges = self.find(content)?;
+        let mut out = content.to_string();
+        for range in ranges.iter().rev() {
+            out.replace_range(range.clone(), new_version);
+        }
+        Ok((out, ranges.len()))
+    }
+}
+
+fn pointer_tokens(pointer: &str) -> Result<Vec<String>> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(Error::validation_invalid_argument(
+            "versionTargets[].jsonPointer",
+            format!("JSON pointer '{}' must start with '/'", pointer),
+            None,
+            None,
+        ));
+    };
+    Ok(rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn key_path(key: &str) -> Vec<&str> {
+    key.split('.').map(str::trim).collect()
+}
+
+/// Walks raw JSON text (already validated by serde) to find the span of a
+/// string value without reserializing the document.
+struct JsonScanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> JsonScanner<'a> {
+    fn new(content: &'a str) -> Self {
+        Self {
+            bytes: content.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume a string starting at its opening quote; returns the range of
+    /// its raw contents.
+    fn string(&mut self) -> Range<usize> {
+        self.pos += 1;
+        let start = self.pos;
+        while let Some(b) = self.peek() {
+            match b {
+                b'\\' => self.pos += 2,
+                b'"' => break,
+                _ => self.pos += 1,
+            }
+        }
+        let end = self.pos;
+        self.pos += 1;
+        start..end
+    }
+
+    fn skip_value(&mut self) {
+        self.skip_ws();
+        match self.peek() {
+            Some(b'"') => {
+                self.string();
+            }
+            Some(b'{') | Some(b'[') => {
+                let mut depth = 0usize;
+                while let Some(b) = self.peek() {
+                    match b {
+                        b'"' => {
+                            self.string();
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                self.pos += 1;
+                                return;
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.pos += 1;
+                }
+            }
+            _ => {
+                while self
+                    .peek()
+                    .is_some_and(|b| !matches!(b, b',' | b'}' | b']') && !b.is_ascii_whitespace())
+                {
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Find the string value at `tokens` from the value at the current
+    /// position.
+    fn find(&mut self, tokens: &[String]) -> Option<Range<usize>> {
+        self.skip_ws();
+        let Some((token, rest)) = tokens.split_first() else {
+            return (self.peek() == Some(b'"')).then(|| self.string());
+        };
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+                loop {
+                    self.skip_ws();
+                    if self.peek()? != b'"' {
+                        return None;
+                    }
+                    let key = self.string();
+                    self.skip_ws();
+                    self.pos += 1; // ':'
+                    if &self.bytes[key] == token.as_bytes() {
+                        return self.find(rest);
+                    }
+                    self.skip_value();
+                    self.skip_ws();
+                    if self.peek()? != b',' {
+                        return None;
+                    }
+                    self.pos += 1;
+                }
+            }
+            b'[' => {
+                let index: usize = token.parse().ok()?;
+                self.pos += 1;
+                for _ in 0..index {
+                    self.skip_value();
+                    self.skip_ws();
+                    if self.peek()? != b',' {
+                        return None;
+                    }
+                    self.pos += 1;
+                }
+                self.find(rest)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Strip surrounding quotes from a TOML or YAML key.
+fn unquote_key(key: &str) -> &str {
+    let key = key.trim();
+    key.strip_prefix('"')
+        .and_then(|k| k.strip_suffix('"'))
+        .or_else(|| key.strip_prefix('\'').and_then(|k| k.strip_suffix('\'')))
+        .unwrap_or(key)
+}
+
+/// Range of a quoted value's contents, with `offset` being the byte position
+/// of `value` in the file.
+fn quoted_range(value: &str, offset: usize) -> Option<Range<usize>> {
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let len = value[1..].find(quote)?;
+    Some(offset + 1..offset + 1 + len)
+}
+
+fn find_toml_value(content: &str, path: &[&str]) -> Option<Range<usize>> {
+    let mut table: Option<Vec<String>> = Some(Vec::new());
+    let mut multiline: Option<&str> = None;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim();
+
+        if let Some(delimiter) = multiline {
+            if trimmed.contains(delimiter) {
+                multiline = None;
+            }
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed.starts_with("[[") {
+            // Array of tables entries are not addressable by key path
+            table = None;
+            continue;
+        }
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let header = header.split(']').next().unwrap_or("");
+            table = Some(
+                header
+                    .split('.')
+                    .map(|k| unquote_key(k).to_string())
+                    .collect(),
+            );
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value_trimmed = value.trim_start();
+        for delimiter in ["\"\"\"", "'''"] {
+            if value_trimmed.starts_with(delimiter) && !value_trimmed[3..].contains(delimiter) {
+                multiline = Some(delimiter);
+            }
+        }
+        let Some(table) = &table else {
+            continue;
+        };
+        let full: Vec<&str> = table
+            .iter()
+            .map(String::as_str)
+            .chain(key.split('.').map(unquote_key))
+            .collect();
+        if full == path {
+            let value_offset = line_start + key.len() + 1 + (value.len() - value_trimmed.len());
+            return quoted_range(value_trimmed, value_offset);
+        }
+    }
+    None
+}
+
+fn find_yaml_value(content: &str, path: &[&str]) -> Option<Range<usize>> {
+    let mut parents: Vec<(usize, String)> = Vec::new();
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let body = line.trim_end_matches(['\n', '\r']);
+        let trimmed = body.trim_start();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("---") {
+            continue;
+        }
+        let indent = body.len() - trimmed.len();
+        while parents.last().is_some_and(|(i, _)| *i >= indent) {
+            parents.pop();
+        }
+        if trimmed.starts_with('-') {
+            // Sequence items are not addressable by key path
+            continue;
+        }
+
+        let Some((key, value)) = trimmed
+            .split_once(": ")
+            .or_else(|| trimmed.strip_suffix(':').map(|key| (key, "")))
+        else {
+            continue;
+        };
+        let key = unquote_key(key);
+        let value_trimmed = value.trim_start();
+        let value_trimmed = match value_trimmed.find(" #") {
+            Some(comment) => &value_trimmed[..comment],
+            None => value_trimmed,
+        }
+        .trim_end();
+
+        if value_trimmed.is_empty() {
+            parents.push((indent, key.to_string()));
+            continue;
+        }
+
+        let matches = parents.len() + 1 == path.len()
+            && parents.iter().zip(path).all(|((_, k), p)| k == p)
+            && key == path[path.len() - 1];
+        if matches {
+            let value_offset = line_start + indent + (trimmed.len() - value.trim_start().len());
+            return match quoted_range(value_trimmed, value_offset) {
+                Some(range) => Some(range),
+                None if value_trimmed.starts_with(['|', '>', '{', '[', '&', '*']) => None,
+                None => Some(value_offset..value_offset + value_trimmed.len()),
+            };
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_pointer_replaces_in_place() {
+        let content = "{\n  \"name\": \"demo\",\n    \"version\":\"1.2.0\", \"nested\": {\"a\": [1, {\"version\": \"0.1.0\"}]}\n}\n";
+        let top = VersionLocator::JsonPointer("/version".to_string());
+        let nested = VersionLocator::JsonPointer("/nested/a/1/version".to_string());
+
+        assert_eq!(top.read(content).unwrap(), vec!["1.2.0"]);
+        assert_eq!(nested.read(content).unwrap(), vec!["0.1.0"]);
+
+        let (updated, count) = top.replace(content, "1.3.0").unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(updated, content.replace("1.2.0", "1.3.0"));
+
+        let missing = VersionLocator::JsonPointer("/nested/a".to_string());
+        assert!(missing.read(content).unwrap().is_empty());
+    }
+
+    #[test]
+    fn toml_key_follows_tables() {
+        let content = "[workspace]\nmembers = [\"a\"]\n\n[package]\nname = \"demo\"\nversion = \"0.4.1\" # bumped by release\n\n[dependencies]\nserde = { version = \"1.0\" }\nversion = \"9.9.9\"\n";
+        let locator = VersionLocator::TomlKey("package.version".to_string());
+
+        assert_eq!(locator.read(content).unwrap(), vec!["0.4.1"]);
+        let (updated, _) = locator.replace(content, "0.5.0").unwrap();
+        assert!(updated.contains("version = \"0.5.0\" # bumped by release\n"));
+        assert!(updated.contains("version = \"9.9.9\""));
+
+        let inherited = "[package]\nversion.workspace = true\n";
+        assert!(locator.read(inherited).unwrap().is_empty());
+    }
+
+    #[test]
+    fn yaml_key_follows_indentation() {
+        let content = "name: demo\nversion: 2.0.0+4 # store build\nflutter:\n  version: '3.1.0'\nenvironment:\n  sdk: \">=3.0.0\"\n";
+        let top = VersionLocator::YamlKey("version".to_string());
+        let nested = VersionLocator::YamlKey("flutter.version".to_string());
+
+        assert_eq!(top.read(content).unwrap(), vec!["2.0.0+4"]);
+        assert_eq!(nested.read(content).unwrap(), vec!["3.1.0"]);
+
+        let (updated, _) = nested.replace(content, "3.2.0").unwrap();
+        assert_eq!(updated, content.replace("'3.1.0'", "'3.2.0'"));
+    }
+}
@@ -138,6 +138,7 @@ fn exit_code_for_error(code: ErrorCode) -> i32 {
         | ErrorCode::DeployNoComponentsConfigured
         | ErrorCode::DeployBuildFailed
         | ErrorCode::DeployUploadFailed
+        | ErrorCode::DeployLocked
         | ErrorCode::GitCommandFailed => 20,
 
         ErrorCode::InternalIoError